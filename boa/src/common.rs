@@ -9,6 +9,11 @@ use std::marker::PhantomData;
 use std::sync::Arc;
 
 use itertools::Itertools;
+use serde::de;
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serialize;
+use serde::Serializer;
 
 pub type TeamId = Arc<String>;
 
@@ -20,6 +25,54 @@ pub enum EliminationStatus {
   NonTrivially(BTreeSet<Arc<Team>>),
 }
 
+#[must_use]
+#[derive(Debug, Eq, PartialEq)]
+pub struct CriticalMatches {
+  // NOTE: Opponents the team would be eliminated by losing against.
+  pub must_win: BTreeSet<TeamId>,
+  // NOTE: Other teams' matches as (winning-team, losing-team) pairs that would
+  // eliminate the team if they happen.
+  pub must_not_happen: BTreeSet<(TeamId, TeamId)>,
+  // NOTE: Matches (including the team's own) as ordered team-pairs that would
+  // eliminate the team if drawn.
+  pub must_not_draw: BTreeSet<(TeamId, TeamId)>,
+  constructor_guard: PhantomData<()>,
+}
+
+impl CriticalMatches {
+  /// # Panics
+  #[must_use]
+  pub fn new(
+    must_win: BTreeSet<TeamId>,
+    must_not_happen: BTreeSet<(TeamId, TeamId)>,
+    must_not_draw: BTreeSet<(TeamId, TeamId)>,
+  ) -> Self {
+    assert!(
+      must_not_happen
+        .iter()
+        .all(|(winning_team_name, losing_team_name)| winning_team_name
+          != losing_team_name),
+      "Invalid must-not-happen matches ({:?}).",
+      must_not_happen,
+    );
+    assert!(
+      must_not_draw
+        .iter()
+        .all(|(first_team_name, second_team_name)| first_team_name
+          < second_team_name),
+      "Invalid must-not-draw matches ({:?}).",
+      must_not_draw,
+    );
+
+    Self {
+      must_win,
+      must_not_happen,
+      must_not_draw,
+      constructor_guard: PhantomData,
+    }
+  }
+}
+
 #[must_use]
 #[derive(Debug)]
 pub struct Tournament {
@@ -46,12 +99,28 @@ impl PartialEq for Tournament {
 impl Eq for Tournament {}
 
 impl Tournament {
+  /// # Panics
   #[must_use]
   pub fn new(
     name: &Arc<String>,
     teams: BTreeSet<Arc<Team>>,
     remaining_points: Option<HashMap<(TeamId, TeamId), usize>>,
   ) -> Self {
+    Self::try_new(name, teams, remaining_points)
+      .unwrap_or_else(|message| panic!("{}", message))
+  }
+
+  /// Same as `new()` but returns why the tournament is invalid instead of
+  /// panicking (e.g., for tournaments read from files).
+  ///
+  /// # Errors
+  ///
+  /// The same conditions `new()` panics on.
+  pub fn try_new(
+    name: &Arc<String>,
+    teams: BTreeSet<Arc<Team>>,
+    remaining_points: Option<HashMap<(TeamId, TeamId), usize>>,
+  ) -> Result<Self, String> {
     const NAME_LENGTH_MIN: usize = 1;
     const NAME_LENGTH_MAX: usize = 100;
     // NOTE: Reamining-points counts and teams counts are significant.
@@ -68,89 +137,86 @@ impl Tournament {
     const REMAINING_POINTS_COUNT_MAX: usize =
       max_pair_combinations(TEAMS_COUNT_MAX);
 
-    assert!(
-      name.len() >= NAME_LENGTH_MIN && name.len() <= NAME_LENGTH_MAX,
-      "Invalid name ({:?}).",
-      name
-    );
+    if !(name.len() >= NAME_LENGTH_MIN && name.len() <= NAME_LENGTH_MAX) {
+      return Err(format!("Invalid name ({:?}).", name));
+    }
 
-    assert!(
-      teams.len() >= TEAMS_COUNT_MIN && teams.len() <= TEAMS_COUNT_MAX,
-      "Invalid no. of teams ({:?}).",
-      teams.len()
-    );
+    if !(teams.len() >= TEAMS_COUNT_MIN && teams.len() <= TEAMS_COUNT_MAX) {
+      return Err(format!("Invalid no. of teams ({:?}).", teams.len()));
+    }
 
-    remaining_points
-      .as_ref()
-      .map_or((), |remaining_points_value| {
-        assert!(
-          remaining_points_value.len() >= REMAINING_POINTS_COUNT_MIN
-            && remaining_points_value.len() <= REMAINING_POINTS_COUNT_MAX
-            && remaining_points_value.len()
-              == max_pair_combinations(teams.len()),
+    if let Some(remaining_points_value) = &remaining_points {
+      if !(remaining_points_value.len() >= REMAINING_POINTS_COUNT_MIN
+        && remaining_points_value.len() <= REMAINING_POINTS_COUNT_MAX
+        && remaining_points_value.len() == max_pair_combinations(teams.len()))
+      {
+        return Err(format!(
           "Invalid no. of remaining-points ({:?}, {:?}).",
           remaining_points_value.len(),
           teams.len(),
-        );
-
-        assert!(
-          remaining_points_value.len()
-            == remaining_points_value
-              .keys()
-              .into_iter()
-              .map(|(name1, name2)| (name1.min(name2), name1.max(name2)))
-              .collect::<HashSet<_>>()
-              .len(),
+        ));
+      }
+
+      if remaining_points_value.len()
+        != remaining_points_value
+          .keys()
+          .map(|(name1, name2)| (name1.min(name2), name1.max(name2)))
+          .collect::<HashSet<_>>()
+          .len()
+      {
+        return Err(format!(
           "Duplicate remaining-points entries ({:?}).",
           remaining_points_value,
-        );
-
-        let teams_names: HashSet<&TeamId> =
-          teams.iter().map(|team| &team.name).collect();
-        assert!(
-          remaining_points_value
-            .keys()
-            .into_iter()
-            .all(|(name1, name2)| teams_names.contains(name1)
-              && teams_names.contains(name2)),
+        ));
+      }
+
+      let teams_names: HashSet<&TeamId> =
+        teams.iter().map(|team| &team.name).collect();
+      if !remaining_points_value.keys().all(|(name1, name2)| {
+        teams_names.contains(name1) && teams_names.contains(name2)
+      }) {
+        return Err(format!(
           "Remaining-points includes non-existing teams ({:?}, {:?}).",
-          remaining_points_value,
-          teams,
-        );
-
-        let remaining_points_per_team: HashMap<&TeamId, usize> =
-          remaining_points_value
-            .iter()
-            .flat_map(|((team_name1, team_name2), &remaining)| {
-              vec![(team_name1, remaining), (team_name2, remaining)]
-            })
-            .into_grouping_map()
-            .sum();
-        if remaining_points_value.is_empty() || teams.is_empty() {
-          // NOTE: This is important to check as otherwise parts of the
-          // validation may break/not-run due to empty-collection.
-          assert!(
-            remaining_points_value.is_empty() && teams.is_empty(),
-            "Invalid remaining-points & teams lengths ({:?}, {:?}).",
-            remaining_points_value.len(),
-            teams.len(),
-          );
-        }
-        assert!(
-          teams.iter().all(|team| team.remaining_points
-            == *remaining_points_per_team.get(&team.name).unwrap_or(&0)),
+          remaining_points_value, teams,
+        ));
+      }
+
+      let remaining_points_per_team: HashMap<&TeamId, usize> =
+        remaining_points_value
+          .iter()
+          .flat_map(|((team_name1, team_name2), &remaining)| {
+            vec![(team_name1, remaining), (team_name2, remaining)]
+          })
+          .into_grouping_map()
+          .sum();
+      // NOTE: This is important to check as otherwise parts of the validation
+      // may break/not-run due to empty-collection.
+      if (remaining_points_value.is_empty() || teams.is_empty())
+        && !(remaining_points_value.is_empty() && teams.is_empty())
+      {
+        return Err(format!(
+          "Invalid remaining-points & teams lengths ({:?}, {:?}).",
+          remaining_points_value.len(),
+          teams.len(),
+        ));
+      }
+      if !teams.iter().all(|team| {
+        team.remaining_points
+          == *remaining_points_per_team.get(&team.name).unwrap_or(&0)
+      }) {
+        return Err(format!(
           "Remaining-points conflicts with teams-stats ({:?}, {:?}).",
-          remaining_points_value,
-          teams,
-        );
-      });
+          remaining_points_value, teams,
+        ));
+      }
+    }
 
-    Self {
+    Ok(Self {
       name: Arc::clone(name),
       teams,
       remaining_points,
       constructor_guard: PhantomData,
-    }
+    })
   }
 }
 #[must_use]
@@ -162,6 +228,233 @@ const fn max_pair_combinations(x: usize) -> usize {
   x * (x - 1) / 2
 }
 
+#[must_use]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CompetitionFormat {
+  // NOTE: How many times each pair of teams meets (e.g., 2 for a double
+  // round-robin).
+  pub matches_per_team_pair: usize,
+  pub split: Option<CompetitionSplit>,
+  pub abandoned_match_rule: AbandonedMatchRule,
+  constructor_guard: PhantomData<()>,
+}
+
+// NOTE: Whether the score of an abandoned match stands or the match is replayed
+// (and so is still a remaining match).
+#[must_use]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum AbandonedMatchRule {
+  #[default]
+  Replayed,
+  ResultStands,
+}
+
+// NOTE: After every pair has met `matches_per_team_pair` times, teams are split
+// (by standings) into groups that only play each other afterwards. For
+// example, the Scottish Premiership splits into two groups of 6.
+#[must_use]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CompetitionSplit {
+  // NOTE: Ordered from the top of the standings to the bottom.
+  pub groups_sizes: Vec<usize>,
+  pub matches_per_team_pair: usize,
+  constructor_guard: PhantomData<()>,
+}
+
+const MATCHES_PER_TEAM_PAIR_MIN: usize = 1;
+const MATCHES_PER_TEAM_PAIR_MAX: usize = 4;
+const GROUPS_COUNT_MIN: usize = 2;
+const GROUPS_COUNT_MAX: usize = 10;
+const GROUP_SIZE_MIN: usize = 2;
+
+impl CompetitionFormat {
+  /// # Panics
+  #[must_use]
+  pub fn new(
+    matches_per_team_pair: usize,
+    split: Option<CompetitionSplit>,
+  ) -> Self {
+    assert!(
+      is_matches_per_team_pair_valid(matches_per_team_pair),
+      "Invalid matches per team-pair ({:?}).",
+      matches_per_team_pair,
+    );
+
+    Self {
+      matches_per_team_pair,
+      split,
+      abandoned_match_rule: AbandonedMatchRule::default(),
+      constructor_guard: PhantomData,
+    }
+  }
+
+  /// Same as `new()` but returns `None` instead of panicking (e.g., for
+  /// formats read from files).
+  #[must_use]
+  pub fn try_new(
+    matches_per_team_pair: usize,
+    split: Option<CompetitionSplit>,
+  ) -> Option<Self> {
+    is_matches_per_team_pair_valid(matches_per_team_pair)
+      .then(|| Self::new(matches_per_team_pair, split))
+  }
+
+  #[must_use]
+  pub fn with_abandoned_match_rule(
+    self,
+    abandoned_match_rule: AbandonedMatchRule,
+  ) -> Self {
+    Self {
+      abandoned_match_rule,
+      ..self
+    }
+  }
+}
+impl Default for CompetitionFormat {
+  // NOTE: A double round-robin which is what most leagues use.
+  fn default() -> Self {
+    Self::new(2, None)
+  }
+}
+
+impl CompetitionSplit {
+  /// # Panics
+  #[must_use]
+  pub fn new(groups_sizes: Vec<usize>, matches_per_team_pair: usize) -> Self {
+    assert!(
+      are_groups_sizes_valid(&groups_sizes),
+      "Invalid groups sizes ({:?}).",
+      groups_sizes,
+    );
+    assert!(
+      is_matches_per_team_pair_valid(matches_per_team_pair),
+      "Invalid matches per team-pair ({:?}).",
+      matches_per_team_pair,
+    );
+
+    Self {
+      groups_sizes,
+      matches_per_team_pair,
+      constructor_guard: PhantomData,
+    }
+  }
+
+  /// Same as `new()` but returns `None` instead of panicking (e.g., for
+  /// splits read from files).
+  #[must_use]
+  pub fn try_new(
+    groups_sizes: Vec<usize>,
+    matches_per_team_pair: usize,
+  ) -> Option<Self> {
+    (are_groups_sizes_valid(&groups_sizes)
+      && is_matches_per_team_pair_valid(matches_per_team_pair))
+    .then(|| Self::new(groups_sizes, matches_per_team_pair))
+  }
+}
+
+#[must_use]
+fn is_matches_per_team_pair_valid(matches_per_team_pair: usize) -> bool {
+  (MATCHES_PER_TEAM_PAIR_MIN..=MATCHES_PER_TEAM_PAIR_MAX)
+    .contains(&matches_per_team_pair)
+}
+
+#[must_use]
+fn are_groups_sizes_valid(groups_sizes: &[usize]) -> bool {
+  (GROUPS_COUNT_MIN..=GROUPS_COUNT_MAX).contains(&groups_sizes.len())
+    && groups_sizes.iter().all(|&size| size >= GROUP_SIZE_MIN)
+}
+
+#[must_use]
+#[derive(Debug, Eq, PartialEq)]
+pub enum AdvancementStatus {
+  // NOTE: Guaranteed to finish within the advancing places of its group even
+  // if tied on points with the teams below it.
+  Clinched,
+  Undecided,
+  // NOTE: Can't finish within the advancing places (or the best-placed place
+  // if any) of its group even if it wins all its remaining matches.
+  Eliminated,
+}
+
+#[must_use]
+#[derive(Debug, Eq, PartialEq)]
+pub struct GroupStage {
+  pub name: Arc<String>,
+  // NOTE: Each group is a tournament with its own table (named after it).
+  pub groups: Vec<Tournament>,
+  pub advancing_teams_count: usize,
+  // NOTE: Teams placed right after the advancing places (e.g., third-placed
+  // teams) that also advance when they're among the best across groups.
+  pub best_placed_teams_count: usize,
+  constructor_guard: PhantomData<()>,
+}
+
+impl GroupStage {
+  /// # Panics
+  #[must_use]
+  pub fn new(
+    name: &Arc<String>,
+    groups: Vec<Tournament>,
+    advancing_teams_count: usize,
+    best_placed_teams_count: usize,
+  ) -> Self {
+    const NAME_LENGTH_MIN: usize = 1;
+    const NAME_LENGTH_MAX: usize = 100;
+    const GROUPS_COUNT_MIN: usize = 1;
+    const GROUPS_COUNT_MAX: usize = 100;
+
+    assert!(
+      name.len() >= NAME_LENGTH_MIN && name.len() <= NAME_LENGTH_MAX,
+      "Invalid name ({:?}).",
+      name
+    );
+    assert!(
+      groups.len() >= GROUPS_COUNT_MIN && groups.len() <= GROUPS_COUNT_MAX,
+      "Invalid no. of groups ({:?}).",
+      groups.len()
+    );
+    assert!(
+      groups.iter().map(|group| &group.name).all_unique(),
+      "Duplicate groups names ({:?}).",
+      groups.iter().map(|group| &group.name).collect::<Vec<_>>(),
+    );
+    assert!(
+      groups
+        .iter()
+        .flat_map(|group| group.teams.iter().map(|team| &team.name))
+        .all_unique(),
+      "Teams in multiple groups ({:?}).",
+      groups,
+    );
+    assert!(
+      groups.iter().all(|group| group.remaining_points.is_some()),
+      "Missing groups remaining-points ({:?}).",
+      groups,
+    );
+    assert!(
+      advancing_teams_count >= 1
+        && groups
+          .iter()
+          .all(|group| advancing_teams_count < group.teams.len()),
+      "Invalid advancing teams count ({:?}).",
+      advancing_teams_count,
+    );
+    assert!(
+      best_placed_teams_count < groups.len(),
+      "Invalid best-placed teams count ({:?}).",
+      best_placed_teams_count,
+    );
+
+    Self {
+      name: Arc::clone(name),
+      groups,
+      advancing_teams_count,
+      best_placed_teams_count,
+      constructor_guard: PhantomData,
+    }
+  }
+}
+
 #[must_use]
 #[derive(Debug)]
 pub struct Team {
@@ -175,6 +468,10 @@ pub struct Team {
   pub matches_lost: usize,
   pub earned_points: usize,
   pub remaining_points: usize,
+  // NOTE: Points deducted (negative) or awarded by rulings (see
+  // `Adjustment::Points`) which are kept apart from the earned ones as they
+  // can take a team below zero points.
+  pub points_adjustment: isize,
 
   pub elimination_status: Option<EliminationStatus>,
 
@@ -199,6 +496,7 @@ impl PartialEq for Team {
       && self.matches_lost == other.matches_lost
       && self.earned_points == other.earned_points
       && self.remaining_points == other.remaining_points
+      && self.points_adjustment == other.points_adjustment
       && self.elimination_status == other.elimination_status
   }
 }
@@ -212,7 +510,7 @@ impl PartialOrd for Team {
 impl Ord for Team {
   #[must_use]
   fn cmp(&self, other: &Self) -> Ordering {
-    let ordering = other.earned_points.cmp(&self.earned_points);
+    let ordering = other.get_points().cmp(&self.get_points());
     if ordering != Ordering::Equal {
       return ordering;
     }
@@ -232,6 +530,7 @@ impl Hash for Team {
 }
 
 impl Team {
+  /// # Panics
   #[must_use]
   #[allow(clippy::too_many_arguments)]
   pub fn new(
@@ -246,46 +545,73 @@ impl Team {
     remaining_points: usize,
     elimination_status: Option<EliminationStatus>,
   ) -> Self {
+    Self::try_new(
+      name,
+      rank,
+      matches_played,
+      matches_left,
+      matches_drawn,
+      matches_won,
+      matches_lost,
+      earned_points,
+      remaining_points,
+      elimination_status,
+    )
+    .unwrap_or_else(|message| panic!("{}", message))
+  }
+
+  /// Same as `new()` but returns why the team is invalid instead of panicking
+  /// (e.g., for teams read from files).
+  ///
+  /// # Errors
+  ///
+  /// The same conditions `new()` panics on.
+  #[allow(clippy::too_many_arguments)]
+  pub fn try_new(
+    name: &TeamId,
+    rank: usize,
+    matches_played: usize,
+    matches_left: usize,
+    matches_drawn: usize,
+    matches_won: usize,
+    matches_lost: usize,
+    earned_points: usize,
+    remaining_points: usize,
+    elimination_status: Option<EliminationStatus>,
+  ) -> Result<Self, String> {
     const NAME_LENGTH_MIN: usize = 1;
     const NAME_LENGTH_MAX: usize = 100;
     const ELIMINATING_TEAMS_COUNT_MIN: usize = 1;
     const ELIMINATING_TEAMS_COUNT_MAX: usize = 100;
 
-    assert!(
-      name.len() >= NAME_LENGTH_MIN && name.len() <= NAME_LENGTH_MAX,
-      "Invalid name ({:?}).",
-      name,
-    );
+    if !(name.len() >= NAME_LENGTH_MIN && name.len() <= NAME_LENGTH_MAX) {
+      return Err(format!("Invalid name ({:?}).", name));
+    }
 
-    assert!(
-      (1..=ELIMINATING_TEAMS_COUNT_MAX).contains(&rank),
-      "Invalid rank ({:?}).",
-      rank
-    );
+    if !(1..=ELIMINATING_TEAMS_COUNT_MAX).contains(&rank) {
+      return Err(format!("Invalid rank ({:?}).", rank));
+    }
 
-    assert!(
-      matches_played == matches_drawn + matches_won + matches_lost,
-      "Invalid matches-stats ({:?}, {:?}, {:?}, {:?}).",
-      matches_played,
-      matches_drawn,
-      matches_won,
-      matches_lost,
-    );
+    if matches_played != matches_drawn + matches_won + matches_lost {
+      return Err(format!(
+        "Invalid matches-stats ({:?}, {:?}, {:?}, {:?}).",
+        matches_played, matches_drawn, matches_won, matches_lost,
+      ));
+    }
 
-    assert!(
-      earned_points >= matches_drawn + matches_won,
-      "Invalid earned-points ({:?}, {:?}, {:?}).",
-      earned_points,
-      matches_drawn,
-      matches_won,
-    );
+    if earned_points < matches_drawn + matches_won {
+      return Err(format!(
+        "Invalid earned-points ({:?}, {:?}, {:?}).",
+        earned_points, matches_drawn, matches_won,
+      ));
+    }
 
-    assert!(
-      remaining_points >= matches_left,
-      "Invalid earned-points ({:?}, {:?}).",
-      remaining_points,
-      matches_left,
-    );
+    if remaining_points < matches_left {
+      return Err(format!(
+        "Invalid earned-points ({:?}, {:?}).",
+        remaining_points, matches_left,
+      ));
+    }
 
     match &elimination_status {
       None | Some(EliminationStatus::Not) => {},
@@ -293,24 +619,29 @@ impl Team {
         EliminationStatus::Trivially(eliminating_teams)
         | EliminationStatus::NonTrivially(eliminating_teams),
       ) => {
-        assert!(
-          eliminating_teams.len() >= ELIMINATING_TEAMS_COUNT_MIN
-            && eliminating_teams.len() <= ELIMINATING_TEAMS_COUNT_MAX,
-          "Invalid eliminating-teams count ({:?}).",
-          eliminating_teams.len(),
-        );
-
-        for eliminating_team in eliminating_teams {
-          assert!(
-            eliminating_team.elimination_status.is_none(),
+        if !(eliminating_teams.len() >= ELIMINATING_TEAMS_COUNT_MIN
+          && eliminating_teams.len() <= ELIMINATING_TEAMS_COUNT_MAX)
+        {
+          return Err(format!(
+            "Invalid eliminating-teams count ({:?}).",
+            eliminating_teams.len(),
+          ));
+        }
+
+        if let Some(eliminating_team) =
+          eliminating_teams.iter().find(|eliminating_team| {
+            eliminating_team.elimination_status.is_some()
+          })
+        {
+          return Err(format!(
             "Invalid elimination-team ({:?}).",
             eliminating_team,
-          );
+          ));
         }
       },
     }
 
-    Self {
+    Ok(Self {
       name: Arc::clone(name),
       rank,
       matches_played,
@@ -320,11 +651,21 @@ impl Team {
       matches_lost,
       earned_points,
       remaining_points,
+      points_adjustment: 0,
       elimination_status,
       constructor_guard: PhantomData,
-    }
+    })
   }
 
+  /// Earned points with the points-adjustment (which can be negative).
+  #[must_use]
+  pub fn get_points(&self) -> isize {
+    isize::try_from(self.earned_points)
+      .unwrap_or(isize::MAX)
+      .saturating_add(self.points_adjustment)
+  }
+
+  /// # Panics
   #[must_use]
   pub fn with_rank(team: &Self, rank: usize) -> Self {
     assert!(
@@ -333,18 +674,30 @@ impl Team {
       team.elimination_status,
     );
 
-    Self::new(
-      &team.name,
-      rank,
-      team.matches_played,
-      team.matches_left,
-      team.matches_drawn,
-      team.matches_won,
-      team.matches_lost,
-      team.earned_points,
-      team.remaining_points,
-      None,
-    )
+    Self {
+      points_adjustment: team.points_adjustment,
+      ..Self::new(
+        &team.name,
+        rank,
+        team.matches_played,
+        team.matches_left,
+        team.matches_drawn,
+        team.matches_won,
+        team.matches_lost,
+        team.earned_points,
+        team.remaining_points,
+        None,
+      )
+    }
+  }
+
+  /// # Panics
+  #[must_use]
+  pub fn with_points_adjustment(team: &Self, points_adjustment: isize) -> Self {
+    Self {
+      points_adjustment,
+      ..Self::with_rank(team, team.rank)
+    }
   }
 
   #[must_use]
@@ -360,18 +713,21 @@ impl Team {
           eliminating_teams
             .iter()
             .map(|eliminating_team| {
-              Arc::new(Self::new(
-                &eliminating_team.name,
-                eliminating_team.rank,
-                eliminating_team.matches_played,
-                eliminating_team.matches_left,
-                eliminating_team.matches_drawn,
-                eliminating_team.matches_won,
-                eliminating_team.matches_lost,
-                eliminating_team.earned_points,
-                eliminating_team.remaining_points,
-                None,
-              ))
+              Arc::new(Self {
+                points_adjustment: eliminating_team.points_adjustment,
+                ..Self::new(
+                  &eliminating_team.name,
+                  eliminating_team.rank,
+                  eliminating_team.matches_played,
+                  eliminating_team.matches_left,
+                  eliminating_team.matches_drawn,
+                  eliminating_team.matches_won,
+                  eliminating_team.matches_lost,
+                  eliminating_team.earned_points,
+                  eliminating_team.remaining_points,
+                  None,
+                )
+              })
             })
             .collect()
         },
@@ -386,8 +742,171 @@ impl Team {
       },
     };
 
-    Self::new(
-      &team.name,
+    Self {
+      points_adjustment: team.points_adjustment,
+      ..Self::new(
+        &team.name,
+        team.rank,
+        team.matches_played,
+        team.matches_left,
+        team.matches_drawn,
+        team.matches_won,
+        team.matches_lost,
+        team.earned_points,
+        team.remaining_points,
+        Some(sanitized_elimination_status),
+      )
+    }
+  }
+}
+
+// NOTE: What tournaments (and their teams) are (de)serialized as so that
+// deserialized ones go through the same validation as constructed ones (see
+// `Tournament::try_new()` and `Team::try_new()`).
+#[must_use]
+#[derive(Deserialize, Serialize)]
+struct TournamentJson {
+  name: String,
+  teams: Vec<TeamJson>,
+  // NOTE: A list (ordered by teams' names) as pairs can't be JSON keys.
+  remaining_points: Option<Vec<RemainingPointsJson>>,
+}
+
+#[must_use]
+#[derive(Deserialize, Serialize)]
+struct RemainingPointsJson {
+  first_team: String,
+  second_team: String,
+  points: usize,
+}
+
+#[must_use]
+#[derive(Deserialize, Serialize)]
+struct TeamJson {
+  name: String,
+  rank: usize,
+  matches_played: usize,
+  matches_left: usize,
+  matches_drawn: usize,
+  matches_won: usize,
+  matches_lost: usize,
+  earned_points: usize,
+  remaining_points: usize,
+  points_adjustment: isize,
+  elimination_status: Option<EliminationStatusJson>,
+}
+
+#[must_use]
+#[derive(Deserialize, Serialize)]
+#[serde(tag = "kind", content = "eliminating_teams", rename_all = "snake_case")]
+enum EliminationStatusJson {
+  Not,
+  Trivially(Vec<TeamJson>),
+  NonTrivially(Vec<TeamJson>),
+}
+
+impl From<&Tournament> for TournamentJson {
+  fn from(tournament: &Tournament) -> Self {
+    Self {
+      name: String::clone(&tournament.name),
+      teams: tournament
+        .teams
+        .iter()
+        .map(|team| TeamJson::from(&**team))
+        .collect(),
+      remaining_points: tournament.remaining_points.as_ref().map(
+        |remaining_points| {
+          remaining_points
+            .iter()
+            .map(|((first_team_name, second_team_name), &points)| {
+              RemainingPointsJson {
+                first_team: String::clone(first_team_name),
+                second_team: String::clone(second_team_name),
+                points,
+              }
+            })
+            .sorted_by(|a, b| {
+              (&a.first_team, &a.second_team)
+                .cmp(&(&b.first_team, &b.second_team))
+            })
+            .collect()
+        },
+      ),
+    }
+  }
+}
+
+impl TryFrom<TournamentJson> for Tournament {
+  type Error = String;
+
+  fn try_from(tournament: TournamentJson) -> Result<Self, Self::Error> {
+    let teams_count = tournament.teams.len();
+    let teams: BTreeSet<Arc<Team>> = tournament
+      .teams
+      .into_iter()
+      .map(|team| Team::try_from(team).map(Arc::new))
+      .collect::<Result<_, _>>()?;
+    if teams.iter().map(|team| &team.name).unique().count() != teams_count {
+      return Err(String::from("Duplicate teams."));
+    }
+
+    let remaining_points = tournament
+      .remaining_points
+      .map(|remaining_points| {
+        let remaining_points_count = remaining_points.len();
+        let remaining_points: HashMap<(TeamId, TeamId), usize> =
+          remaining_points
+            .into_iter()
+            .map(|remaining_points| {
+              (
+                (
+                  Arc::new(remaining_points.first_team),
+                  Arc::new(remaining_points.second_team),
+                ),
+                remaining_points.points,
+              )
+            })
+            .collect();
+
+        if remaining_points.len() == remaining_points_count {
+          Ok(remaining_points)
+        } else {
+          Err(String::from("Duplicate remaining-points entries."))
+        }
+      })
+      .transpose()?;
+
+    Self::try_new(&Arc::new(tournament.name), teams, remaining_points)
+  }
+}
+
+impl From<&Team> for TeamJson {
+  fn from(team: &Team) -> Self {
+    Self {
+      name: String::clone(&team.name),
+      rank: team.rank,
+      matches_played: team.matches_played,
+      matches_left: team.matches_left,
+      matches_drawn: team.matches_drawn,
+      matches_won: team.matches_won,
+      matches_lost: team.matches_lost,
+      earned_points: team.earned_points,
+      remaining_points: team.remaining_points,
+      points_adjustment: team.points_adjustment,
+      elimination_status: team
+        .elimination_status
+        .as_ref()
+        .map(EliminationStatusJson::from),
+    }
+  }
+}
+
+impl TryFrom<TeamJson> for Team {
+  type Error = String;
+
+  fn try_from(team: TeamJson) -> Result<Self, Self::Error> {
+    Self::try_new(
+      &Arc::new(team.name),
       team.rank,
       team.matches_played,
       team.matches_left,
@@ -396,7 +915,105 @@ impl Team {
       team.matches_lost,
       team.earned_points,
       team.remaining_points,
-      Some(sanitized_elimination_status),
+      team
+        .elimination_status
+        .map(EliminationStatus::try_from)
+        .transpose()?,
     )
+    .map(|constructed_team| Self {
+      points_adjustment: team.points_adjustment,
+      ..constructed_team
+    })
+  }
+}
+
+impl From<&EliminationStatus> for EliminationStatusJson {
+  fn from(elimination_status: &EliminationStatus) -> Self {
+    let to_json = |eliminating_teams: &BTreeSet<Arc<Team>>| {
+      eliminating_teams
+        .iter()
+        .map(|team| TeamJson::from(&**team))
+        .collect()
+    };
+
+    match elimination_status {
+      EliminationStatus::Not => Self::Not,
+      EliminationStatus::Trivially(eliminating_teams) => {
+        Self::Trivially(to_json(eliminating_teams))
+      },
+      EliminationStatus::NonTrivially(eliminating_teams) => {
+        Self::NonTrivially(to_json(eliminating_teams))
+      },
+    }
+  }
+}
+
+impl TryFrom<EliminationStatusJson> for EliminationStatus {
+  type Error = String;
+
+  fn try_from(
+    elimination_status: EliminationStatusJson,
+  ) -> Result<Self, Self::Error> {
+    let from_json = |eliminating_teams: Vec<TeamJson>| {
+      eliminating_teams
+        .into_iter()
+        .map(|team| Team::try_from(team).map(Arc::new))
+        .collect::<Result<BTreeSet<Arc<Team>>, String>>()
+    };
+
+    Ok(match elimination_status {
+      EliminationStatusJson::Not => Self::Not,
+      EliminationStatusJson::Trivially(eliminating_teams) => {
+        Self::Trivially(from_json(eliminating_teams)?)
+      },
+      EliminationStatusJson::NonTrivially(eliminating_teams) => {
+        Self::NonTrivially(from_json(eliminating_teams)?)
+      },
+    })
+  }
+}
+
+impl Serialize for Tournament {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    TournamentJson::from(self).serialize(serializer)
+  }
+}
+
+impl<'de> Deserialize<'de> for Tournament {
+  fn deserialize<D: Deserializer<'de>>(
+    deserializer: D,
+  ) -> Result<Self, D::Error> {
+    Self::try_from(TournamentJson::deserialize(deserializer)?)
+      .map_err(de::Error::custom)
+  }
+}
+
+impl Serialize for Team {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    TeamJson::from(self).serialize(serializer)
+  }
+}
+
+impl<'de> Deserialize<'de> for Team {
+  fn deserialize<D: Deserializer<'de>>(
+    deserializer: D,
+  ) -> Result<Self, D::Error> {
+    Self::try_from(TeamJson::deserialize(deserializer)?)
+      .map_err(de::Error::custom)
+  }
+}
+
+impl Serialize for EliminationStatus {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    EliminationStatusJson::from(self).serialize(serializer)
+  }
+}
+
+impl<'de> Deserialize<'de> for EliminationStatus {
+  fn deserialize<D: Deserializer<'de>>(
+    deserializer: D,
+  ) -> Result<Self, D::Error> {
+    Self::try_from(EliminationStatusJson::deserialize(deserializer)?)
+      .map_err(de::Error::custom)
   }
 }
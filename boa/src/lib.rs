@@ -3,13 +3,79 @@ mod mincut_maxflow;
 mod tournament;
 
 use std::boxed::Box;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::ffi::CStr;
 use std::ffi::CString;
 use std::os::raw::c_char;
+use std::path::Path;
+use std::path::PathBuf;
 use std::ptr;
+use std::sync::Arc;
+use std::sync::Mutex;
 
-use crate::common::EliminationStatus;
-use crate::common::Team;
-use crate::common::Tournament;
+pub use crate::common::AbandonedMatchRule;
+pub use crate::common::AdvancementStatus;
+pub use crate::common::CompetitionFormat;
+pub use crate::common::CompetitionSplit;
+pub use crate::common::CriticalMatches;
+pub use crate::common::EliminationStatus;
+pub use crate::common::GroupStage;
+pub use crate::common::Team;
+pub use crate::common::TeamId;
+pub use crate::common::Tournament;
+pub use crate::tournament::knockout::KnockoutBracket;
+pub use crate::tournament::knockout::KnockoutLeg;
+pub use crate::tournament::knockout::KnockoutRound;
+pub use crate::tournament::knockout::KnockoutStatus;
+pub use crate::tournament::knockout::KnockoutTie;
+pub use crate::tournament::replay::ReplayedMatchday;
+pub use crate::tournament::replay::TournamentReplay;
+pub use crate::tournament::Adjustment;
+pub use crate::tournament::ConditionalResponse;
+pub use crate::tournament::Discrepancy;
+pub use crate::tournament::DiscrepancyKind;
+pub use crate::tournament::ExportedTournaments;
+pub use crate::tournament::FetchError;
+pub use crate::tournament::FetchFailure;
+pub use crate::tournament::FetchedTournament;
+pub use crate::tournament::Fixture;
+pub use crate::tournament::FixtureStatus;
+pub use crate::tournament::MatchOutcome;
+pub use crate::tournament::MatchResult;
+pub use crate::tournament::MatchStatus;
+pub use crate::tournament::Provider;
+pub use crate::tournament::ProvidersConfig;
+pub use crate::tournament::StaleTournament;
+pub use crate::tournament::Transport;
+pub use crate::tournament::Validators;
+
+// NOTE: Fetched tournaments (before prediction) are kept around so that
+// scenarios can be predicted natively without fetching again.
+static FETCHED_TOURNAMENTS: Mutex<Vec<Tournament>> = Mutex::new(Vec::new());
+// NOTE: Tournaments that failed the last fetch (see `boa_get_fetch_failures`).
+static FETCH_FAILURES: Mutex<Vec<FetchFailure>> = Mutex::new(Vec::new());
+// NOTE: Providers' disagreements in the last fetch (see
+// `boa_get_discrepancies`).
+static DISCREPANCIES: Mutex<Vec<Discrepancy>> = Mutex::new(Vec::new());
+// NOTE: Tournaments served from the cache in the last fetch (see
+// `boa_get_stale_tournaments`).
+static STALE_TOURNAMENTS: Mutex<Vec<StaleTournament>> = Mutex::new(Vec::new());
+// NOTE: Applied to all fetched tournaments (see `set_adjustments_file`).
+static ADJUSTMENTS: Mutex<BTreeMap<Arc<String>, Vec<Adjustment>>> =
+  Mutex::new(BTreeMap::new());
+// NOTE: The default (shipped) config is used until another one is set (see
+// `set_providers_config_file`).
+static PROVIDERS_CONFIG: Mutex<Option<ProvidersConfig>> = Mutex::new(None);
+// NOTE: Nothing is cached until it's set (see `set_cache_dir`).
+static CACHE_DIR: Mutex<Option<PathBuf>> = Mutex::new(None);
+// NOTE: Providers enabled or disabled (by name) on top of the providers' config
+// (see `set_provider_enabled`).
+static PROVIDERS_ENABLED: Mutex<BTreeMap<String, bool>> =
+  Mutex::new(BTreeMap::new());
+// NOTE: Registered after the built-in providers (see `register_provider`).
+static REGISTERED_PROVIDERS: Mutex<Vec<Arc<dyn Provider>>> =
+  Mutex::new(Vec::new());
 
 pub fn test() {
   mincut_maxflow::test();
@@ -41,6 +107,7 @@ pub struct TeamNative {
   matches_lost: u64,
   earned_points: u64,
   remaining_points: u64,
+  points_adjustment: i64,
 
   elimination_status: u64,
   eliminating_teams_count: u64,
@@ -48,8 +115,367 @@ pub struct TeamNative {
 }
 
 #[must_use]
-pub fn get_tournaments() -> Vec<Tournament> {
-  tournament::get_tournaments()
+#[repr(C)]
+pub struct FetchFailureNative {
+  provider_name: *const c_char,
+  tournament_name: *const c_char,
+  message: *const c_char,
+}
+
+#[must_use]
+#[repr(C)]
+pub struct DiscrepancyNative {
+  tournament_name: *const c_char,
+  message: *const c_char,
+}
+
+#[must_use]
+#[repr(C)]
+pub struct StaleTournamentNative {
+  provider_name: *const c_char,
+  tournament_name: *const c_char,
+  fetched_at: i64,
+  message: *const c_char,
+}
+
+#[must_use]
+#[repr(C)]
+pub struct MatchResultNative {
+  first_team_name: *const c_char,
+  first_team_score: u64,
+  second_team_name: *const c_char,
+  second_team_score: u64,
+}
+
+/// Fetches and predicts all tournaments. Tournaments that can't be fetched
+/// (e.g., a provider is down or its response changed) are returned as failures
+/// instead.
+///
+/// Tournaments fetched by more than one provider are reconciled (see
+/// `set_providers_config_file`) and their providers' disagreements are
+/// returned as discrepancies.
+///
+/// Tournaments that can't be refreshed (e.g., offline) but were fetched before
+/// are returned from the cache (see `set_cache_dir`) and as stale.
+///
+/// Blocks on its own runtime (see `get_tournaments_async`) which is shared by
+/// all calls. When called from within another runtime (which can't be
+/// blocked), it blocks on a separate thread instead.
+/// # Panics
+#[must_use]
+pub fn get_tournaments() -> (
+  Vec<Tournament>,
+  Vec<FetchFailure>,
+  Vec<Discrepancy>,
+  Vec<StaleTournament>,
+) {
+  tournament::get_tournaments(
+    &get_providers_config(),
+    &ADJUSTMENTS.lock().unwrap(),
+  )
+}
+
+/// Same as `get_tournaments` but asynchronous. It must run on a multi-threaded
+/// Tokio runtime (with its time and IO drivers enabled) as tournaments are
+/// fetched (and predicted) on its blocking threads.
+///
+/// Requests are limited per host (both concurrently and per second), retried
+/// when they may have failed temporarily and time out (both each and all).
+/// # Panics
+pub async fn get_tournaments_async() -> (
+  Vec<Tournament>,
+  Vec<FetchFailure>,
+  Vec<Discrepancy>,
+  Vec<StaleTournament>,
+) {
+  let adjustments = ADJUSTMENTS.lock().unwrap().clone();
+
+  tournament::get_tournaments_async(&get_providers_config(), &adjustments).await
+}
+
+/// Fetches all tournaments (like `get_tournaments`) as JSON documents (by
+/// tournament-name) in the interchange format instead of predicting them.
+///
+/// The documents can be read back by listing them in the providers' config's
+/// `json` section. Adjustments aren't part of them.
+/// # Panics
+#[must_use]
+pub fn export_tournaments() -> (
+  ExportedTournaments,
+  Vec<FetchFailure>,
+  Vec<Discrepancy>,
+  Vec<StaleTournament>,
+) {
+  tournament::export_tournaments(&get_providers_config())
+}
+
+/// Loads the providers' config (which competitions and seasons are fetched)
+/// from a JSON file with the same format as the default one.
+///
+/// Returns `false` (and keeps the current config) if the file can't be read,
+/// parsed or is invalid.
+/// # Panics
+#[must_use]
+pub fn set_providers_config_file(path: &Path) -> bool {
+  let Some(providers_config) = tournament::read_providers_config(path) else {
+    return false;
+  };
+  *PROVIDERS_CONFIG.lock().unwrap() = Some(providers_config);
+
+  true
+}
+
+/// Sets the directory where fetching caches data (e.g., the discovered current
+/// seasons and tournaments' pages) between runs.
+///
+/// Pages are refreshed after the providers' config's `cache_ttl_seconds` and
+/// are used as is when that fails (see `get_tournaments`).
+///
+/// Tournaments' results are also stored there so that only their newer matches
+/// are fetched afterwards. Stored results that were corrected by their
+/// providers are returned as discrepancies.
+///
+/// Returns `false` (and keeps the current directory) if it isn't a directory.
+/// # Panics
+#[must_use]
+pub fn set_cache_dir(path: &Path) -> bool {
+  if !path.is_dir() {
+    return false;
+  }
+  *CACHE_DIR.lock().unwrap() = Some(path.to_path_buf());
+
+  true
+}
+
+/// Enables or disables fetching a provider (by name, e.g., `Koora`) regardless
+/// of the providers' config (whose `disabled_providers` aren't fetched).
+///
+/// Returns `false` if there's no provider with that name.
+/// # Panics
+#[must_use]
+pub fn set_provider_enabled(provider_name: &str, is_enabled: bool) -> bool {
+  if !get_providers_config()
+    .providers
+    .set_enabled(provider_name, is_enabled)
+  {
+    return false;
+  }
+  PROVIDERS_ENABLED
+    .lock()
+    .unwrap()
+    .insert(String::from(provider_name), is_enabled);
+
+  true
+}
+
+/// Registers a provider (e.g., one of an app's own servers) to be fetched
+/// after the built-in ones and those registered before it.
+///
+/// It's enabled unless it's disabled with `set_provider_enabled` (as the
+/// providers' config's `disabled_providers` only knows the built-in ones).
+///
+/// Returns `false` if a provider with the same name is already registered.
+/// # Panics
+#[must_use]
+pub fn register_provider(provider: Arc<dyn Provider>) -> bool {
+  if !get_providers_config()
+    .providers
+    .register(Arc::clone(&provider))
+  {
+    return false;
+  }
+  REGISTERED_PROVIDERS.lock().unwrap().push(provider);
+
+  true
+}
+
+/// Gets the providers' names (in the order they're fetched in) with whether
+/// each is enabled.
+/// # Panics
+#[must_use]
+pub fn get_providers() -> Vec<(&'static str, bool)> {
+  get_providers_config().providers.get_providers()
+}
+
+/// Gets a team's name in a locale (e.g., `ar`) falling back to its canonical
+/// name (which is what tournaments' teams are named).
+/// # Panics
+#[must_use]
+pub fn get_team_display_name(team_name: &TeamId, locale: &str) -> TeamId {
+  get_providers_config()
+    .teams
+    .get_display_name(team_name, locale)
+}
+
+#[must_use]
+fn get_providers_config() -> ProvidersConfig {
+  let mut providers_config =
+    PROVIDERS_CONFIG.lock().unwrap().clone().unwrap_or_default();
+  providers_config
+    .cache_dir
+    .clone_from(&CACHE_DIR.lock().unwrap());
+  let registered_providers = REGISTERED_PROVIDERS.lock().unwrap().clone();
+  for provider in registered_providers {
+    // NOTE: Only providers with new names are ever registered.
+    let _is_registered = providers_config.providers.register(provider);
+  }
+  let providers_enabled = PROVIDERS_ENABLED.lock().unwrap().clone();
+  for (provider_name, is_enabled) in providers_enabled {
+    // NOTE: Only registered providers are ever set.
+    let _is_registered = providers_config
+      .providers
+      .set_enabled(&provider_name, is_enabled);
+  }
+
+  providers_config
+}
+
+/// Loads adjustments (point deductions, awarded results and voided matches)
+/// from a JSON file to be applied to tournaments fetched afterwards.
+///
+/// Returns `false` (and keeps the current adjustments) if the file can't be
+/// read, parsed or is invalid (e.g., a team playing itself).
+/// # Panics
+#[must_use]
+pub fn set_adjustments_file(path: &Path) -> bool {
+  let Some(adjustments) = tournament::read_adjustments(path) else {
+    return false;
+  };
+  *ADJUSTMENTS.lock().unwrap() = adjustments;
+
+  true
+}
+
+/// Builds a tournament from matches' results (in chronological order).
+///
+/// `teams_names` has to include all teams even those that haven't played yet.
+/// # Panics
+#[must_use]
+pub fn postprocess_tournament(
+  tournament_name: &Arc<String>,
+  teams_names: &BTreeSet<TeamId>,
+  matches_results: &[MatchResult],
+) -> Tournament {
+  tournament::postprocess_tournament(
+    tournament_name,
+    teams_names,
+    matches_results,
+  )
+}
+
+/// Same as `postprocess_tournament` but for tournaments that aren't a double
+/// round-robin (e.g., single round-robin or split leagues).
+/// # Panics
+#[must_use]
+pub fn postprocess_tournament_with_format(
+  tournament_name: &Arc<String>,
+  teams_names: &BTreeSet<TeamId>,
+  matches_results: &[MatchResult],
+  format: &CompetitionFormat,
+) -> Tournament {
+  tournament::postprocess_tournament_with_format(
+    tournament_name,
+    teams_names,
+    matches_results,
+    format,
+  )
+}
+
+/// Applies hypothetical matches' results to a tournament and returns `None`
+/// if any of them can't happen (or the tournament has no remaining-points).
+/// # Panics
+#[must_use]
+pub fn apply_matches_results(
+  tournament: &Tournament,
+  matches_results: &[MatchResult],
+) -> Option<Tournament> {
+  tournament::apply_matches_results(tournament, matches_results)
+}
+
+/// # Panics
+#[must_use]
+pub fn predict_tournament(tournament: &Tournament) -> Tournament {
+  tournament::predict_tournament(tournament)
+}
+
+/// Finds the must-win, must-not-happen and must-not-draw matches of each
+/// non-eliminated team in a tournament (before predicting it).
+///
+/// This predicts each team once per remaining match so it's much slower than
+/// `predict_tournament`. `None` is returned if the tournament has no
+/// remaining-points or was predicted already.
+/// # Panics
+#[must_use]
+pub fn predict_critical_matches(
+  tournament: &Tournament,
+) -> Option<BTreeMap<TeamId, CriticalMatches>> {
+  tournament::predict_critical_matches(tournament)
+}
+
+/// Predicts whether each team in each group (by name) of a group-stage can
+/// still advance, where groups are built like any other tournament (e.g., with
+/// `postprocess_tournament`).
+///
+/// No provider fetches group-stages (e.g., a fetched UEFA Champions League is
+/// a single table) so groups are always built by the caller. `None` is
+/// returned if any group has more than 12 remaining matches (e.g., a 5-team
+/// double round-robin that just started).
+/// # Panics
+#[must_use]
+pub fn predict_group_stage(
+  group_stage: &GroupStage,
+) -> Option<BTreeMap<Arc<String>, BTreeMap<TeamId, AdvancementStatus>>> {
+  tournament::predict_group_stage(group_stage)
+}
+
+/// Predicts which teams are still alive in a knockout-bracket (e.g., cups'
+/// knockout rounds) and which teams they can still meet.
+///
+/// No provider fetches knockout-brackets (e.g., a fetched UEFA Europa League's
+/// knockout matches are counted as league matches) so brackets are always
+/// built by the caller.
+/// # Panics
+#[must_use]
+pub fn predict_knockout_bracket(
+  bracket: &KnockoutBracket,
+) -> BTreeMap<TeamId, KnockoutStatus> {
+  tournament::predict_knockout_bracket(bracket)
+}
+
+/// Sets the number of threads used for predictions which can only be done once
+/// and before any prediction (otherwise `false` is returned). By default, the
+/// number of logical CPUs is used.
+#[cfg(feature = "parallel")]
+#[must_use]
+pub fn set_threads_count(threads_count: usize) -> bool {
+  rayon::ThreadPoolBuilder::new()
+    .num_threads(threads_count)
+    .build_global()
+    .is_ok()
+}
+
+/// Replays a tournament's season from its (chronologically ordered) matches'
+/// results.
+///
+/// The results are the caller's, e.g., those of a tournament that was fetched
+/// or cached before, and are replayed under the tournament's `format` and
+/// `adjustments` (as when fetching). `None` is returned if any of them is
+/// invalid (e.g., a team playing itself or split groups that don't add up to
+/// the teams).
+/// # Panics
+#[must_use]
+pub fn replay_tournament(
+  tournament_name: &Arc<String>,
+  matches_results: &[MatchResult],
+  format: &CompetitionFormat,
+  adjustments: &[Adjustment],
+) -> Option<TournamentReplay> {
+  tournament::replay_tournament(
+    tournament_name,
+    matches_results,
+    format,
+    adjustments,
+  )
 }
 
 /// # Panics
@@ -60,8 +486,344 @@ pub extern "C" fn boa_get_tournaments(
   tournaments_count: *mut u64,
   tournaments: *mut *const TournamentNative,
 ) -> i32 {
-  let local_tournaments = tournament::get_tournaments();
+  let (fetched_tournaments, fetch_failures, discrepancies, stale_tournaments) =
+    tournament::fetch_tournaments(
+      &get_providers_config(),
+      &ADJUSTMENTS.lock().unwrap(),
+    );
+  let local_tournaments = tournament::predict_tournaments(&fetched_tournaments);
+  *FETCHED_TOURNAMENTS.lock().unwrap() = fetched_tournaments;
+  *FETCH_FAILURES.lock().unwrap() = fetch_failures;
+  *DISCREPANCIES.lock().unwrap() = discrepancies;
+  *STALE_TOURNAMENTS.lock().unwrap() = stale_tournaments;
+
+  do_tournaments(local_tournaments, tournaments_count, tournaments);
+
+  0
+}
+
+/// Gets the tournaments that failed the last `boa_get_tournaments` (with a
+/// human-readable message each) which are freed with `boa_free_fetch_failures`.
+/// # Panics
+#[must_use]
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn boa_get_fetch_failures(
+  failures_count: *mut u64,
+  failures: *mut *const FetchFailureNative,
+) -> i32 {
+  let fetch_failures = FETCH_FAILURES.lock().unwrap().clone();
+
+  unsafe {
+    *failures_count = fetch_failures.len() as u64;
+  }
+
+  let failures_native: *const FetchFailureNative = Box::into_raw(
+    fetch_failures
+      .iter()
+      .map(|failure| FetchFailureNative {
+        provider_name: CString::new(failure.provider_name).unwrap().into_raw(),
+        tournament_name: CString::new(&**failure.tournament_name)
+          .unwrap()
+          .into_raw(),
+        message: CString::new(failure.error.to_string()).unwrap().into_raw(),
+      })
+      .collect::<Vec<_>>()
+      .into_boxed_slice(),
+  )
+    as *const FetchFailureNative;
+
+  unsafe {
+    // NOTE: Same as tournaments (see `do_tournaments`).
+    *failures = if *failures_count == 0 {
+      ptr::null()
+    } else {
+      failures_native
+    };
+  }
+
+  0
+}
+
+/// Gets the providers' disagreements in the last `boa_get_tournaments` (with a
+/// human-readable message each) which are freed with `boa_free_discrepancies`.
+/// # Panics
+#[must_use]
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn boa_get_discrepancies(
+  discrepancies_count: *mut u64,
+  discrepancies: *mut *const DiscrepancyNative,
+) -> i32 {
+  let all_discrepancies = DISCREPANCIES.lock().unwrap().clone();
+
+  unsafe {
+    *discrepancies_count = all_discrepancies.len() as u64;
+  }
+
+  let discrepancies_native: *const DiscrepancyNative = Box::into_raw(
+    all_discrepancies
+      .iter()
+      .map(|discrepancy| DiscrepancyNative {
+        tournament_name: CString::new(&**discrepancy.tournament_name)
+          .unwrap()
+          .into_raw(),
+        message: CString::new(discrepancy.to_string()).unwrap().into_raw(),
+      })
+      .collect::<Vec<_>>()
+      .into_boxed_slice(),
+  )
+    as *const DiscrepancyNative;
 
+  unsafe {
+    // NOTE: Same as tournaments (see `do_tournaments`).
+    *discrepancies = if *discrepancies_count == 0 {
+      ptr::null()
+    } else {
+      discrepancies_native
+    };
+  }
+
+  0
+}
+
+/// Gets the tournaments served from the cache in the last `boa_get_tournaments`
+/// (with when they were fetched and a human-readable message each) which are
+/// freed with `boa_free_stale_tournaments`.
+/// # Panics
+#[must_use]
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn boa_get_stale_tournaments(
+  stale_tournaments_count: *mut u64,
+  stale_tournaments: *mut *const StaleTournamentNative,
+) -> i32 {
+  let all_stale_tournaments = STALE_TOURNAMENTS.lock().unwrap().clone();
+
+  unsafe {
+    *stale_tournaments_count = all_stale_tournaments.len() as u64;
+  }
+
+  let stale_tournaments_native: *const StaleTournamentNative = Box::into_raw(
+    all_stale_tournaments
+      .iter()
+      .map(|stale_tournament| StaleTournamentNative {
+        provider_name: CString::new(stale_tournament.provider_name)
+          .unwrap()
+          .into_raw(),
+        tournament_name: CString::new(&**stale_tournament.tournament_name)
+          .unwrap()
+          .into_raw(),
+        fetched_at: stale_tournament.fetched_at,
+        message: CString::new(stale_tournament.error.to_string())
+          .unwrap()
+          .into_raw(),
+      })
+      .collect::<Vec<_>>()
+      .into_boxed_slice(),
+  )
+    as *const StaleTournamentNative;
+
+  unsafe {
+    // NOTE: Same as tournaments (see `do_tournaments`).
+    *stale_tournaments = if *stale_tournaments_count == 0 {
+      ptr::null()
+    } else {
+      stale_tournaments_native
+    };
+  }
+
+  0
+}
+
+/// Sets the number of threads used for predictions (with the `parallel`
+/// feature) which can only be done once and before any prediction.
+///
+/// Returns `1` if that's not possible (e.g., `parallel` isn't enabled).
+/// # Panics
+#[must_use]
+#[no_mangle]
+pub extern "C" fn boa_set_threads_count(threads_count: u64) -> i32 {
+  #[cfg(feature = "parallel")]
+  {
+    i32::from(!set_threads_count(usize::try_from(threads_count).unwrap()))
+  }
+  #[cfg(not(feature = "parallel"))]
+  {
+    let _ = threads_count;
+    1
+  }
+}
+
+/// Loads adjustments from a JSON file to be applied by `boa_get_tournaments`.
+///
+/// Returns `1` if the path isn't UTF-8 or the file can't be read or parsed.
+/// # Panics
+#[must_use]
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn boa_set_adjustments_file(path: *const c_char) -> i32 {
+  let Ok(path) = unsafe { CStr::from_ptr(path) }.to_str() else {
+    return 1;
+  };
+
+  i32::from(!set_adjustments_file(Path::new(path)))
+}
+
+/// Loads the providers' config from a JSON file to be used by
+/// `boa_get_tournaments`.
+///
+/// Returns `1` if the path isn't UTF-8 or the file can't be read, parsed or
+/// is invalid.
+/// # Panics
+#[must_use]
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn boa_set_providers_config_file(path: *const c_char) -> i32 {
+  let Ok(path) = unsafe { CStr::from_ptr(path) }.to_str() else {
+    return 1;
+  };
+
+  i32::from(!set_providers_config_file(Path::new(path)))
+}
+
+/// Sets the directory where fetching caches data between runs.
+///
+/// Returns `1` if the path isn't UTF-8 or isn't a directory.
+/// # Panics
+#[must_use]
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn boa_set_cache_dir(path: *const c_char) -> i32 {
+  let Ok(path) = unsafe { CStr::from_ptr(path) }.to_str() else {
+    return 1;
+  };
+
+  i32::from(!set_cache_dir(Path::new(path)))
+}
+
+/// Enables (if `is_enabled` isn't `0`) or disables fetching a provider by
+/// `boa_get_tournaments`.
+///
+/// Returns `1` if the name isn't UTF-8 or there's no provider with that name.
+/// # Panics
+#[must_use]
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn boa_set_provider_enabled(
+  provider_name: *const c_char,
+  is_enabled: i32,
+) -> i32 {
+  let Ok(provider_name) = unsafe { CStr::from_ptr(provider_name) }.to_str()
+  else {
+    return 1;
+  };
+
+  i32::from(!set_provider_enabled(provider_name, is_enabled != 0))
+}
+
+/// Gets a team's name in a locale which is freed with
+/// `boa_free_team_display_name`.
+/// # Panics
+#[must_use]
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn boa_get_team_display_name(
+  team_name: *const c_char,
+  locale: *const c_char,
+  display_name: *mut *const c_char,
+) -> i32 {
+  let team_name = unsafe { CStr::from_ptr(team_name) }.to_str().unwrap();
+  let locale = unsafe { CStr::from_ptr(locale) }.to_str().unwrap();
+
+  let team_display_name =
+    get_team_display_name(&Arc::new(team_name.to_owned()), locale);
+  unsafe {
+    *display_name = CString::new(&**team_display_name).unwrap().into_raw();
+  }
+
+  0
+}
+
+/// Predicts a tournament with hypothetical matches' results applied.
+///
+/// The tournament has to be fetched first by `boa_get_tournaments` and the
+/// result is returned as a single tournament that's freed with
+/// `boa_free_tournaments` as usual.
+///
+/// Returns `1` if the tournament wasn't fetched, `2` if any of the results
+/// can't happen (e.g., unknown teams or no remaining matches between them) and
+/// `3` if any of the names isn't UTF-8.
+/// # Panics
+#[must_use]
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn boa_predict_tournament_scenario(
+  tournament_name: *const c_char,
+  matches_results_count: u64,
+  matches_results: *const MatchResultNative,
+  tournaments_count: *mut u64,
+  tournaments: *mut *const TournamentNative,
+) -> i32 {
+  let get_string = |s: *const c_char| {
+    unsafe { CStr::from_ptr(s) }
+      .to_str()
+      .ok()
+      .map(|s| Arc::new(s.to_owned()))
+  };
+
+  let Some(tournament_name) = get_string(tournament_name) else {
+    return 3;
+  };
+  let matches_results_count = usize::try_from(matches_results_count).unwrap();
+  let Some(matches_results) = (0..matches_results_count)
+    .map(|i| {
+      let match_result = unsafe { &*matches_results.add(i) };
+      Some((
+        (
+          get_string(match_result.first_team_name)?,
+          usize::try_from(match_result.first_team_score).unwrap(),
+        ),
+        (
+          get_string(match_result.second_team_name)?,
+          usize::try_from(match_result.second_team_score).unwrap(),
+        ),
+      ))
+    })
+    .collect::<Option<Vec<MatchResult>>>()
+  else {
+    return 3;
+  };
+
+  let fetched_tournaments = FETCHED_TOURNAMENTS.lock().unwrap();
+  let Some(fetched_tournament) = fetched_tournaments
+    .iter()
+    .find(|tournament| tournament.name == tournament_name)
+  else {
+    return 1;
+  };
+  let Some(tournament) =
+    tournament::apply_matches_results(fetched_tournament, &matches_results)
+  else {
+    return 2;
+  };
+  drop(fetched_tournaments);
+
+  do_tournaments(
+    vec![tournament::predict_tournament(&tournament)],
+    tournaments_count,
+    tournaments,
+  );
+
+  0
+}
+
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+fn do_tournaments(
+  local_tournaments: Vec<Tournament>,
+  tournaments_count: *mut u64,
+  tournaments: *mut *const TournamentNative,
+) {
   unsafe {
     *tournaments_count = local_tournaments.len() as u64;
   }
@@ -99,8 +861,6 @@ pub extern "C" fn boa_get_tournaments(
       tournaments_native
     };
   }
-
-  0
 }
 
 #[must_use]
@@ -124,6 +884,7 @@ fn do_team(team: &Team) -> TeamNative {
     matches_lost: team.matches_lost as u64,
     earned_points: team.earned_points as u64,
     remaining_points: team.remaining_points as u64,
+    points_adjustment: team.points_adjustment as i64,
 
     elimination_status: match &team.elimination_status {
       None | Some(EliminationStatus::Not) => 1u64,
@@ -156,3 +917,65 @@ pub extern "C" fn boa_free_tournaments(
     *tournaments = ptr::null();
   }
 }
+
+/// # Panics
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref, unused_must_use)]
+pub extern "C" fn boa_free_team_display_name(display_name: *mut *const c_char) {
+  unsafe {
+    if (*display_name).is_null() {
+      return;
+    }
+
+    CString::from_raw(*display_name as *mut c_char);
+    *display_name = ptr::null();
+  }
+}
+
+/// # Panics
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref, unused_must_use)]
+pub extern "C" fn boa_free_discrepancies(
+  discrepancies: *mut *const DiscrepancyNative,
+) {
+  unsafe {
+    if (*discrepancies).is_null() {
+      return;
+    }
+
+    Box::from_raw(*discrepancies as *mut DiscrepancyNative);
+    *discrepancies = ptr::null();
+  }
+}
+
+/// # Panics
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref, unused_must_use)]
+pub extern "C" fn boa_free_stale_tournaments(
+  stale_tournaments: *mut *const StaleTournamentNative,
+) {
+  unsafe {
+    if (*stale_tournaments).is_null() {
+      return;
+    }
+
+    Box::from_raw(*stale_tournaments as *mut StaleTournamentNative);
+    *stale_tournaments = ptr::null();
+  }
+}
+
+/// # Panics
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref, unused_must_use)]
+pub extern "C" fn boa_free_fetch_failures(
+  failures: *mut *const FetchFailureNative,
+) {
+  unsafe {
+    if (*failures).is_null() {
+      return;
+    }
+
+    Box::from_raw(*failures as *mut FetchFailureNative);
+    *failures = ptr::null();
+  }
+}
@@ -1,5 +1,10 @@
 fn main() {
   boa::test();
 
-  let _ignored_tournaments = boa::get_tournaments();
+  let (
+    _ignored_tournaments,
+    _ignored_failures,
+    _ignored_discrepancies,
+    _ignored_stale_tournaments,
+  ) = boa::get_tournaments();
 }
@@ -1,23 +1,246 @@
+pub(super) mod adjustments;
+mod critical_matches;
 mod fetching;
+mod group_stage;
+pub(super) mod knockout;
 mod prediction;
+pub(super) mod replay;
+mod scenario;
 
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::sync::Arc;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::common::AdvancementStatus;
+use crate::common::CompetitionFormat;
+use crate::common::CriticalMatches;
+use crate::common::GroupStage;
+use crate::common::TeamId;
 use crate::common::Tournament;
-use crate::tournament::fetching::fetch_tournaments;
+pub use crate::tournament::adjustments::Adjustment;
+pub use crate::tournament::fetching::common::ExportedTournaments;
+pub use crate::tournament::fetching::common::FetchError;
+pub use crate::tournament::fetching::common::FetchFailure;
+pub use crate::tournament::fetching::common::FetchedTournament;
+pub use crate::tournament::fetching::common::Fixture;
+pub use crate::tournament::fetching::common::FixtureStatus;
+pub use crate::tournament::fetching::common::MatchOutcome;
+pub use crate::tournament::fetching::common::MatchResult;
+pub use crate::tournament::fetching::common::MatchStatus;
+pub use crate::tournament::fetching::common::StaleTournament;
+pub use crate::tournament::fetching::config::ProvidersConfig;
+pub use crate::tournament::fetching::reconciliation::Discrepancy;
+pub use crate::tournament::fetching::reconciliation::DiscrepancyKind;
+pub use crate::tournament::fetching::registry::Provider;
+pub use crate::tournament::fetching::transport::ConditionalResponse;
+pub use crate::tournament::fetching::transport::Transport;
+pub use crate::tournament::fetching::transport::Validators;
+use crate::tournament::knockout::KnockoutBracket;
+use crate::tournament::knockout::KnockoutStatus;
 use crate::tournament::prediction::predict_tournament_eliminated_teams;
+use crate::tournament::replay::TournamentReplay;
+
+/// # Panics
+#[must_use]
+pub(super) fn get_tournaments(
+  providers_config: &ProvidersConfig,
+  all_tournaments_adjustments: &BTreeMap<Arc<String>, Vec<Adjustment>>,
+) -> (
+  Vec<Tournament>,
+  Vec<FetchFailure>,
+  Vec<Discrepancy>,
+  Vec<StaleTournament>,
+) {
+  let (tournaments, failures, discrepancies, stale_tournaments) =
+    fetch_tournaments(providers_config, all_tournaments_adjustments);
+
+  (
+    predict_tournaments(&tournaments),
+    failures,
+    discrepancies,
+    stale_tournaments,
+  )
+}
+
+/// # Panics
+pub(super) async fn get_tournaments_async(
+  providers_config: &ProvidersConfig,
+  all_tournaments_adjustments: &BTreeMap<Arc<String>, Vec<Adjustment>>,
+) -> (
+  Vec<Tournament>,
+  Vec<FetchFailure>,
+  Vec<Discrepancy>,
+  Vec<StaleTournament>,
+) {
+  let (tournaments, failures, discrepancies, stale_tournaments) =
+    fetching::fetch_tournaments_async(
+      providers_config,
+      all_tournaments_adjustments,
+    )
+    .await;
+  // NOTE: Predictions are CPU-bound so they're kept off the runtime's workers.
+  let tournaments =
+    tokio::task::spawn_blocking(move || predict_tournaments(&tournaments))
+      .await
+      .expect("Predicting failed");
+
+  (tournaments, failures, discrepancies, stale_tournaments)
+}
+
+/// # Panics
+#[must_use]
+pub(super) fn predict_tournaments(
+  tournaments: &[Tournament],
+) -> Vec<Tournament> {
+  #[cfg(feature = "parallel")]
+  let tournaments = tournaments.par_iter();
+  #[cfg(not(feature = "parallel"))]
+  let tournaments = tournaments.iter();
+
+  tournaments.map(predict_tournament).collect()
+}
+
+/// # Panics
+#[must_use]
+pub(super) fn fetch_tournaments(
+  providers_config: &ProvidersConfig,
+  all_tournaments_adjustments: &BTreeMap<Arc<String>, Vec<Adjustment>>,
+) -> (
+  Vec<Tournament>,
+  Vec<FetchFailure>,
+  Vec<Discrepancy>,
+  Vec<StaleTournament>,
+) {
+  fetching::fetch_tournaments(providers_config, all_tournaments_adjustments)
+}
+
+/// # Panics
+#[must_use]
+pub(super) fn export_tournaments(
+  providers_config: &ProvidersConfig,
+) -> (
+  ExportedTournaments,
+  Vec<FetchFailure>,
+  Vec<Discrepancy>,
+  Vec<StaleTournament>,
+) {
+  fetching::export_tournaments(providers_config)
+}
+
+#[must_use]
+pub(super) fn read_providers_config(path: &Path) -> Option<ProvidersConfig> {
+  fetching::config::read_providers_config(path)
+}
+
+#[must_use]
+pub(super) fn read_adjustments(
+  path: &Path,
+) -> Option<BTreeMap<Arc<String>, Vec<Adjustment>>> {
+  adjustments::read_adjustments(path)
+}
+
+/// # Panics
+#[must_use]
+pub(super) fn postprocess_tournament(
+  tournament_name: &Arc<String>,
+  teams_names: &BTreeSet<TeamId>,
+  matches_results: &[MatchResult],
+) -> Tournament {
+  fetching::common::postprocess_tournament_results(
+    tournament_name,
+    teams_names,
+    matches_results,
+  )
+}
+
+/// # Panics
+#[must_use]
+pub(super) fn postprocess_tournament_with_format(
+  tournament_name: &Arc<String>,
+  teams_names: &BTreeSet<TeamId>,
+  matches_results: &[MatchResult],
+  format: &CompetitionFormat,
+) -> Tournament {
+  fetching::common::postprocess_tournament_fixtures(
+    tournament_name,
+    teams_names,
+    matches_results,
+    None,
+    format,
+    &[],
+  )
+}
+
+/// # Panics
+#[must_use]
+pub(super) fn apply_matches_results(
+  tournament: &Tournament,
+  matches_results: &[MatchResult],
+) -> Option<Tournament> {
+  scenario::apply_matches_results(tournament, matches_results)
+}
+
+/// # Panics
+#[must_use]
+pub(super) fn predict_tournament(tournament: &Tournament) -> Tournament {
+  Tournament::new(
+    &tournament.name,
+    predict_tournament_eliminated_teams(tournament),
+    tournament.remaining_points.clone(),
+  )
+}
+
+/// # Panics
+#[must_use]
+pub(super) fn predict_critical_matches(
+  tournament: &Tournament,
+) -> Option<BTreeMap<TeamId, CriticalMatches>> {
+  critical_matches::predict_tournament_critical_matches(tournament)
+}
+
+/// # Panics
+#[must_use]
+pub(super) fn predict_group_stage(
+  group_stage: &GroupStage,
+) -> Option<BTreeMap<Arc<String>, BTreeMap<TeamId, AdvancementStatus>>> {
+  group_stage::predict_group_stage(group_stage)
+}
+
+/// # Panics
+#[must_use]
+pub(super) fn predict_knockout_bracket(
+  bracket: &KnockoutBracket,
+) -> BTreeMap<TeamId, KnockoutStatus> {
+  knockout::predict_knockout_bracket(bracket)
+}
 
 /// # Panics
 #[must_use]
-pub(super) fn get_tournaments() -> Vec<Tournament> {
-  fetch_tournaments()
-    .into_iter()
-    .map(|tournament| {
-      let teams = predict_tournament_eliminated_teams(&tournament);
-      Tournament::new(&tournament.name, teams, None)
-    })
-    .collect()
+pub(super) fn replay_tournament(
+  tournament_name: &Arc<String>,
+  matches_results: &[MatchResult],
+  format: &CompetitionFormat,
+  adjustments: &[Adjustment],
+) -> Option<TournamentReplay> {
+  replay::replay_tournament(
+    tournament_name,
+    matches_results,
+    format,
+    adjustments,
+  )
 }
 
 pub(super) fn test() {
+  adjustments::test();
+  critical_matches::test();
   fetching::test();
+  group_stage::test();
+  knockout::test();
   prediction::test();
+  replay::test();
+  scenario::test();
 }
@@ -0,0 +1,352 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::common::CompetitionFormat;
+use crate::common::TeamId;
+use crate::tournament::fetching::common::postprocess_tournament_fixtures;
+use crate::tournament::fetching::common::MatchResult;
+use crate::tournament::fetching::test_helper_stored_tournaments_results;
+
+/// Administrative changes to a tournament that aren't reflected in its
+/// matches' results.
+#[must_use]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Adjustment {
+  // NOTE: Negative for deductions (e.g., for financial breaches).
+  Points {
+    team_name: TeamId,
+    points: isize,
+  },
+  // NOTE: Replaces the played result with the same home and away teams (if
+  // any). Used for forfeits (e.g., awarded 3-0).
+  AwardedResult(MatchResult),
+  // NOTE: Removes the played result with the same home and away teams and the
+  // match isn't replayed.
+  VoidedMatch {
+    home_team_name: TeamId,
+    away_team_name: TeamId,
+  },
+}
+
+/// Reads tournaments' adjustments (by tournament-name) from a JSON file like:
+/// `{"Premier League": [{"type": "points", "team": "Everton", "points": -10}]}`
+/// where other types are `awarded` (with `home_team`, `home_team_score`,
+/// `away_team` and `away_team_score`) and `voided` (with `home_team` and
+/// `away_team`). `None` is returned when the file can't be read, parsed or is
+/// invalid (e.g., a team playing itself).
+#[must_use]
+pub(super) fn read_adjustments(
+  path: &Path,
+) -> Option<BTreeMap<Arc<String>, Vec<Adjustment>>> {
+  parse_adjustments(&fs::read_to_string(path).ok()?)
+}
+
+#[must_use]
+fn parse_adjustments(
+  adjustments_json: &str,
+) -> Option<BTreeMap<Arc<String>, Vec<Adjustment>>> {
+  use serde::Deserialize;
+
+  #[must_use]
+  #[derive(Deserialize)]
+  #[serde(tag = "type", rename_all = "snake_case")]
+  enum AdjustmentJson {
+    Points {
+      team: String,
+      points: isize,
+    },
+    Awarded {
+      home_team: String,
+      home_team_score: usize,
+      away_team: String,
+      away_team_score: usize,
+    },
+    Voided {
+      home_team: String,
+      away_team: String,
+    },
+  }
+
+  let all_tournaments_adjustments: BTreeMap<String, Vec<AdjustmentJson>> =
+    serde_json::from_str(adjustments_json).ok()?;
+  let all_tournaments_adjustments: BTreeMap<Arc<String>, Vec<Adjustment>> =
+    all_tournaments_adjustments
+      .into_iter()
+      .map(|(tournament_name, adjustments)| {
+        (
+          Arc::new(tournament_name),
+          adjustments
+            .into_iter()
+            .map(|adjustment| match adjustment {
+              AdjustmentJson::Points { team, points } => Adjustment::Points {
+                team_name: Arc::new(team),
+                points,
+              },
+              AdjustmentJson::Awarded {
+                home_team,
+                home_team_score,
+                away_team,
+                away_team_score,
+              } => Adjustment::AwardedResult((
+                (Arc::new(home_team), home_team_score),
+                (Arc::new(away_team), away_team_score),
+              )),
+              AdjustmentJson::Voided {
+                home_team,
+                away_team,
+              } => Adjustment::VoidedMatch {
+                home_team_name: Arc::new(home_team),
+                away_team_name: Arc::new(away_team),
+              },
+            })
+            .collect(),
+        )
+      })
+      .collect();
+
+  all_tournaments_adjustments
+    .values()
+    .flatten()
+    .all(is_valid_adjustment)
+    .then_some(all_tournaments_adjustments)
+}
+
+// NOTE: Teams aren't checked against the tournament's as they're only known
+// once it's fetched (see `apply_results_adjustments()`).
+#[must_use]
+pub(crate) fn is_valid_adjustment(adjustment: &Adjustment) -> bool {
+  const NAME_LENGTH_MIN: usize = 1;
+  const NAME_LENGTH_MAX: usize = 100;
+
+  let is_valid_name = |team_name: &TeamId| {
+    (NAME_LENGTH_MIN..=NAME_LENGTH_MAX).contains(&team_name.len())
+  };
+
+  match adjustment {
+    Adjustment::Points { team_name, .. } => is_valid_name(team_name),
+    Adjustment::AwardedResult(((home_team_name, _), (away_team_name, _)))
+    | Adjustment::VoidedMatch {
+      home_team_name,
+      away_team_name,
+    } => {
+      is_valid_name(home_team_name)
+        && is_valid_name(away_team_name)
+        && home_team_name != away_team_name
+    },
+  }
+}
+
+/// Applies awarded and voided results (in order) returning the adjusted
+/// matches' results and the voided matches as (home-team, away-team) pairs.
+///
+/// Adjustments of teams that aren't in the tournament are ignored (e.g., when
+/// a provider names a team differently) and voided matches that haven't been
+/// played yet are still voided (i.e., they won't be played).
+#[must_use]
+pub(crate) fn apply_results_adjustments(
+  teams_names: &BTreeSet<TeamId>,
+  matches_results: &[MatchResult],
+  adjustments: &[Adjustment],
+) -> (Vec<MatchResult>, Vec<(TeamId, TeamId)>) {
+  let mut matches_results = matches_results.to_vec();
+  let mut voided_matches = vec![];
+  let find_result = |matches_results: &[MatchResult],
+                     home_team_name: &TeamId,
+                     away_team_name: &TeamId| {
+    matches_results.iter().position(
+      |((first_team_name, _), (second_team_name, _))| {
+        first_team_name == home_team_name && second_team_name == away_team_name
+      },
+    )
+  };
+
+  let is_known_pair = |home_team_name: &TeamId, away_team_name: &TeamId| {
+    teams_names.contains(home_team_name) && teams_names.contains(away_team_name)
+  };
+
+  for adjustment in adjustments {
+    match adjustment {
+      Adjustment::Points { .. } => {},
+      Adjustment::AwardedResult(awarded_result) => {
+        let ((home_team_name, _), (away_team_name, _)) = awarded_result;
+        if !is_known_pair(home_team_name, away_team_name) {
+          continue;
+        }
+        match find_result(&matches_results, home_team_name, away_team_name) {
+          Some(i) => matches_results[i] = awarded_result.clone(),
+          None => matches_results.push(awarded_result.clone()),
+        }
+      },
+      Adjustment::VoidedMatch {
+        home_team_name,
+        away_team_name,
+      } => {
+        if !is_known_pair(home_team_name, away_team_name) {
+          continue;
+        }
+        if let Some(i) =
+          find_result(&matches_results, home_team_name, away_team_name)
+        {
+          matches_results.remove(i);
+        }
+        voided_matches
+          .push((Arc::clone(home_team_name), Arc::clone(away_team_name)));
+      },
+    }
+  }
+
+  (matches_results, voided_matches)
+}
+
+/// Sums the points adjustments of a team.
+#[must_use]
+pub(crate) fn get_points_adjustment(
+  adjustments: &[Adjustment],
+  team_name: &TeamId,
+) -> isize {
+  adjustments
+    .iter()
+    .map(|adjustment| match adjustment {
+      Adjustment::Points {
+        team_name: adjusted_team_name,
+        points,
+      } if adjusted_team_name == team_name => *points,
+      _ => 0,
+    })
+    .sum()
+}
+
+#[allow(clippy::too_many_lines)]
+pub(super) fn test() {
+  #[cfg(test)]
+  use pretty_assertions::assert_eq;
+
+  let (tournament_name, matches_results) =
+    test_helper_stored_tournaments_results()
+      .into_iter()
+      .next()
+      .unwrap();
+  let teams_names: BTreeSet<TeamId> = matches_results
+    .iter()
+    .flat_map(|((first_team_name, _), (second_team_name, _))| {
+      vec![Arc::clone(first_team_name), Arc::clone(second_team_name)]
+    })
+    .collect();
+  let postprocess = |adjustments: &[Adjustment]| {
+    postprocess_tournament_fixtures(
+      &tournament_name,
+      &teams_names,
+      &matches_results,
+      None,
+      &CompetitionFormat::default(),
+      adjustments,
+    )
+  };
+  let get_team_stats = |adjustments: &[Adjustment], team_name: &str| {
+    let tournament = postprocess(adjustments);
+    let team = tournament
+      .teams
+      .iter()
+      .find(|team| *team.name == team_name)
+      .unwrap();
+
+    (
+      team.matches_played,
+      team.matches_left,
+      team.matches_won,
+      team.earned_points,
+      team.get_points(),
+      team.remaining_points,
+    )
+  };
+
+  assert_eq!(parse_adjustments("not json"), None);
+  let all_tournaments_adjustments = parse_adjustments(
+    r#"{
+      "Saudi Professional League": [
+        {"type": "points", "team": "Al Ittihad", "points": -60},
+        {"type": "points", "team": "Al Hilal", "points": 3},
+        {"type": "awarded", "home_team": "Al Fayha", "home_team_score": 0,
+          "away_team": "Al Ittihad", "away_team_score": 3},
+        {"type": "voided", "home_team": "Abha", "away_team": "Al Shabab"}
+      ]
+    }"#,
+  )
+  .unwrap();
+  let adjustments = &all_tournaments_adjustments[&tournament_name];
+  assert_eq!(
+    adjustments[2],
+    Adjustment::AwardedResult((
+      (Arc::new(String::from("Al Fayha")), 0),
+      (Arc::new(String::from("Al Ittihad")), 3),
+    )),
+  );
+
+  // NOTE: Al Ittihad's first match (a loss) is awarded to it which gives it 3
+  // more earned points while the 60 points deduction is kept apart from them
+  // (and can leave it with negative points).
+  assert_eq!(get_team_stats(&[], "Al Ittihad"), (19, 11, 15, 47, 47, 33));
+  assert_eq!(
+    get_team_stats(adjustments, "Al Ittihad"),
+    (19, 11, 16, 50, -10, 33),
+  );
+  assert_eq!(get_team_stats(&[], "Al Hilal"), (17, 13, 8, 31, 31, 39));
+  assert_eq!(
+    get_team_stats(adjustments, "Al Hilal"),
+    (17, 13, 8, 31, 34, 39),
+  );
+  // NOTE: The voided match isn't played nor left anymore.
+  assert_eq!(get_team_stats(&[], "Abha"), (20, 10, 8, 28, 28, 30));
+  assert_eq!(get_team_stats(adjustments, "Abha"), (19, 10, 7, 25, 25, 30));
+
+  // NOTE: Adjustments of teams that aren't in the tournament are ignored and a
+  // voided match that hasn't been played yet won't be.
+  let unknown_team_name = Arc::new(String::from("Unknown"));
+  let abha_name = Arc::new(String::from("Abha"));
+  let al_fayha_name = Arc::new(String::from("Al Fayha"));
+  assert_eq!(
+    postprocess(&[
+      Adjustment::Points {
+        team_name: Arc::clone(&unknown_team_name),
+        points: -3,
+      },
+      Adjustment::AwardedResult((
+        (Arc::clone(&unknown_team_name), 3),
+        (Arc::clone(&abha_name), 0),
+      )),
+      Adjustment::VoidedMatch {
+        home_team_name: Arc::clone(&abha_name),
+        away_team_name: Arc::clone(&unknown_team_name),
+      },
+    ]),
+    postprocess(&[]),
+  );
+  assert!(!matches_results.iter().any(
+    |((home_team_name, _), (away_team_name, _))| {
+      *home_team_name == abha_name && *away_team_name == al_fayha_name
+    }
+  ));
+  assert_eq!(
+    get_team_stats(
+      &[Adjustment::VoidedMatch {
+        home_team_name: abha_name,
+        away_team_name: al_fayha_name,
+      }],
+      "Abha",
+    ),
+    (20, 9, 8, 28, 28, 27),
+  );
+}
+
+#[cfg(test)]
+mod tests {
+  use super::test;
+
+  #[test]
+  fn test_tournament_adjustments() {
+    test();
+  }
+}
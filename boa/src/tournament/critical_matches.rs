@@ -0,0 +1,253 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use crate::common::CriticalMatches;
+use crate::common::Team;
+use crate::common::TeamId;
+use crate::common::Tournament;
+use crate::tournament::fetching::common::postprocess_tournament_results;
+use crate::tournament::fetching::common::MatchResult;
+use crate::tournament::fetching::common::WIN_FACTOR;
+use crate::tournament::predict_tournament;
+use crate::tournament::prediction::is_eliminated;
+use crate::tournament::prediction::predict_team_elimination_status;
+use crate::tournament::scenario::apply_matches_results;
+
+/// Finds the remaining matches that are decisive for each non-eliminated team
+/// by predicting its elimination-status again with a single match's result
+/// fixed. `None` is returned when the tournament has no remaining-points or
+/// its teams have their elimination-statuses predicted already.
+/// # Panics
+#[must_use]
+pub(super) fn predict_tournament_critical_matches(
+  tournament: &Tournament,
+) -> Option<BTreeMap<TeamId, CriticalMatches>> {
+  if tournament
+    .teams
+    .iter()
+    .any(|team| team.elimination_status.is_some())
+  {
+    return None;
+  }
+
+  // NOTE: Pairs are ordered so that a match is found once whatever the
+  // home-team is.
+  let remaining_matches: BTreeSet<(&TeamId, &TeamId)> = tournament
+    .remaining_points
+    .as_ref()?
+    .iter()
+    .filter(|(_, &remaining_points)| remaining_points >= WIN_FACTOR)
+    .map(|((first_team_name, second_team_name), _)| {
+      (
+        first_team_name.min(second_team_name),
+        first_team_name.max(second_team_name),
+      )
+    })
+    .collect();
+
+  Some(
+    tournament
+      .teams
+      .iter()
+      .filter(|team| {
+        !is_eliminated(&predict_team_elimination_status(tournament, team))
+      })
+      .map(|team| {
+        (
+          Arc::clone(&team.name),
+          predict_team_critical_matches(tournament, team, &remaining_matches),
+        )
+      })
+      .collect(),
+  )
+}
+
+#[must_use]
+fn predict_team_critical_matches(
+  tournament: &Tournament,
+  team: &Team,
+  remaining_matches: &BTreeSet<(&TeamId, &TeamId)>,
+) -> CriticalMatches {
+  // NOTE: Scores don't matter only who wins (if anyone).
+  let is_eliminated_by = |match_result: MatchResult| {
+    let tournament =
+      apply_matches_results(tournament, &[match_result]).unwrap();
+    let team = tournament
+      .teams
+      .iter()
+      .find(|candidate_team| candidate_team.name == team.name)
+      .unwrap();
+
+    is_eliminated(&predict_team_elimination_status(&tournament, team))
+  };
+
+  let must_win: BTreeSet<TeamId> = remaining_matches
+    .iter()
+    .filter_map(|&(first_team_name, second_team_name)| {
+      if *first_team_name == team.name {
+        Some(second_team_name)
+      } else if *second_team_name == team.name {
+        Some(first_team_name)
+      } else {
+        None
+      }
+    })
+    .filter(|opponent_name| {
+      is_eliminated_by((
+        (Arc::clone(opponent_name), 1),
+        (Arc::clone(&team.name), 0),
+      ))
+    })
+    .map(Arc::clone)
+    .collect();
+
+  // NOTE: Either team winning can be critical and so can a draw as it gives
+  // both teams points (see `must_not_draw` below).
+  let must_not_happen: BTreeSet<(TeamId, TeamId)> = remaining_matches
+    .iter()
+    .filter(|&&(first_team_name, second_team_name)| {
+      *first_team_name != team.name && *second_team_name != team.name
+    })
+    .flat_map(|&(first_team_name, second_team_name)| {
+      vec![
+        (first_team_name, second_team_name),
+        (second_team_name, first_team_name),
+      ]
+    })
+    .filter(|(winning_team_name, losing_team_name)| {
+      is_eliminated_by((
+        (Arc::clone(winning_team_name), 1),
+        (Arc::clone(losing_team_name), 0),
+      ))
+    })
+    .map(|(winning_team_name, losing_team_name)| {
+      (Arc::clone(winning_team_name), Arc::clone(losing_team_name))
+    })
+    .collect();
+
+  let must_not_draw: BTreeSet<(TeamId, TeamId)> = remaining_matches
+    .iter()
+    .filter(|&&(first_team_name, second_team_name)| {
+      is_eliminated_by((
+        (Arc::clone(first_team_name), 0),
+        (Arc::clone(second_team_name), 0),
+      ))
+    })
+    .map(|&(first_team_name, second_team_name)| {
+      (Arc::clone(first_team_name), Arc::clone(second_team_name))
+    })
+    .collect();
+
+  CriticalMatches::new(must_win, must_not_happen, must_not_draw)
+}
+
+pub(super) fn test() {
+  #[cfg(test)]
+  use pretty_assertions::assert_eq;
+
+  let tournament_name = Arc::new(String::from("dummy-tournament"));
+  let (first, second, third, fourth) = (
+    Arc::new(String::from("first")),
+    Arc::new(String::from("second")),
+    Arc::new(String::from("third")),
+    Arc::new(String::from("fourth")),
+  );
+  let teams_names: BTreeSet<TeamId> = vec![&first, &second, &third, &fourth]
+    .into_iter()
+    .map(Arc::clone)
+    .collect();
+  // NOTE: Points are (first: 9, second: 6, third: 3, fourth: 0) and every pair
+  // has a single match left. The only way for fourth to survive is winning all
+  // its matches with first losing all of its and third beating second.
+  let matches_results: Vec<MatchResult> = vec![
+    ((Arc::clone(&first), 1), (Arc::clone(&second), 0)),
+    ((Arc::clone(&third), 1), (Arc::clone(&fourth), 0)),
+    ((Arc::clone(&first), 1), (Arc::clone(&third), 0)),
+    ((Arc::clone(&second), 1), (Arc::clone(&fourth), 0)),
+    ((Arc::clone(&first), 1), (Arc::clone(&fourth), 0)),
+    ((Arc::clone(&second), 1), (Arc::clone(&third), 0)),
+  ];
+  let tournament = postprocess_tournament_results(
+    &tournament_name,
+    &teams_names,
+    &matches_results,
+  );
+
+  let critical_matches =
+    predict_tournament_critical_matches(&tournament).unwrap();
+  assert_eq!(
+    critical_matches,
+    vec![
+      (
+        Arc::clone(&first),
+        CriticalMatches::new(BTreeSet::new(), BTreeSet::new(), BTreeSet::new()),
+      ),
+      (
+        Arc::clone(&fourth),
+        CriticalMatches::new(
+          vec![Arc::clone(&first), Arc::clone(&second), Arc::clone(&third)]
+            .into_iter()
+            .collect(),
+          vec![
+            (Arc::clone(&first), Arc::clone(&second)),
+            (Arc::clone(&first), Arc::clone(&third)),
+            (Arc::clone(&second), Arc::clone(&third)),
+          ]
+          .into_iter()
+          .collect(),
+          // NOTE: Including its own matches as fourth can't afford dropping
+          // any points.
+          vec![
+            (Arc::clone(&first), Arc::clone(&fourth)),
+            (Arc::clone(&first), Arc::clone(&second)),
+            (Arc::clone(&first), Arc::clone(&third)),
+            (Arc::clone(&fourth), Arc::clone(&second)),
+            (Arc::clone(&fourth), Arc::clone(&third)),
+            (Arc::clone(&second), Arc::clone(&third)),
+          ]
+          .into_iter()
+          .collect(),
+        ),
+      ),
+      (
+        Arc::clone(&second),
+        CriticalMatches::new(BTreeSet::new(), BTreeSet::new(), BTreeSet::new()),
+      ),
+      (
+        Arc::clone(&third),
+        CriticalMatches::new(
+          vec![Arc::clone(&first), Arc::clone(&second)]
+            .into_iter()
+            .collect(),
+          BTreeSet::new(),
+          BTreeSet::new(),
+        ),
+      ),
+    ]
+    .into_iter()
+    .collect(),
+  );
+
+  // NOTE: Tournaments that can't be predicted are rejected instead of
+  // panicking.
+  for invalid_tournament in [
+    Tournament::new(&tournament_name, tournament.teams.clone(), None),
+    predict_tournament(&tournament),
+  ] {
+    assert_eq!(
+      predict_tournament_critical_matches(&invalid_tournament),
+      None
+    );
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::test;
+
+  #[test]
+  fn test_tournament_critical_matches() {
+    test();
+  }
+}
@@ -1,70 +1,165 @@
-mod common;
+mod async_transport;
+mod cache;
+pub(super) mod common;
+pub(super) mod config;
+mod discovery;
+mod football_data;
+mod interchange;
+mod koora_table;
+#[cfg(test)]
+mod mock_server;
+pub(super) mod reconciliation;
+pub(super) mod registry;
+mod sync;
+pub(super) mod teams;
+pub(super) mod transport;
 
+use std::collections::BTreeMap;
+use std::fs;
+use std::future::Future;
+use std::panic;
+use std::path::Path;
 use std::sync::Arc;
+use std::sync::Mutex;
+use std::thread;
 
-use reqwest::blocking::Client;
+use chrono::prelude::Utc;
+use chrono::TimeZone;
+use itertools::Itertools;
+use serde_json::Value;
+use tokio::runtime::Handle;
+use tokio::runtime::Runtime;
 
 use crate::common::Team;
+use crate::common::TeamId;
 use crate::common::Tournament;
+use crate::tournament::adjustments::Adjustment;
+use crate::tournament::fetching::async_transport::AsyncHttpTransport;
+use crate::tournament::fetching::async_transport::BlockingTransport;
+use crate::tournament::fetching::async_transport::FetchPolicy;
+#[cfg(test)]
+use crate::tournament::fetching::async_transport::HostLimits;
+use crate::tournament::fetching::cache::CachingTransport;
+use crate::tournament::fetching::common::build_tournaments;
+use crate::tournament::fetching::common::take_fetched;
+use crate::tournament::fetching::common::ExportedTournaments;
+use crate::tournament::fetching::common::FetchError;
+use crate::tournament::fetching::common::FetchFailure;
+use crate::tournament::fetching::common::FetchedTournament;
+use crate::tournament::fetching::common::FetchedTournaments;
+use crate::tournament::fetching::common::Fixture;
+use crate::tournament::fetching::common::FixtureStatus;
+use crate::tournament::fetching::common::MatchOutcome;
 use crate::tournament::fetching::common::MatchResult;
+use crate::tournament::fetching::common::MatchStatus;
+use crate::tournament::fetching::common::StaleTournament;
 use crate::tournament::fetching::common::TournamentProvider;
+use crate::tournament::fetching::config::ProvidersConfig;
+use crate::tournament::fetching::discovery::discover_premier_league_season;
+use crate::tournament::fetching::discovery::f64_id_to_usize;
+use crate::tournament::fetching::football_data::parse_football_data_csv;
+use crate::tournament::fetching::interchange::parse_interchange_tournament;
+use crate::tournament::fetching::interchange::write_interchange_tournament;
+use crate::tournament::fetching::interchange::ImportedTournament;
+use crate::tournament::fetching::koora_table::parse_koora_table;
+use crate::tournament::fetching::koora_table::KooraMatch;
+#[cfg(test)]
+use crate::tournament::fetching::mock_server::MockServer;
+use crate::tournament::fetching::reconciliation::reconcile_tournaments;
+use crate::tournament::fetching::reconciliation::Discrepancy;
+use crate::tournament::fetching::registry::Provider;
+#[cfg(test)]
+use crate::tournament::fetching::registry::StaticProvider;
+use crate::tournament::fetching::sync::ResultsStore;
+use crate::tournament::fetching::sync::SyncedTournament;
+use crate::tournament::fetching::teams::TeamsRegistry;
+use crate::tournament::fetching::transport::HttpTransport;
+use crate::tournament::fetching::transport::RecordingTransport;
+use crate::tournament::fetching::transport::ReplayTransport;
+use crate::tournament::fetching::transport::Transport;
 
 #[must_use]
 struct PremierLeague {}
 impl TournamentProvider for PremierLeague {
+  const NAME: &'static str = "Premier League";
   const TEST_TOURNAMENT_NAME: &'static str = "First Team - Premier League";
   const TEST_DATA_FILE_ID: &'static str = "2021-12-26T14:58:52";
   const TEST_DATA_PREFIX: &'static str = "premier-league";
+  const ORIGIN: &'static str = "https://www.premierleague.com";
+  const BASE_URL: &'static str = "https://footballapi.pulselive.com";
+  const IS_INCREMENTAL: bool = true;
+
+  #[must_use]
+  fn tournaments_names(providers_config: &ProvidersConfig) -> Vec<Arc<String>> {
+    providers_config
+      .premier_league
+      .iter()
+      .map(|competition| Arc::clone(&competition.name))
+      .collect()
+  }
 
   #[must_use]
-  fn download_tournaments() -> Vec<(Arc<String>, Vec<String>)> {
+  fn download_tournaments(
+    transport: &dyn Transport,
+    base_url: &str,
+    providers_config: &ProvidersConfig,
+  ) -> FetchedTournaments<Vec<String>> {
     // NOTE: Used to match exactly the value used in official page.
     const PAGE_SIZE: usize = 40;
     // NOTE: Used to prevent an infinite loop in case the API response changes.
     const ITEMS_MAX: usize = 2 * 1000;
 
-    let client = get_client("https://www.premierleague.com");
+    let now = Utc::now().timestamp();
 
-    vec![
-    ("First Team - Premier League", 1, 418, "1,2,130,131,43,4,6,7,9,26,10,11,12,23,14,20,21,33,25,38",),
-    ("First Team - UEFA Champions League",2 ,424 , "47,541,48,49,51,229,52,231,4,232,83,56,58,87,1408,241,62,243,10,201,11,12,64,67,635,68,108,71,110,204,252,253,74",),
-    ("First Team - UEFA Europa League", 3, 457, "541,49,50,52,505,53,494,81,84,364,58,366,989,61,93,95,96,26,369,97,98,63,202,65,66,105,470,635,106,69,1420,108,110,752,111,249,506,373,25,74",),
-    ("PL2 - Primier League 2 - Division 1", 16, 438, "385,332,334,336,275,337,339,279,343,344,381,387,383,358",),
-    ("PL2 - Primier League 2 - Division 2", 17, 447, "386,266,335,341,345,346,347,281,351,352,354,355,357,360",),
-  ].into_iter().map(|(tournament_name, competition_id, competition_season_id, competition_teams_ids)| -> (Arc<String>, Vec<String>){
-    let mut page = 0;
-    let mut tournament_results_pages_json_non_parsed: Vec<String> = vec![];
-    loop {
-      assert!(
-        page * PAGE_SIZE <= ITEMS_MAX,
-        "too many pages ({:?}, {:?})",
-        page,
-        PAGE_SIZE,
-      );
+    providers_config.premier_league.iter().map(|competition| -> (Arc<String>, Result<Vec<String>, FetchError>){
+    let download_pages = || -> Result<Vec<String>, FetchError> {
+      let (competition_season_id, competition_teams_ids) =
+        discover_premier_league_season(
+          transport,
+          base_url,
+          competition,
+          providers_config.cache_dir.as_deref(),
+          now,
+        )?;
+      let competition_teams_ids =
+        competition_teams_ids.iter().map(ToString::to_string).join(",");
+
+      let mut page = 0;
+      let mut tournament_results_pages_json_non_parsed: Vec<String> = vec![];
+      loop {
+        if page * PAGE_SIZE > ITEMS_MAX {
+          return Err(FetchError::TooManyPages { pages_count: page });
+        }
 
-      let tournament_url=format!("https://footballapi.pulselive.com/football/fixtures?comps={competition_id}&compSeasons={competition_season_id}&teams={competition_teams_ids}&page={page}&pageSize={page_size}&sort=desc&statuses=C&altIds=true",
-      competition_id=competition_id,competition_season_id=competition_season_id,competition_teams_ids=competition_teams_ids, page=page, page_size=PAGE_SIZE,);
-      // SEE: https://docs.rs/reqwest/0.11.7/reqwest/struct.RequestBuilder.html#method.send
-      let resp = client
+        let tournament_url=format!("{base_url}/football/fixtures?comps={competition_id}&compSeasons={competition_season_id}&teams={competition_teams_ids}&page={page}&pageSize={page_size}&sort=desc&statuses=C&altIds=true",
+        base_url=base_url, competition_id=competition.competition_id,competition_season_id=competition_season_id,competition_teams_ids=competition_teams_ids, page=page, page_size=PAGE_SIZE,);
         // NOTE: Used to match exactly the URL used in the official page.
         // SEE: https://www.premierleague.com/results
-        .get(tournament_url)
-        .send()
-        .unwrap()
-        .text()
-        .unwrap();
+        let resp = transport.get_page(
+          &tournament_url,
+          &competition.name,
+          &page.to_string(),
+        )?;
 
-      let is_last_empty_page = resp.contains("\"content\":[]");
+        let is_last_empty_page = resp.contains("\"content\":[]");
+        // NOTE: Older pages' matches were synced already.
+        let is_last_synced_page = matches!(
+          providers_config.synced_since.get(&competition.name),
+          Some(since) if has_kickoff_before(&resp, *since)
+        );
 
-      tournament_results_pages_json_non_parsed.push(resp);
-      page += 1;
+        tournament_results_pages_json_non_parsed.push(resp);
+        page += 1;
 
-      if is_last_empty_page {
-        break;
+        if is_last_empty_page || is_last_synced_page {
+          break;
+        }
       }
-    }
 
-    (Arc::new(String::from(tournament_name)), tournament_results_pages_json_non_parsed)
+      Ok(tournament_results_pages_json_non_parsed)
+    };
+
+    (Arc::clone(&competition.name), download_pages())
   }).collect()
   }
 
@@ -74,7 +169,8 @@ impl TournamentProvider for PremierLeague {
       Arc<String>,
       Vec<String>,
     )>,
-  ) -> Vec<(Arc<String>, Vec<MatchResult>)> {
+    teams_registry: &TeamsRegistry,
+  ) -> FetchedTournaments<Vec<MatchOutcome>> {
     use serde::Deserialize;
 
     #[must_use]
@@ -85,8 +181,21 @@ impl TournamentProvider for PremierLeague {
 
     #[must_use]
     #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
     struct ContentItem {
       teams: (ContentItemTeam, ContentItemTeam),
+      status: String,
+      kickoff: Option<ContentItemKickoff>,
+      #[serde(default)]
+      extra_time: bool,
+      #[serde(default)]
+      shootout: bool,
+    }
+
+    #[must_use]
+    #[derive(Deserialize)]
+    struct ContentItemKickoff {
+      millis: f64,
     }
 
     #[must_use]
@@ -100,245 +209,788 @@ impl TournamentProvider for PremierLeague {
     #[derive(Deserialize)]
     struct ContentItemTeamTeam {
       name: String,
+      id: f64,
     }
 
     all_tournaments_results_pages_json_non_parsed
       .into_iter()
       .map(
-        |(tournament_name, tournament_results_pages_json_non_parsed)| -> (Arc<String>, Vec<MatchResult>) {
-          let matches_results: Vec<MatchResult> =
-            tournament_results_pages_json_non_parsed
-              .into_iter()
-              .flat_map(|tournament_results_page_json_non_parsed| {
-                let tournament_results_single_page_json: Page =
-                  serde_json::from_str(
-                    &tournament_results_page_json_non_parsed,
-                  )
-                  .unwrap();
-                tournament_results_single_page_json
-                  .content
-                  .into_iter()
-                  .map(|ContentItem { teams, .. }| {
-                    let (first_team, second_team) = teams;
-
-                    (
-                      // NOTE: We don't handle the case where a team wins in
-                      // penalties. And we don't have to because the tournaments
-                      // we support, at the moment, don't need this.
-                      (
-                        Arc::new(first_team.team.name),
-                        f64_score_to_usize(first_team.score),
-                      ),
-                      (
-                        Arc::new(second_team.team.name),
-                        f64_score_to_usize(second_team.score),
-                      ),
-                    )
-                  })
-                  .collect::<Vec<_>>()
-              })
-              .collect();
-
-          (tournament_name, matches_results)
+        |(tournament_name, tournament_results_pages_json_non_parsed)| -> (Arc<String>, Result<Vec<MatchOutcome>, FetchError>) {
+          let process_pages = || -> Result<Vec<MatchOutcome>, FetchError> {
+            let mut matches_outcomes: Vec<MatchOutcome> = vec![];
+            for tournament_results_page_json_non_parsed in
+              tournament_results_pages_json_non_parsed
+            {
+              let tournament_results_single_page_json: Page =
+                serde_json::from_str(&tournament_results_page_json_non_parsed)?;
+              for ContentItem {
+                teams,
+                status,
+                kickoff,
+                extra_time,
+                shootout,
+              } in tournament_results_single_page_json.content
+              {
+                let (first_team, second_team) = teams;
+
+                // NOTE: Only completed matches are requested (`statuses=C`).
+                if status != "C" {
+                  return Err(FetchError::Parse {
+                    message: format!("Invalid status ({:?}).", status),
+                  });
+                }
+                // NOTE: The shootout's score isn't part of the response (and
+                // the league doesn't have shootouts anyway).
+                if shootout {
+                  return Err(FetchError::Parse {
+                    message: format!(
+                      "Unsupported shootout ({:?}, {:?}).",
+                      first_team.team.name, second_team.team.name,
+                    ),
+                  });
+                }
+                // NOTE: Scores are at the end of extra-time (if played).
+                let score = (
+                  f64_score_to_usize(first_team.score)?,
+                  f64_score_to_usize(second_team.score)?,
+                );
+
+                let resolve_team = |team: &ContentItemTeamTeam| -> Result<TeamId, FetchError> {
+                  Ok(teams_registry.resolve(
+                    Self::NAME,
+                    u64::try_from(f64_id_to_usize(team.id)?).ok(),
+                    &team.name,
+                  ))
+                };
+
+                matches_outcomes.push(MatchOutcome {
+                  home_team_name: resolve_team(&first_team.team)?,
+                  away_team_name: resolve_team(&second_team.team)?,
+                  regulation_score: (!extra_time).then_some(score),
+                  extra_time_score: extra_time.then_some(score),
+                  shootout_score: None,
+                  status: MatchStatus::Finished,
+                  date: kickoff.map(|kickoff| f64_millis_to_timestamp(kickoff.millis)),
+                });
+              }
+            }
+            // NOTE: Results are requested in descending order (`sort=desc`)
+            // but have to be ordered chronologically.
+            matches_outcomes.reverse();
+
+            Ok(matches_outcomes)
+          };
+
+          (tournament_name, process_pages())
         },
       )
       .collect()
   }
 }
 
-#[must_use]
 #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
-fn f64_score_to_usize(score: f64) -> usize {
+fn f64_score_to_usize(score: f64) -> Result<usize, FetchError> {
   const EPSILON: f64 = 0.00001;
   const MAX: f64 = 1000.0;
 
-  assert!(
-    score >= 0f64 && (score.round() - score).abs() < EPSILON && score <= MAX,
-    "Invalid input {}",
-    score,
-  );
+  if score >= 0f64 && (score.round() - score).abs() < EPSILON && score <= MAX {
+    Ok(score as usize)
+  } else {
+    Err(FetchError::Parse {
+      message: format!("Invalid score ({:?}).", score),
+    })
+  }
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn f64_millis_to_timestamp(millis: f64) -> i64 {
+  (millis / 1000.0).round() as i64
+}
 
-  score as usize
+// NOTE: Pages are in descending order so the ones after a page with a match
+// before a unix-timestamp are all before it. Invalid pages fail when they're
+// processed instead.
+#[must_use]
+fn has_kickoff_before(page_json: &str, timestamp: i64) -> bool {
+  let Ok(page) = serde_json::from_str::<Value>(page_json) else {
+    return false;
+  };
+
+  page["content"]
+    .as_array()
+    .into_iter()
+    .flatten()
+    .any(|item| {
+      matches!(
+        item["kickoff"]["millis"].as_f64(),
+        Some(millis) if f64_millis_to_timestamp(millis) < timestamp
+      )
+    })
 }
 
 #[must_use]
 struct Koora {}
 impl TournamentProvider for Koora {
+  const NAME: &'static str = "Koora";
   const TEST_TOURNAMENT_NAME: &'static str = "Saudi Professional League";
   const TEST_DATA_FILE_ID: &'static str = "2022-02-14T22:50:10";
   const TEST_DATA_PREFIX: &'static str = "koora";
+  const ORIGIN: &'static str = "https://www.goalzz.com";
+  const BASE_URL: &'static str = "https://www.goalzz.com";
+  const IS_INCREMENTAL: bool = true;
 
   #[must_use]
-  fn download_tournaments() -> Vec<(Arc<String>, Vec<String>)> {
-    let client = get_client("https://www.goalzz.com");
+  fn tournaments_names(providers_config: &ProvidersConfig) -> Vec<Arc<String>> {
+    providers_config
+      .koora
+      .iter()
+      .map(|competition| Arc::clone(&competition.name))
+      .collect()
+  }
 
-    vec![
-      ("Saudi Professional League", 22551),
-      ("Saudi U-13 Premier League", 22279),
-      ("Saudi U-15 Premier League", 22307),
-      ("Saudi Arabia U-19 League Division 1", 22277),
-      ("Saudi Arabia U-17 League Division 1", 22278),
-      ("Saudi Arabia Youth League - U19", 22274),
-      ("Saudi U-17 Premier League", 22276),
-    ]
-    .into_iter()
-    .map(
-      |(tournament_name, competition_id)| -> (Arc<String>, Vec<String>) {
-        let months = vec![
-          "202101", "202102", "202103", "202104", "202105", "202106", "202107",
-          "202108", "202109", "202110", "202111", "202112", "202201", "202202",
-          "202203", "202204", "202205", "202206", "202207", "202208", "202209",
-          "202210", "202211", "202212",
-        ];
+  #[must_use]
+  fn download_tournaments(
+    transport: &dyn Transport,
+    base_url: &str,
+    providers_config: &ProvidersConfig,
+  ) -> FetchedTournaments<Vec<String>> {
+    providers_config
+      .koora
+      .iter()
+      .map(|competition| -> (Arc<String>, Result<Vec<String>, FetchError>) {
+        // NOTE: Earlier months' matches were synced already.
+        let since_month = providers_config
+          .synced_since
+          .get(&competition.name)
+          .and_then(|since| Utc.timestamp_opt(*since, 0).single())
+          .map(|since| since.format("%Y%m").to_string());
 
         (
-          Arc::new(String::from(tournament_name)),
-          months
+          Arc::clone(&competition.name),
+          competition
+            .get_months()
             .into_iter()
-            .map(|current_month| -> String {
+            .filter(|month| {
+              !matches!(&since_month, Some(since_month) if month < since_month)
+            })
+            .map(|current_month| -> Result<String, FetchError> {
               let tournament_url = format!(
-                "https://www.goalzz.com/main.aspx?c={competition_id}&stage=1&smonth={current_month}&ajax=true",
-                competition_id=competition_id, current_month=current_month,
+                "{base_url}/main.aspx?c={competition_id}&stage=1&smonth={current_month}&ajax=true",
+                base_url=base_url, competition_id=competition.competition_id, current_month=current_month,
               );
 
-              // SEE: https://docs.rs/reqwest/0.11.7/reqwest/struct.RequestBuilder.html#method.send
-              let resp = client
-                // NOTE: Used to match exactly the URL used in the official page.
-                // SEE: https://www.goalzz.com/main.aspx?c=22551&stage=1&smonth=202108
-                // SEE: https://www.kooora.com/?c=22551&stage=1&smonth=202108
-                .get(tournament_url)
-                .send()
-                .unwrap()
-                .text()
-                .unwrap();
-
-              resp.replace('\n', "")
+              // NOTE: Used to match exactly the URL used in the official page.
+              // SEE: https://www.goalzz.com/main.aspx?c=22551&stage=1&smonth=202108
+              // SEE: https://www.kooora.com/?c=22551&stage=1&smonth=202108
+              let resp = transport.get_page(
+                &tournament_url,
+                &competition.name,
+                &current_month,
+              )?;
+
+              Ok(resp.replace('\n', ""))
             })
             .collect(),
         )
-      },
-    )
-    .collect()
+      })
+      .collect()
   }
 
   #[must_use]
   fn process_tournaments(
     all_tournaments_responses: Vec<(Arc<String>, Vec<String>)>,
-  ) -> Vec<(Arc<String>, Vec<MatchResult>)> {
-    use serde::Deserialize;
+    teams_registry: &TeamsRegistry,
+  ) -> FetchedTournaments<Vec<MatchOutcome>> {
+    all_tournaments_responses
+      .into_iter()
+      .map(|(tournament_name, responses)| {
+        let matches_outcomes = parse_koora_tables(&responses).map(|matches| {
+          matches
+            .iter()
+            .filter_map(|koora_match| {
+              koora_match.get_match_outcome(teams_registry)
+            })
+            .collect()
+        });
 
-    #[must_use]
-    #[derive(Deserialize)]
-    struct Table {
-      matches_list: Vec<serde_json::Value>,
-    }
+        (tournament_name, matches_outcomes)
+      })
+      .collect()
+  }
 
+  #[must_use]
+  fn process_tournaments_fixtures(
+    all_tournaments_responses: &[(Arc<String>, Vec<String>)],
+    teams_registry: &TeamsRegistry,
+  ) -> FetchedTournaments<Vec<Fixture>> {
     all_tournaments_responses
-      .into_iter()
-      .map(
-        |(tournament_name, responses)| -> (Arc<String>, Vec<MatchResult>) {
-          let matches_results: Vec<MatchResult> = responses
-            .into_iter()
-            .flat_map(|current_response| {
-              let table: Table =
-                serde_json::from_str(&current_response).unwrap();
-              let table_cells = table.matches_list;
-
-              let results: Vec<MatchResult> = (0..table_cells.len())
-                .filter_map(|i| -> Option<MatchResult> {
-                  let cell = &table_cells[i];
-
-                  match cell {
-                    serde_json::Value::String(_) => (),
-                    _ => return None,
-                  }
-
-                  let cell_value = cell.as_str().unwrap();
-
-                  let possible_scores: Vec<&str> =
-                    cell_value.split('|').collect();
-                  if possible_scores.len() != 2
-                    || possible_scores[0]
-                      .matches(char::is_numeric)
-                      .next()
-                      .is_none()
-                    || possible_scores[1]
-                      .matches(char::is_numeric)
-                      .next()
-                      .is_none()
-                  {
-                    return None;
-                  }
-
-                  let first_team_score: usize =
-                    possible_scores[0].parse().unwrap();
-                  let second_team_score: usize =
-                    possible_scores[1].parse().unwrap();
-
-                  let first_team_name = table_cells[i - 2].as_str().unwrap();
-                  let second_team_name = table_cells[i + 3].as_str().unwrap();
-
-                  Some((
-                    (Arc::new(String::from(first_team_name)), first_team_score),
-                    (
-                      Arc::new(String::from(second_team_name)),
-                      second_team_score,
-                    ),
-                  ))
-                })
-                .collect();
+      .iter()
+      .map(|(tournament_name, responses)| {
+        let fixtures = parse_koora_tables(responses).map(|matches| {
+          matches
+            .iter()
+            .filter_map(|koora_match| koora_match.get_fixture(teams_registry))
+            .collect()
+        });
 
-              results
+        (Arc::clone(tournament_name), fixtures)
+      })
+      .collect()
+  }
+}
+
+fn parse_koora_tables(
+  responses: &[String],
+) -> Result<Vec<KooraMatch>, FetchError> {
+  responses
+    .iter()
+    .map(|response| parse_koora_table(response))
+    .collect::<Result<Vec<_>, _>>()
+    .map(|matches| matches.concat())
+}
+
+// NOTE: Reads historic seasons from local CSV files (in football-data.co.uk's
+// layout) instead of fetching them.
+#[must_use]
+struct FootballData {}
+impl TournamentProvider for FootballData {
+  const NAME: &'static str = "football-data.co.uk";
+  const TEST_TOURNAMENT_NAME: &'static str = "English Premier League";
+  // NOTE: The first matchday of football-data.co.uk's `E0.csv` of the
+  // 2021-2022 season (trimmed to the columns that are read).
+  const TEST_DATA_FILE_ID: &'static str = "E0-2021-2022-matchday-1";
+  const TEST_DATA_PREFIX: &'static str = "football-data";
+  const ORIGIN: &'static str = "https://www.football-data.co.uk";
+  // NOTE: The directory that files' paths are relative to (the current one by
+  // default).
+  const BASE_URL: &'static str = "";
+
+  #[must_use]
+  fn tournaments_names(providers_config: &ProvidersConfig) -> Vec<Arc<String>> {
+    providers_config
+      .football_data
+      .iter()
+      .map(|competition| Arc::clone(&competition.name))
+      .collect()
+  }
+
+  #[must_use]
+  fn download_tournaments(
+    _transport: &dyn Transport,
+    base_url: &str,
+    providers_config: &ProvidersConfig,
+  ) -> FetchedTournaments<Vec<String>> {
+    providers_config
+      .football_data
+      .iter()
+      .map(|competition| {
+        let path = Path::new(base_url).join(&competition.path);
+        let lines = fs::read_to_string(&path)
+          .map(|csv| csv.lines().map(String::from).collect())
+          .map_err(|error| FetchError::Io {
+            path: path.display().to_string(),
+            message: error.to_string(),
+          });
+
+        (Arc::clone(&competition.name), lines)
+      })
+      .collect()
+  }
+
+  #[must_use]
+  fn process_tournaments(
+    all_tournaments_lines: Vec<(Arc<String>, Vec<String>)>,
+    teams_registry: &TeamsRegistry,
+  ) -> FetchedTournaments<Vec<MatchOutcome>> {
+    all_tournaments_lines
+      .into_iter()
+      .map(|(tournament_name, lines)| {
+        let matches_outcomes = parse_football_data_csv(&lines).map(|matches| {
+          let mut matches_outcomes: Vec<MatchOutcome> = matches
+            .iter()
+            .map(|football_data_match| {
+              football_data_match.get_match_outcome(teams_registry)
             })
             .collect();
+          // NOTE: Files are ordered by date but that isn't guaranteed.
+          matches_outcomes.sort_by_key(|match_outcome| match_outcome.date);
 
-          (tournament_name, matches_results)
-        },
-      )
+          matches_outcomes
+        });
+
+        (tournament_name, matches_outcomes)
+      })
       .collect()
   }
 }
 
+// NOTE: Reads tournaments from local JSON files in the interchange format (or
+// openfootball's) which unlike other providers' include their formats.
 #[must_use]
-fn get_client(origin: &'static str) -> Client {
-  use reqwest::header::HeaderMap;
-  use reqwest::header::HeaderValue;
-
-  // SEE: https://docs.rs/reqwest/0.11.7/reqwest/struct.ClientBuilder.html
-  let client = Client::builder()
-  .user_agent("Mozilla/5.0 (X11; Ubuntu; Linux x86_64; rv:95.0) Gecko/20100101 Firefox/95.0")
-  .referer(true)
-  .https_only(true)
-  .gzip(true)
-  .deflate(true)
-  .brotli(true);
-
-  // SEE: https://docs.rs/reqwest/0.11.7/reqwest/struct.ClientBuilder.html#method.default_headers
-  let mut headers = HeaderMap::new();
-  headers.insert(
-    "Content-Type",
-    HeaderValue::from_static(
-      "application/x-www-form-urlencoded; charset=UTF-8",
-    ),
+struct JsonFile {}
+impl JsonFile {
+  #[must_use]
+  fn import_tournaments(
+    all_tournaments_lines: Vec<(Arc<String>, Vec<String>)>,
+    teams_registry: &TeamsRegistry,
+  ) -> FetchedTournaments<ImportedTournament> {
+    all_tournaments_lines
+      .into_iter()
+      .map(|(tournament_name, lines)| {
+        (
+          tournament_name,
+          parse_interchange_tournament(&lines.join("\n"), teams_registry),
+        )
+      })
+      .collect()
+  }
+}
+impl TournamentProvider for JsonFile {
+  const NAME: &'static str = "JSON";
+  const TEST_TOURNAMENT_NAME: &'static str = "First Team - Premier League";
+  // NOTE: Premier League's stored data as written by
+  // `write_interchange_tournament()`.
+  const TEST_DATA_FILE_ID: &'static str = "2021-12-26T14:58:52";
+  const TEST_DATA_PREFIX: &'static str = "json";
+  const ORIGIN: &'static str = "https://github.com/openfootball";
+  // NOTE: The directory that files' paths are relative to (the current one by
+  // default).
+  const BASE_URL: &'static str = "";
+
+  #[must_use]
+  fn tournaments_names(providers_config: &ProvidersConfig) -> Vec<Arc<String>> {
+    providers_config
+      .json
+      .iter()
+      .map(|competition| Arc::clone(&competition.name))
+      .collect()
+  }
+
+  #[must_use]
+  fn download_tournaments(
+    _transport: &dyn Transport,
+    base_url: &str,
+    providers_config: &ProvidersConfig,
+  ) -> FetchedTournaments<Vec<String>> {
+    providers_config
+      .json
+      .iter()
+      .map(|competition| {
+        let path = Path::new(base_url).join(&competition.path);
+        let lines = fs::read_to_string(&path)
+          .map(|json| json.lines().map(String::from).collect())
+          .map_err(|error| FetchError::Io {
+            path: path.display().to_string(),
+            message: error.to_string(),
+          });
+
+        (Arc::clone(&competition.name), lines)
+      })
+      .collect()
+  }
+
+  #[must_use]
+  fn process_tournaments(
+    all_tournaments_lines: Vec<(Arc<String>, Vec<String>)>,
+    teams_registry: &TeamsRegistry,
+  ) -> FetchedTournaments<Vec<MatchOutcome>> {
+    Self::import_tournaments(all_tournaments_lines, teams_registry)
+      .into_iter()
+      .map(|(tournament_name, imported_tournament)| {
+        (
+          tournament_name,
+          imported_tournament
+            .map(|imported_tournament| imported_tournament.matches_outcomes),
+        )
+      })
+      .collect()
+  }
+
+  #[must_use]
+  fn process_tournaments_fixtures(
+    all_tournaments_lines: &[(Arc<String>, Vec<String>)],
+    teams_registry: &TeamsRegistry,
+  ) -> FetchedTournaments<Vec<Fixture>> {
+    Self::import_tournaments(all_tournaments_lines.to_vec(), teams_registry)
+      .into_iter()
+      .filter_map(|(tournament_name, imported_tournament)| {
+        match imported_tournament {
+          Ok(imported_tournament) => imported_tournament
+            .fixtures
+            .map(|fixtures| (tournament_name, Ok(fixtures))),
+          Err(error) => Some((tournament_name, Err(error))),
+        }
+      })
+      .collect()
+  }
+
+  // NOTE: Overridden as formats are part of the files (instead of being the
+  // same for all tournaments of the provider).
+  #[must_use]
+  fn fetch_tournaments_outcomes(
+    transport: &dyn Transport,
+    base_url: &str,
+    providers_config: &ProvidersConfig,
+  ) -> (Vec<FetchedTournament>, Vec<FetchFailure>) {
+    let mut failures = vec![];
+
+    let downloaded_tournaments = take_fetched(
+      Self::NAME,
+      Self::download_tournaments(transport, base_url, providers_config),
+      &mut failures,
+    );
+    let imported_tournaments = take_fetched(
+      Self::NAME,
+      Self::import_tournaments(downloaded_tournaments, &providers_config.teams),
+      &mut failures,
+    );
+
+    (
+      imported_tournaments
+        .into_iter()
+        .map(|(tournament_name, imported_tournament)| FetchedTournament {
+          name: tournament_name,
+          provider_name: Self::NAME,
+          matches_outcomes: imported_tournament.matches_outcomes,
+          fixtures: imported_tournament.fixtures,
+          format: imported_tournament.format,
+        })
+        .collect(),
+      failures,
+    )
+  }
+}
+
+// NOTE: Same as `fetch_tournaments_async()` but blocks on its own runtime (so
+// it can't be called from within another one).
+/// # Panics
+#[must_use]
+pub(super) fn fetch_tournaments(
+  providers_config: &ProvidersConfig,
+  all_tournaments_adjustments: &BTreeMap<Arc<String>, Vec<Adjustment>>,
+) -> (
+  Vec<Tournament>,
+  Vec<FetchFailure>,
+  Vec<Discrepancy>,
+  Vec<StaleTournament>,
+) {
+  block_on(fetch_tournaments_async(
+    providers_config,
+    all_tournaments_adjustments,
+  ))
+}
+
+// NOTE: Providers (and their tournaments) are fetched independently and only
+// the failed tournaments are missing. Tournaments fetched by more than one
+// provider are reconciled into a single tournament. Tournaments that couldn't
+// be refreshed are fetched from the cache (if any) and are stale.
+/// # Panics
+pub(super) async fn fetch_tournaments_async(
+  providers_config: &ProvidersConfig,
+  all_tournaments_adjustments: &BTreeMap<Arc<String>, Vec<Adjustment>>,
+) -> (
+  Vec<Tournament>,
+  Vec<FetchFailure>,
+  Vec<Discrepancy>,
+  Vec<StaleTournament>,
+) {
+  let (fetched_tournaments, failures, discrepancies, stale_tournaments) =
+    fetch_reconciled_tournaments(providers_config).await;
+
+  (
+    build_tournaments(fetched_tournaments, all_tournaments_adjustments),
+    failures,
+    discrepancies,
+    stale_tournaments,
+  )
+}
+
+// NOTE: Same as `fetch_tournaments()` but each tournament is written in the
+// interchange format (by name) instead of being built. Adjustments aren't
+// applied as they aren't part of the format.
+/// # Panics
+#[must_use]
+pub(super) fn export_tournaments(
+  providers_config: &ProvidersConfig,
+) -> (
+  ExportedTournaments,
+  Vec<FetchFailure>,
+  Vec<Discrepancy>,
+  Vec<StaleTournament>,
+) {
+  let (fetched_tournaments, failures, discrepancies, stale_tournaments) =
+    block_on(fetch_reconciled_tournaments(providers_config));
+
+  (
+    fetched_tournaments
+      .iter()
+      .map(|fetched_tournament| {
+        (
+          Arc::clone(&fetched_tournament.name),
+          write_interchange_tournament(fetched_tournament),
+        )
+      })
+      .collect(),
+    failures,
+    discrepancies,
+    stale_tournaments,
+  )
+}
+
+// NOTE: Tournaments of the enabled providers are fetched concurrently on the
+// runtime's blocking threads (as providers are blocking) while their requests
+// are limited per host.
+async fn fetch_reconciled_tournaments(
+  providers_config: &ProvidersConfig,
+) -> (
+  Vec<FetchedTournament>,
+  Vec<FetchFailure>,
+  Vec<Discrepancy>,
+  Vec<StaleTournament>,
+) {
+  let now = Utc::now().timestamp();
+  let transport = Arc::new(AsyncHttpTransport::new(FetchPolicy::default()));
+  let shared_providers_config = Arc::new(providers_config.clone());
+
+  let tournaments_tasks: Vec<_> = providers_config
+    .providers
+    .get_enabled_providers()
+    .into_iter()
+    .flat_map(|provider| {
+      provider
+        .tournaments_names(providers_config)
+        .into_iter()
+        .map(move |tournament_name| (Arc::clone(&provider), tournament_name))
+    })
+    .map(|(provider, tournament_name)| {
+      let runtime = Handle::current();
+      let transport = Arc::clone(&transport);
+      let providers_config = Arc::clone(&shared_providers_config);
+      let provider_name = provider.name();
+      let task_tournament_name = Arc::clone(&tournament_name);
+
+      (
+        provider_name,
+        task_tournament_name,
+        tokio::task::spawn_blocking(move || {
+          fetch_provider_tournaments(
+            &*provider,
+            &providers_config,
+            BlockingTransport::new(runtime, transport, provider.origin()),
+            &[tournament_name],
+            now,
+          )
+        }),
+      )
+    })
+    .collect();
+
+  let mut all_fetched_tournaments = vec![];
+  let mut all_failures = vec![];
+  let mut all_corrections = vec![];
+  let mut all_stale_tournaments = vec![];
+  for (provider_name, tournament_name, tournament_task) in tournaments_tasks {
+    // NOTE: A task that panicked only fails its own tournament (unless panics
+    // abort, e.g., in release builds).
+    let (fetched_tournaments, failures, corrections, stale_tournaments) =
+      match tournament_task.await {
+        Ok(fetched) => fetched,
+        Err(join_error) => (
+          vec![],
+          vec![FetchFailure {
+            provider_name,
+            tournament_name,
+            error: FetchError::Aborted {
+              message: join_error.to_string(),
+            },
+          }],
+          vec![],
+          vec![],
+        ),
+      };
+    all_fetched_tournaments.extend(fetched_tournaments);
+    all_failures.extend(failures);
+    all_corrections.extend(corrections);
+    all_stale_tournaments.extend(stale_tournaments);
+  }
+
+  let (fetched_tournaments, mut discrepancies) = reconcile_tournaments(
+    all_fetched_tournaments,
+    &providers_config.primary_providers,
+  );
+  // NOTE: A tournament that failed for a provider isn't failed if another
+  // provider fetched it.
+  all_failures.retain(|failure| {
+    fetched_tournaments.iter().all(|fetched_tournament| {
+      fetched_tournament.name != failure.tournament_name
+    })
+  });
+  discrepancies.extend(all_corrections);
+
+  (
+    fetched_tournaments,
+    all_failures,
+    discrepancies,
+    all_stale_tournaments,
+  )
+}
+
+// NOTE: Tournaments' pages are cached when there's a cache directory. Stale
+// tournaments are only those fetched (and not those that failed anyway).
+// Tournaments of incremental providers are also synced there (and only their
+// newer matches fetched) with their corrections as discrepancies.
+#[must_use]
+fn fetch_provider_tournaments<T: Transport>(
+  provider: &dyn Provider,
+  providers_config: &ProvidersConfig,
+  transport: T,
+  tournaments_names: &[Arc<String>],
+  now: i64,
+) -> (
+  Vec<FetchedTournament>,
+  Vec<FetchFailure>,
+  Vec<Discrepancy>,
+  Vec<StaleTournament>,
+) {
+  let Some(cache_dir) = &providers_config.cache_dir else {
+    let (fetched_tournaments, failures) = provider.fetch_tournaments_outcomes(
+      &transport,
+      providers_config,
+      tournaments_names,
+    );
+
+    return (fetched_tournaments, failures, vec![], vec![]);
+  };
+
+  let results_store = ResultsStore::new(provider.name(), cache_dir, now);
+  let mut synced_tournaments: BTreeMap<Arc<String>, SyncedTournament> =
+    if provider.is_incremental() {
+      tournaments_names
+        .iter()
+        .filter_map(|tournament_name| {
+          results_store
+            .read(tournament_name)
+            .map(|synced_tournament| {
+              (Arc::clone(tournament_name), synced_tournament)
+            })
+        })
+        .collect()
+    } else {
+      BTreeMap::new()
+    };
+  let providers_config = ProvidersConfig {
+    synced_since: synced_tournaments
+      .iter()
+      .filter_map(|(tournament_name, synced_tournament)| {
+        synced_tournament
+          .since
+          .map(|since| (Arc::clone(tournament_name), since))
+      })
+      .collect(),
+    ..providers_config.clone()
+  };
+
+  let transport = CachingTransport::new(
+    transport,
+    provider.name(),
+    cache_dir,
+    providers_config.cache_ttl_seconds,
+    now,
   );
-  headers.insert("Sec-Fetch-Dest", HeaderValue::from_static("empty"));
-  headers.insert("Sec-Fetch-Mode", HeaderValue::from_static("cors"));
-  headers.insert("Sec-Fetch-Site", HeaderValue::from_static("cross-site"));
-  headers.insert(
-    "Accept-Language",
-    HeaderValue::from_static("en-US,en;q=0.5"),
+  let (fetched_tournaments, failures) = provider.fetch_tournaments_outcomes(
+    &transport,
+    &providers_config,
+    tournaments_names,
   );
-  headers.insert("Origin", HeaderValue::from_static(origin));
 
-  client.default_headers(headers).build().unwrap()
+  let mut corrections = vec![];
+  let fetched_tournaments: Vec<FetchedTournament> = if provider.is_incremental()
+  {
+    fetched_tournaments
+      .into_iter()
+      .map(|fetched_tournament| {
+        let synced_tournament =
+          synced_tournaments.remove(&fetched_tournament.name);
+        let (merged_tournament, tournament_corrections) =
+          results_store.merge(fetched_tournament, synced_tournament);
+        corrections.extend(tournament_corrections);
+
+        merged_tournament
+      })
+      .collect()
+  } else {
+    fetched_tournaments
+  };
+  let stale_tournaments = transport
+    .take_stale_tournaments()
+    .into_iter()
+    .filter(|(tournament_name, _)| {
+      fetched_tournaments
+        .iter()
+        .any(|fetched_tournament| fetched_tournament.name == *tournament_name)
+    })
+    .map(|(tournament_name, (fetched_at, error))| StaleTournament {
+      provider_name: provider.name(),
+      tournament_name,
+      fetched_at,
+      error,
+    })
+    .collect();
+
+  (
+    fetched_tournaments,
+    failures,
+    corrections,
+    stale_tournaments,
+  )
 }
 
+// NOTE: Built on first use and shared by all blocking calls (see
+// `block_on()`).
+static RUNTIME: Mutex<Option<Arc<Runtime>>> = Mutex::new(None);
+
 /// # Panics
 #[must_use]
-pub(super) fn fetch_tournaments() -> Vec<Tournament> {
-  Koora::fetch_tournaments()
+fn get_runtime() -> Arc<Runtime> {
+  Arc::clone(RUNTIME.lock().unwrap().get_or_insert_with(|| {
+    Arc::new(
+      tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .expect("Unable to start runtime"),
+    )
+  }))
+}
+
+// NOTE: The blocking API (e.g., for FFI) is a wrapper of the async one. When
+// called from within another runtime (which can't be blocked), the future is
+// blocked on from another thread instead.
+/// # Panics
+fn block_on<F>(future: F) -> F::Output
+where
+  F: Future + Send,
+  F::Output: Send,
+{
+  let runtime = get_runtime();
+  if Handle::try_current().is_err() {
+    return runtime.block_on(future);
+  }
+
+  thread::scope(|scope| {
+    scope
+      .spawn(|| runtime.block_on(future))
+      .join()
+      .unwrap_or_else(|panic| panic::resume_unwind(panic))
+  })
+}
+
+/// # Panics
+#[must_use]
+pub(super) fn test_helper_stored_tournaments_results(
+) -> Vec<(Arc<String>, Vec<MatchResult>)> {
+  Koora::stored_tournaments_results()
     .into_iter()
-    .chain(PremierLeague::fetch_tournaments())
+    .chain(PremierLeague::stored_tournaments_results())
     .collect()
 }
 
@@ -347,6 +999,43 @@ pub(super) fn test() {
   #[cfg(test)]
   use pretty_assertions::assert_eq;
 
+  async_transport::test();
+  cache::test();
+  common::test();
+  config::test();
+  discovery::test();
+  football_data::test();
+  interchange::test();
+  koora_table::test();
+  teams::test();
+  reconciliation::test();
+  registry::test();
+  sync::test();
+
+  let (_, premier_league_outcomes) = PremierLeague::process_tournaments(
+    PremierLeague::test_helper_download_tournaments(),
+    &TeamsRegistry::default(),
+  )
+  .pop()
+  .unwrap();
+  let premier_league_outcomes = premier_league_outcomes.unwrap();
+  assert_eq!(
+    premier_league_outcomes.last().unwrap(),
+    &MatchOutcome {
+      home_team_name: Arc::new(String::from("Tottenham Hotspur")),
+      away_team_name: Arc::new(String::from("Liverpool")),
+      regulation_score: Some((2, 2)),
+      extra_time_score: None,
+      shootout_score: None,
+      status: MatchStatus::Finished,
+      date: Some(1_639_931_400),
+    },
+  );
+  assert!(premier_league_outcomes.iter().all(|match_outcome| {
+    match_outcome.status == MatchStatus::Finished
+      && match_outcome.extra_time_score.is_none()
+  }));
+
   assert_eq!(
     Koora::test_fetch_tournaments().first().unwrap(),
     &Tournament::new(
@@ -357,8 +1046,8 @@ pub(super) fn test() {
           1,
           19,
           11,
-          15,
           2,
+          15,
           2,
           47,
           33,
@@ -369,8 +1058,8 @@ pub(super) fn test() {
           2,
           20,
           10,
-          11,
           7,
+          11,
           2,
           40,
           30,
@@ -381,8 +1070,8 @@ pub(super) fn test() {
           3,
           20,
           10,
-          12,
           2,
+          12,
           6,
           38,
           30,
@@ -393,8 +1082,8 @@ pub(super) fn test() {
           4,
           17,
           13,
-          8,
           7,
+          8,
           2,
           31,
           39,
@@ -405,8 +1094,8 @@ pub(super) fn test() {
           5,
           20,
           10,
-          8,
           6,
+          8,
           6,
           30,
           30,
@@ -417,9 +1106,9 @@ pub(super) fn test() {
           6,
           20,
           10,
-          8,
           4,
           8,
+          8,
           28,
           30,
           None,
@@ -429,8 +1118,8 @@ pub(super) fn test() {
           7,
           19,
           11,
-          6,
           8,
+          6,
           5,
           26,
           33,
@@ -441,8 +1130,8 @@ pub(super) fn test() {
           8,
           20,
           10,
-          7,
           4,
+          7,
           9,
           25,
           30,
@@ -453,8 +1142,8 @@ pub(super) fn test() {
           9,
           20,
           10,
-          5,
           9,
+          5,
           6,
           24,
           30,
@@ -465,8 +1154,8 @@ pub(super) fn test() {
           10,
           20,
           10,
-          7,
           1,
+          7,
           12,
           22,
           30,
@@ -477,8 +1166,8 @@ pub(super) fn test() {
           11,
           20,
           10,
-          5,
           6,
+          5,
           9,
           21,
           30,
@@ -489,8 +1178,8 @@ pub(super) fn test() {
           12,
           19,
           11,
-          4,
           8,
+          4,
           7,
           20,
           33,
@@ -501,8 +1190,8 @@ pub(super) fn test() {
           13,
           19,
           11,
-          4,
           8,
+          4,
           7,
           20,
           33,
@@ -513,8 +1202,8 @@ pub(super) fn test() {
           14,
           20,
           10,
-          4,
           7,
+          4,
           9,
           19,
           30,
@@ -525,8 +1214,8 @@ pub(super) fn test() {
           15,
           19,
           11,
-          4,
           6,
+          4,
           9,
           18,
           33,
@@ -537,8 +1226,8 @@ pub(super) fn test() {
           16,
           20,
           10,
-          3,
           5,
+          3,
           12,
           14,
           30,
@@ -684,6 +1373,44 @@ pub(super) fn test() {
     )
   );
 
+  let (_, koora_fixtures) = Koora::stored_tournaments_fixtures()
+    .into_iter()
+    .next()
+    .unwrap();
+  assert_eq!(koora_fixtures.len(), 44);
+  assert_eq!(
+    koora_fixtures
+      .iter()
+      .filter(|fixture| fixture.status == FixtureStatus::Postponed)
+      .map(|fixture| (
+        fixture.home_team_name.as_str(),
+        fixture.away_team_name.as_str()
+      ))
+      .collect::<Vec<_>>(),
+    vec![
+      ("Al Fayha", "Al Hilal"),
+      ("Al Ettifaq", "Al Hilal"),
+      ("Al Fateh", "Al Faisaly"),
+    ],
+  );
+  assert_eq!(
+    koora_fixtures
+      .iter()
+      .find(|fixture| fixture.status != FixtureStatus::Scheduled
+        && fixture.status != FixtureStatus::Postponed)
+      .unwrap(),
+    &Fixture {
+      home_team_name: Arc::new(String::from("Al Hilal")),
+      away_team_name: Arc::new(String::from("Al Ittihad")),
+      date: Some(1_646_761_500),
+      venue: Some(String::from("Prince Faisal Bin Fahd Stadium")),
+      status: FixtureStatus::Rescheduled {
+        original_date: 1_637_452_800,
+      },
+    },
+  );
+  assert_eq!(PremierLeague::stored_tournaments_fixtures(), vec![],);
+
   assert_eq!(
     PremierLeague::test_fetch_tournaments().first().unwrap(),
     &Tournament::new(
@@ -694,8 +1421,8 @@ pub(super) fn test() {
           1,
           18,
           20,
-          14,
           2,
+          14,
           2,
           44,
           60,
@@ -706,8 +1433,8 @@ pub(super) fn test() {
           2,
           18,
           20,
-          12,
           5,
+          12,
           1,
           41,
           60,
@@ -718,8 +1445,8 @@ pub(super) fn test() {
           3,
           18,
           20,
-          11,
           5,
+          11,
           2,
           38,
           60,
@@ -730,8 +1457,8 @@ pub(super) fn test() {
           4,
           18,
           20,
-          10,
           2,
+          10,
           6,
           32,
           60,
@@ -742,8 +1469,8 @@ pub(super) fn test() {
           5,
           17,
           21,
-          8,
           4,
+          8,
           5,
           28,
           63,
@@ -754,8 +1481,8 @@ pub(super) fn test() {
           6,
           16,
           22,
-          8,
           3,
+          8,
           5,
           27,
           66,
@@ -766,8 +1493,8 @@ pub(super) fn test() {
           7,
           15,
           23,
-          8,
           2,
+          8,
           5,
           26,
           69,
@@ -778,9 +1505,9 @@ pub(super) fn test() {
           8,
           18,
           20,
-          7,
           4,
           7,
+          7,
           25,
           60,
           None,
@@ -790,8 +1517,8 @@ pub(super) fn test() {
           9,
           17,
           21,
-          7,
           1,
+          7,
           9,
           22,
           63,
@@ -802,9 +1529,9 @@ pub(super) fn test() {
           10,
           16,
           22,
-          6,
           4,
           6,
+          6,
           22,
           66,
           None,
@@ -826,9 +1553,9 @@ pub(super) fn test() {
           12,
           16,
           22,
-          4,
           8,
           4,
+          4,
           20,
           66,
           None,
@@ -838,8 +1565,8 @@ pub(super) fn test() {
           13,
           17,
           21,
-          4,
           8,
+          4,
           5,
           20,
           63,
@@ -850,8 +1577,8 @@ pub(super) fn test() {
           14,
           17,
           21,
-          5,
           4,
+          5,
           8,
           19,
           63,
@@ -862,8 +1589,8 @@ pub(super) fn test() {
           15,
           17,
           21,
-          3,
           8,
+          3,
           6,
           17,
           63,
@@ -874,8 +1601,8 @@ pub(super) fn test() {
           16,
           18,
           20,
-          3,
           7,
+          3,
           8,
           16,
           60,
@@ -886,8 +1613,8 @@ pub(super) fn test() {
           17,
           16,
           22,
-          4,
           1,
+          4,
           11,
           13,
           66,
@@ -898,8 +1625,8 @@ pub(super) fn test() {
           18,
           15,
           23,
-          1,
           8,
+          1,
           6,
           11,
           69,
@@ -910,8 +1637,8 @@ pub(super) fn test() {
           19,
           18,
           20,
-          1,
           7,
+          1,
           10,
           10,
           60,
@@ -922,8 +1649,8 @@ pub(super) fn test() {
           20,
           17,
           21,
-          2,
           4,
+          2,
           11,
           10,
           63,
@@ -1138,14 +1865,803 @@ pub(super) fn test() {
       ),
     )
   );
+
+  test_record_and_replay();
+  test_football_data();
+  test_json_file();
+  test_block_on();
+}
+
+#[cfg(test)]
+#[must_use]
+fn get_stored_responses() -> (Vec<String>, Vec<String>) {
+  let (_, koora_responses) =
+    Koora::test_helper_download_tournaments().pop().unwrap();
+  let (_, premier_league_responses) =
+    PremierLeague::test_helper_download_tournaments()
+      .pop()
+      .unwrap();
+
+  (koora_responses, premier_league_responses)
+}
+
+// NOTE: Only the test tournaments have stored responses (of the months of the
+// 2021-2022 season for Koora) and other requests get empty ones as if their
+// tournaments had no matches.
+#[cfg(test)]
+#[must_use]
+fn get_stored_response(
+  koora_responses: &[String],
+  premier_league_responses: &[String],
+  url: &str,
+) -> String {
+  const KOORA_MONTHS: [&str; 8] = [
+    "202108", "202109", "202110", "202111", "202112", "202201", "202202",
+    "202203",
+  ];
+
+  if url.contains("/main.aspx?") {
+    KOORA_MONTHS
+      .iter()
+      .position(|month| {
+        url.contains("c=22551&") && url.contains(&format!("smonth={month}&"))
+      })
+      .map_or_else(
+        || String::from(r#"{"matches_list":[-1]}"#),
+        |i| koora_responses[i].clone(),
+      )
+  } else {
+    (0..premier_league_responses.len())
+      .find(|page| {
+        url.contains("comps=1&") && url.contains(&format!("&page={page}&"))
+      })
+      .map_or_else(
+        || String::from(r#"{"content":[]}"#),
+        |i| premier_league_responses[i].clone(),
+      )
+  }
+}
+
+// NOTE: The stored CSV file is the first matchday of a real season (with
+// football-data.co.uk's names for teams) so teams must be aliased and only
+// have their first match played.
+#[allow(clippy::too_many_lines)]
+fn test_football_data() {
+  #[cfg(test)]
+  use pretty_assertions::assert_eq;
+
+  use crate::tournament::fetching::config::FileCompetition;
+  use crate::tournament::fetching::teams::parse_teams_registry;
+
+  const ALIASES: [(&str, &str); 10] = [
+    ("Brighton and Hove Albion", "Brighton"),
+    ("Leeds United", "Leeds"),
+    ("Leicester City", "Leicester"),
+    ("Manchester City", "Man City"),
+    ("Manchester United", "Man United"),
+    ("Newcastle United", "Newcastle"),
+    ("Norwich City", "Norwich"),
+    ("Tottenham Hotspur", "Tottenham"),
+    ("West Ham United", "West Ham"),
+    ("Wolverhampton Wanderers", "Wolves"),
+  ];
+
+  let teams_json = format!(
+    "[{}]",
+    ALIASES
+      .iter()
+      .map(|(name, alias)| {
+        format!(
+          r#"{{"id": "{}", "name": "{}", "aliases": {{"{}": {{"names": ["{}"]}}}}}}"#,
+          alias,
+          name,
+          FootballData::NAME,
+          alias,
+        )
+      })
+      .join(","),
+  );
+  let get_providers_config = |path: &str| ProvidersConfig {
+    football_data: vec![FileCompetition {
+      name: Arc::new(String::from(FootballData::TEST_TOURNAMENT_NAME)),
+      path: Path::new(path).to_path_buf(),
+    }],
+    teams: parse_teams_registry(serde_json::from_str(&teams_json).unwrap())
+      .unwrap(),
+    ..ProvidersConfig::default()
+  };
+  let fetch = |path: &str| {
+    FootballData::fetch_tournaments(
+      &HttpTransport::new(FootballData::ORIGIN),
+      "data",
+      &get_providers_config(path),
+      &BTreeMap::new(),
+    )
+  };
+
+  let (football_data_tournaments, football_data_failures) = fetch(&format!(
+    "{}-{}",
+    FootballData::TEST_DATA_PREFIX,
+    FootballData::TEST_DATA_FILE_ID,
+  ));
+  assert_eq!(football_data_failures, vec![]);
+  let football_data_tournament = football_data_tournaments.first().unwrap();
+  assert_eq!(
+    football_data_tournament.name,
+    Arc::new(String::from(FootballData::TEST_TOURNAMENT_NAME)),
+  );
+  assert_eq!(football_data_tournament.teams.len(), 20);
+  let get_team_stats = |team_name: &str| {
+    let team = football_data_tournament
+      .teams
+      .iter()
+      .find(|team| *team.name == team_name)
+      .unwrap();
+
+    (
+      team.matches_played,
+      team.matches_left,
+      team.matches_won,
+      team.matches_drawn,
+      team.matches_lost,
+      team.earned_points,
+      team.remaining_points,
+    )
+  };
+  for (team_name, team_stats) in [
+    ("Brentford", (1, 37, 1, 0, 0, 3, 111)),
+    ("Arsenal", (1, 37, 0, 0, 1, 0, 111)),
+    ("Manchester United", (1, 37, 1, 0, 0, 3, 111)),
+    ("Wolverhampton Wanderers", (1, 37, 0, 0, 1, 0, 111)),
+    ("Tottenham Hotspur", (1, 37, 1, 0, 0, 3, 111)),
+    ("Manchester City", (1, 37, 0, 0, 1, 0, 111)),
+  ] {
+    assert_eq!(get_team_stats(team_name), team_stats, "{}", team_name);
+  }
+  // NOTE: Teams with points are ranked before the others.
+  assert_eq!(
+    football_data_tournament
+      .teams
+      .iter()
+      .take_while(|team| team.earned_points == 3)
+      .count(),
+    10,
+  );
+
+  let (football_data_tournaments, football_data_failures) =
+    fetch("missing.csv");
+  assert_eq!(football_data_tournaments, vec![]);
+  assert!(matches!(
+    football_data_failures.as_slice(),
+    [FetchFailure {
+      provider_name: FootballData::NAME,
+      error: FetchError::Io { .. },
+      ..
+    }],
+  ));
+}
+
+// NOTE: The stored document is Premier League's stored data as written by
+// `write_interchange_tournament()` so it must be read as the same tournament
+// and written back as is (which keeps the format's version stable).
+fn test_json_file() {
+  #[cfg(test)]
+  use pretty_assertions::assert_eq;
+
+  use crate::tournament::fetching::config::FileCompetition;
+
+  let path = format!(
+    "{}-{}",
+    JsonFile::TEST_DATA_PREFIX,
+    JsonFile::TEST_DATA_FILE_ID,
+  );
+  let providers_config = ProvidersConfig {
+    json: vec![FileCompetition {
+      name: Arc::new(String::from(JsonFile::TEST_TOURNAMENT_NAME)),
+      path: Path::new(&path).to_path_buf(),
+    }],
+    ..ProvidersConfig::default()
+  };
+
+  assert_eq!(
+    JsonFile::fetch_tournaments(
+      &HttpTransport::new(JsonFile::ORIGIN),
+      "data",
+      &providers_config,
+      &BTreeMap::new(),
+    ),
+    (PremierLeague::test_fetch_tournaments(), vec![]),
+  );
+
+  let (fetched_tournaments, failures) = JsonFile::fetch_tournaments_outcomes(
+    &HttpTransport::new(JsonFile::ORIGIN),
+    "data",
+    &providers_config,
+  );
+  assert_eq!(failures, vec![]);
+  assert_eq!(
+    write_interchange_tournament(fetched_tournaments.first().unwrap()),
+    fs::read_to_string(Path::new("data").join(&path)).unwrap(),
+  );
+
+  // NOTE: The first matchday of openfootball's `en.1.json` of the 2021-2022
+  // season (trimmed to the fields that are read) which has no unplayed
+  // matches so all of the season's other matches are assumed from the format.
+  let open_football_tournament_name =
+    Arc::new(String::from("English Premier League"));
+  let (open_football_tournaments, failures) = JsonFile::fetch_tournaments(
+    &HttpTransport::new(JsonFile::ORIGIN),
+    "data",
+    &ProvidersConfig {
+      json: vec![FileCompetition {
+        name: Arc::clone(&open_football_tournament_name),
+        path: Path::new(&format!(
+          "{}-openfootball-en.1-2021-2022-matchday-1",
+          JsonFile::TEST_DATA_PREFIX,
+        ))
+        .to_path_buf(),
+      }],
+      ..ProvidersConfig::default()
+    },
+    &BTreeMap::new(),
+  );
+  assert_eq!(failures, vec![]);
+  let open_football_tournament = open_football_tournaments.first().unwrap();
+  assert_eq!(open_football_tournament.name, open_football_tournament_name);
+  assert_eq!(open_football_tournament.teams.len(), 20);
+  let get_team_stats = |team_name: &str| {
+    let team = open_football_tournament
+      .teams
+      .iter()
+      .find(|team| *team.name == team_name)
+      .unwrap();
+
+    (
+      team.matches_played,
+      team.matches_left,
+      team.matches_won,
+      team.earned_points,
+      team.remaining_points,
+    )
+  };
+  for (team_name, team_stats) in [
+    ("Brentford FC", (1, 37, 1, 3, 111)),
+    ("Arsenal FC", (1, 37, 0, 0, 111)),
+    ("Brighton & Hove Albion FC", (1, 37, 1, 3, 111)),
+    ("Manchester City FC", (1, 37, 0, 0, 111)),
+  ] {
+    assert_eq!(get_team_stats(team_name), team_stats, "{}", team_name);
+  }
+}
+
+// NOTE: The runtime is shared (even when blocked on from within another one)
+// and a tournament whose fetching panics only fails on its own.
+fn test_block_on() {
+  #[cfg(test)]
+  use pretty_assertions::assert_eq;
+
+  use crate::tournament::fetching::registry::ProvidersRegistry;
+
+  struct PanickingProvider {}
+  impl Provider for PanickingProvider {
+    fn name(&self) -> &'static str {
+      "Panicking"
+    }
+
+    fn origin(&self) -> &'static str {
+      "http://localhost"
+    }
+
+    fn tournaments_names(
+      &self,
+      _providers_config: &ProvidersConfig,
+    ) -> Vec<Arc<String>> {
+      vec![Arc::new(String::from("Panicking League"))]
+    }
+
+    fn fetch_tournaments_outcomes(
+      &self,
+      _transport: &dyn Transport,
+      _providers_config: &ProvidersConfig,
+      _tournaments_names: &[Arc<String>],
+    ) -> (Vec<FetchedTournament>, Vec<FetchFailure>) {
+      panic!("Unexpected page");
+    }
+  }
+
+  assert!(Arc::ptr_eq(&get_runtime(), &get_runtime()));
+  assert_eq!(block_on(async { 1 }), 1);
+  assert_eq!(
+    tokio::runtime::Builder::new_current_thread()
+      .build()
+      .unwrap()
+      .block_on(async { block_on(async { 2 }) }),
+    2,
+  );
+
+  let mut providers_registry = ProvidersRegistry::default();
+  for (provider_name, _) in providers_registry.get_providers() {
+    assert!(providers_registry.set_enabled(provider_name, false));
+  }
+  assert!(providers_registry.register(Arc::new(PanickingProvider {})));
+  let (fetched_tournaments, failures, discrepancies, stale_tournaments) =
+    block_on(fetch_reconciled_tournaments(&ProvidersConfig {
+      providers: providers_registry,
+      ..ProvidersConfig::default()
+    }));
+  assert!(fetched_tournaments.is_empty());
+  assert_eq!((discrepancies, stale_tournaments), (vec![], vec![]));
+  assert!(matches!(
+    failures.as_slice(),
+    [FetchFailure {
+      provider_name: "Panicking",
+      tournament_name,
+      error: FetchError::Aborted { .. },
+    }] if **tournament_name == "Panicking League",
+  ));
+}
+
+// NOTE: Replays the cassettes under `data/` which are the real responses (with
+// their URLs) of the test tournaments of the providers that fetch through a
+// transport. They were recorded along with the stored responses (i.e., Koora's
+// only cover the season's months from August to March) so other tournaments
+// can only be replayed once they're recorded too (see
+// `test_helper_record_cassette()`).
+#[allow(clippy::too_many_lines)]
+fn test_record_and_replay() {
+  #[cfg(test)]
+  use pretty_assertions::assert_eq;
+
+  use chrono::NaiveDate;
+
+  use crate::tournament::fetching::transport::read_cassette;
+
+  let get_cassette_path = |prefix: &str, file_id: &str| {
+    Path::new(&format!("data/{prefix}-cassette-{file_id}")).to_path_buf()
+  };
+  let koora_cassette_path =
+    get_cassette_path(Koora::TEST_DATA_PREFIX, Koora::TEST_DATA_FILE_ID);
+  let premier_league_cassette_path = get_cassette_path(
+    PremierLeague::TEST_DATA_PREFIX,
+    PremierLeague::TEST_DATA_FILE_ID,
+  );
+  let mut koora_providers_config = ProvidersConfig::test_helper_pinned()
+    .with_tournaments(&[Arc::new(String::from(Koora::TEST_TOURNAMENT_NAME))]);
+  for competition in &mut koora_providers_config.koora {
+    competition.season_start = NaiveDate::from_ymd_opt(2021, 8, 1).unwrap();
+    competition.season_end = NaiveDate::from_ymd_opt(2022, 3, 31).unwrap();
+  }
+  let premier_league_providers_config = ProvidersConfig::test_helper_pinned()
+    .with_tournaments(&[Arc::new(String::from(
+      PremierLeague::TEST_TOURNAMENT_NAME,
+    ))]);
+
+  assert_eq!(
+    Koora::fetch_tournaments(
+      &ReplayTransport::new(&koora_cassette_path).unwrap(),
+      Koora::BASE_URL,
+      &koora_providers_config,
+      &BTreeMap::new(),
+    ),
+    (Koora::test_fetch_tournaments(), vec![]),
+  );
+  assert_eq!(
+    PremierLeague::fetch_tournaments(
+      &ReplayTransport::new(&premier_league_cassette_path).unwrap(),
+      PremierLeague::BASE_URL,
+      &premier_league_providers_config,
+      &BTreeMap::new(),
+    ),
+    (PremierLeague::test_fetch_tournaments(), vec![]),
+  );
+  let to_replayed =
+    |downloaded_tournaments: Vec<(Arc<String>, Vec<String>)>| {
+      downloaded_tournaments
+        .into_iter()
+        .map(|(tournament_name, responses)| (tournament_name, Ok(responses)))
+        .collect::<Vec<_>>()
+    };
+  assert_eq!(
+    Koora::replay_tournaments(&koora_cassette_path, &koora_providers_config)
+      .unwrap(),
+    to_replayed(Koora::test_helper_download_tournaments()),
+  );
+  assert_eq!(
+    PremierLeague::replay_tournaments(
+      &premier_league_cassette_path,
+      &premier_league_providers_config,
+    )
+    .unwrap(),
+    to_replayed(PremierLeague::test_helper_download_tournaments()),
+  );
+
+  // NOTE: Recording while replaying records the same requests again (only
+  // later).
+  let recorded_cassette_path = std::env::temp_dir().join(format!(
+    "boa-cassette-{}-{:?}",
+    std::process::id(),
+    std::thread::current().id(),
+  ));
+  let _ignored_tournaments = Koora::download_tournaments(
+    &RecordingTransport::new(
+      ReplayTransport::new(&koora_cassette_path).unwrap(),
+      &recorded_cassette_path,
+    )
+    .unwrap(),
+    Koora::BASE_URL,
+    &koora_providers_config,
+  );
+  let recorded_requests = read_cassette(&recorded_cassette_path).unwrap();
+  let koora_recorded_requests = read_cassette(&koora_cassette_path).unwrap();
+  assert_eq!(
+    recorded_requests
+      .iter()
+      .map(|recorded_request| (
+        &recorded_request.url,
+        &recorded_request.response
+      ))
+      .collect::<Vec<_>>(),
+    koora_recorded_requests
+      .iter()
+      .map(|recorded_request| (
+        &recorded_request.url,
+        &recorded_request.response
+      ))
+      .collect::<Vec<_>>(),
+  );
+  assert!(recorded_requests.iter().zip(&koora_recorded_requests).all(
+    |(recorded_request, koora_recorded_request)| {
+      recorded_request.recorded_at > koora_recorded_request.recorded_at
+    }
+  ));
+  fs::remove_file(recorded_cassette_path).unwrap();
+
+  // NOTE: Cassettes that can't be read or written fail instead of panicking.
+  assert!(matches!(
+    ReplayTransport::new(Path::new("data/missing-cassette")),
+    Err(FetchError::Io { .. }),
+  ));
+  assert!(matches!(
+    ReplayTransport::new(Path::new("data/providers-2021-2022.json")),
+    Err(FetchError::Parse { .. }),
+  ));
+  assert!(matches!(
+    RecordingTransport::new(
+      ReplayTransport::new(&koora_cassette_path).unwrap(),
+      Path::new("data/missing/cassette"),
+    ),
+    Err(FetchError::Io { .. }),
+  ));
+  assert!(matches!(
+    ReplayTransport::new(&koora_cassette_path)
+      .unwrap()
+      .get(Koora::BASE_URL),
+    Err(FetchError::Network { .. }),
+  ));
+}
+
+// NOTE: Unlike other tests, this one only runs with `cargo test` as it needs a
+// local server.
+#[cfg(test)]
+#[allow(clippy::too_many_lines)]
+fn test_mock_server() {
+  use pretty_assertions::assert_eq;
+
+  let (koora_responses, premier_league_responses) = get_stored_responses();
+  let server = MockServer::new(move |path| {
+    Some(get_stored_response(
+      &koora_responses,
+      &premier_league_responses,
+      path,
+    ))
+  });
+  let transport = HttpTransport::with_https_only(Koora::ORIGIN, false);
+  let providers_config = ProvidersConfig::test_helper_pinned();
+
+  assert_eq!(
+    Koora::fetch_tournaments(
+      &transport,
+      &server.base_url,
+      &providers_config,
+      &BTreeMap::new()
+    ),
+    (Koora::test_fetch_tournaments(), vec![]),
+  );
+  // NOTE: A request per month for each tournament.
+  assert_eq!(server.requests_count(), 7 * 24);
+  assert_eq!(
+    PremierLeague::fetch_tournaments(
+      &transport,
+      &server.base_url,
+      &providers_config,
+      &BTreeMap::new(),
+    ),
+    (PremierLeague::test_fetch_tournaments(), vec![]),
+  );
+  // NOTE: Pages are requested until an empty one which is the sixth for the
+  // first tournament and the first for the other 4.
+  assert_eq!(server.requests_count(), 7 * 24 + 6 + 4);
+
+  // NOTE: Pages that are never empty stop at the items limit (2000 items in
+  // pages of 40) for each of the 5 tournaments.
+  let server = MockServer::new(|_| Some(String::from(r#"{"content":[{}]}"#)));
+  assert_eq!(
+    PremierLeague::download_tournaments(
+      &transport,
+      &server.base_url,
+      &providers_config,
+    )
+    .into_iter()
+    .map(|(_, pages)| pages)
+    .collect::<Vec<_>>(),
+    vec![Err(FetchError::TooManyPages { pages_count: 51 }); 5],
+  );
+  assert_eq!(server.requests_count(), 5 * 51);
+
+  for handler in [
+    |_: &str| Some(String::from("not json")),
+    |_: &str| Some(String::from(r#"{"matches_list":"#)),
+    |_: &str| Some(String::from(r#"{"matches_list":[5,"Al Hilal","1|2"]}"#)),
+  ] {
+    let server = MockServer::new(handler);
+    let (tournaments, failures) = Koora::fetch_tournaments(
+      &transport,
+      &server.base_url,
+      &providers_config,
+      &BTreeMap::new(),
+    );
+    assert_eq!(tournaments, vec![]);
+    assert_eq!(failures.len(), 7);
+    assert!(failures
+      .iter()
+      .all(|failure| failure.provider_name == Koora::NAME
+        && matches!(failure.error, FetchError::Parse { .. })));
+  }
+
+  // NOTE: Only the failed tournament is missing.
+  let (koora_responses, premier_league_responses) = get_stored_responses();
+  let server = MockServer::new(move |path| {
+    if path.contains("c=22279&") || path.contains("comps=2&") {
+      return None;
+    }
+
+    Some(get_stored_response(
+      &koora_responses,
+      &premier_league_responses,
+      path,
+    ))
+  });
+  let (koora_tournaments, koora_failures) = Koora::fetch_tournaments(
+    &transport,
+    &server.base_url,
+    &providers_config,
+    &BTreeMap::new(),
+  );
+  assert_eq!(koora_tournaments, Koora::test_fetch_tournaments());
+  assert_eq!(
+    koora_failures
+      .iter()
+      .map(|failure| (failure.provider_name, failure.tournament_name.as_str()))
+      .collect::<Vec<_>>(),
+    vec![(Koora::NAME, "Saudi U-13 Premier League")],
+  );
+  assert!(matches!(
+    &koora_failures[0].error,
+    FetchError::Network { url, .. } if url.contains("c=22279&")
+  ));
+  let (premier_league_tournaments, premier_league_failures) =
+    PremierLeague::fetch_tournaments(
+      &transport,
+      &server.base_url,
+      &providers_config,
+      &BTreeMap::new(),
+    );
+  assert_eq!(
+    premier_league_tournaments,
+    PremierLeague::test_fetch_tournaments(),
+  );
+  assert_eq!(
+    premier_league_failures
+      .iter()
+      .map(|failure| failure.tournament_name.as_str())
+      .collect::<Vec<_>>(),
+    vec!["First Team - UEFA Champions League"],
+  );
+
+  // NOTE: Cached pages are used without any requests until they expire and
+  // then (as stale) when they can't be refreshed (e.g., the server is down).
+  let cache_dir = std::env::temp_dir().join(format!(
+    "boa-fetching-cache-{}-{:?}",
+    std::process::id(),
+    std::thread::current().id(),
+  ));
+  let now = 1_640_000_000;
+  let (koora_responses, premier_league_responses) = get_stored_responses();
+  let server = MockServer::new(move |path| {
+    Some(get_stored_response(
+      &koora_responses,
+      &premier_league_responses,
+      path,
+    ))
+  });
+  let base_url = server.base_url.clone();
+  let fetch_cached_tournaments = |now: i64| {
+    let transport = CachingTransport::new(
+      HttpTransport::with_https_only(Koora::ORIGIN, false),
+      Koora::NAME,
+      &cache_dir,
+      60,
+      now,
+    );
+    let (tournaments, failures) = Koora::fetch_tournaments(
+      &transport,
+      &base_url,
+      &providers_config,
+      &BTreeMap::new(),
+    );
+    assert_eq!(tournaments, Koora::test_fetch_tournaments());
+    assert_eq!(failures, vec![]);
+
+    transport
+      .take_stale_tournaments()
+      .into_iter()
+      .map(|(tournament_name, (fetched_at, _))| (tournament_name, fetched_at))
+      .collect::<Vec<_>>()
+  };
+  assert_eq!(fetch_cached_tournaments(now), vec![]);
+  assert_eq!(server.requests_count(), 7 * 24);
+  assert_eq!(fetch_cached_tournaments(now + 59), vec![]);
+  assert_eq!(server.requests_count(), 7 * 24);
+  drop(server);
+  assert_eq!(
+    fetch_cached_tournaments(now + 60),
+    providers_config
+      .koora
+      .iter()
+      .map(|competition| (Arc::clone(&competition.name), now))
+      .sorted()
+      .collect::<Vec<_>>(),
+  );
+  fs::remove_dir_all(&cache_dir).unwrap();
+
+  // NOTE: Synced tournaments are only fetched since their last completed (or
+  // first remaining) match and are the same once merged.
+  let (koora_responses, premier_league_responses) = get_stored_responses();
+  let server = MockServer::new(move |path| {
+    Some(get_stored_response(
+      &koora_responses,
+      &premier_league_responses,
+      path,
+    ))
+  });
+  let synced_providers_config = ProvidersConfig {
+    cache_dir: Some(cache_dir.clone()),
+    cache_ttl_seconds: 0,
+    ..providers_config.clone()
+  };
+  let sync_tournaments = |provider: &dyn Provider, now: i64| {
+    let (fetched_tournaments, failures, corrections, _) =
+      fetch_provider_tournaments(
+        provider,
+        &synced_providers_config,
+        HttpTransport::with_https_only(provider.origin(), false),
+        &provider.tournaments_names(&synced_providers_config),
+        now,
+      );
+    assert_eq!(failures, vec![]);
+    assert_eq!(corrections, vec![]);
+
+    build_tournaments(fetched_tournaments, &BTreeMap::new())
+  };
+  let koora_provider = StaticProvider::<Koora>::new(&server.base_url);
+  let premier_league_provider =
+    StaticProvider::<PremierLeague>::new(&server.base_url);
+  // NOTE: The stored data's snapshot.
+  let now = 1_644_879_010;
+  assert_eq!(
+    sync_tournaments(&koora_provider, now),
+    Koora::test_fetch_tournaments(),
+  );
+  assert_eq!(server.requests_count(), 7 * 24);
+  assert_eq!(
+    sync_tournaments(&koora_provider, now + 60),
+    Koora::test_fetch_tournaments(),
+  );
+  let koora_requests_count = server.requests_count() - 7 * 24;
+  assert!(koora_requests_count > 0 && koora_requests_count < 7 * 24);
+  assert_eq!(
+    sync_tournaments(&premier_league_provider, now),
+    PremierLeague::test_fetch_tournaments(),
+  );
+  let premier_league_requests_count = server.requests_count();
+  assert_eq!(
+    sync_tournaments(&premier_league_provider, now + 60),
+    PremierLeague::test_fetch_tournaments(),
+  );
+  // NOTE: Only the first (non-empty) page of the first tournament.
+  assert_eq!(
+    server.requests_count() - premier_league_requests_count,
+    1 + 4,
+  );
+  fs::remove_dir_all(&cache_dir).unwrap();
+
+  // NOTE: The async transport (through its blocking wrapper) fetches the same
+  // but doesn't retry permanent failures (e.g., `404`).
+  let (koora_responses, premier_league_responses) = get_stored_responses();
+  let server = MockServer::new(move |path| {
+    if path.contains("c=22279&") {
+      return None;
+    }
+
+    Some(get_stored_response(
+      &koora_responses,
+      &premier_league_responses,
+      path,
+    ))
+  });
+  let async_transport = Arc::new(AsyncHttpTransport::new(FetchPolicy {
+    default_host_limits: HostLimits {
+      concurrent_requests_max: 4,
+      requests_per_second: 1000.0,
+      burst_max: 1000,
+    },
+    https_only: false,
+    ..FetchPolicy::default()
+  }));
+  let base_url = server.base_url.clone();
+  let (koora_tournaments, koora_failures) = block_on(async {
+    let transport =
+      BlockingTransport::new(Handle::current(), async_transport, Koora::ORIGIN);
+
+    tokio::task::spawn_blocking(move || {
+      Koora::fetch_tournaments(
+        &transport,
+        &base_url,
+        &providers_config,
+        &BTreeMap::new(),
+      )
+    })
+    .await
+    .unwrap()
+  });
+  assert_eq!(koora_tournaments, Koora::test_fetch_tournaments());
+  assert_eq!(
+    koora_failures
+      .iter()
+      .map(|failure| failure.tournament_name.as_str())
+      .collect::<Vec<_>>(),
+    vec!["Saudi U-13 Premier League"],
+  );
+  // NOTE: The failed tournament stops at its first month.
+  assert_eq!(server.requests_count(), 6 * 24 + 1);
 }
 
 #[cfg(test)]
 mod tests {
   use super::test;
+  use super::test_mock_server;
+  use super::Koora;
+  use super::PremierLeague;
+  use super::TournamentProvider;
 
   #[test]
   fn test_tournament_fetching() {
     test()
   }
+
+  #[test]
+  fn test_tournament_fetching_mock_server() {
+    test_mock_server();
+  }
+
+  // NOTE: Regenerates the stored responses and cassettes under `data/` with
+  // `cargo test -- --ignored`.
+  #[test]
+  #[ignore = "needs the network"]
+  fn test_generate_downloaded_tournaments() {
+    Koora::test_helper_generate_downloaded_tournaments();
+    PremierLeague::test_helper_generate_downloaded_tournaments();
+    Koora::test_helper_record_cassette();
+    PremierLeague::test_helper_record_cassette();
+  }
 }
@@ -0,0 +1,529 @@
+use std::collections::hash_map::RandomState;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+use std::hash::Hasher;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+use reqwest::header::ETAG;
+use reqwest::header::IF_MODIFIED_SINCE;
+use reqwest::header::IF_NONE_MATCH;
+use reqwest::header::LAST_MODIFIED;
+use reqwest::Client;
+use reqwest::StatusCode;
+use reqwest::Url;
+use tokio::runtime::Handle;
+use tokio::sync::Semaphore;
+
+use crate::tournament::fetching::common::FetchError;
+use crate::tournament::fetching::transport::get_default_headers;
+use crate::tournament::fetching::transport::to_fetch_error;
+use crate::tournament::fetching::transport::ConditionalResponse;
+use crate::tournament::fetching::transport::Transport;
+use crate::tournament::fetching::transport::Validators;
+use crate::tournament::fetching::transport::USER_AGENT;
+
+/// How politely (and for how long) requests are made. Each host has its own
+/// limits which are shared by all of its requests (even other providers').
+#[must_use]
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct FetchPolicy {
+  pub(crate) default_host_limits: HostLimits,
+  // NOTE: By host (e.g., `www.goalzz.com`).
+  pub(crate) hosts_limits: BTreeMap<String, HostLimits>,
+  // NOTE: Retries (after the first attempt) of failures that may be temporary
+  // (e.g., a timeout or a `503`).
+  pub(crate) retries_max: u32,
+  pub(crate) backoff_initial: Duration,
+  pub(crate) backoff_max: Duration,
+  pub(crate) request_timeout: Duration,
+  // NOTE: Of the whole fetch after which requests fail without being made.
+  pub(crate) overall_timeout: Duration,
+  // NOTE: Plain HTTP is only meant for local servers (e.g., in tests).
+  pub(crate) https_only: bool,
+}
+
+#[must_use]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct HostLimits {
+  pub(crate) concurrent_requests_max: usize,
+  // NOTE: A token-bucket which allows bursts (of up to `burst_max` requests)
+  // and is then refilled at a steady rate.
+  pub(crate) requests_per_second: f64,
+  pub(crate) burst_max: u32,
+}
+
+impl Default for FetchPolicy {
+  fn default() -> Self {
+    Self {
+      default_host_limits: HostLimits {
+        concurrent_requests_max: 4,
+        requests_per_second: 8.0,
+        burst_max: 8,
+      },
+      // NOTE: Koora's pages are the most (a page per month for each
+      // tournament) and are generated on every request.
+      hosts_limits: BTreeMap::from([(
+        String::from("www.goalzz.com"),
+        HostLimits {
+          concurrent_requests_max: 2,
+          requests_per_second: 2.0,
+          burst_max: 4,
+        },
+      )]),
+      retries_max: 3,
+      backoff_initial: Duration::from_millis(500),
+      backoff_max: Duration::from_secs(8),
+      request_timeout: Duration::from_secs(30),
+      overall_timeout: Duration::from_secs(5 * 60),
+      https_only: true,
+    }
+  }
+}
+
+#[must_use]
+struct HostLimiter {
+  semaphore: Semaphore,
+  bucket: Mutex<TokenBucket>,
+}
+
+#[must_use]
+#[derive(Debug)]
+struct TokenBucket {
+  capacity: f64,
+  tokens: f64,
+  refill_per_second: f64,
+  refilled_at: Instant,
+}
+
+impl TokenBucket {
+  fn new(host_limits: &HostLimits, now: Instant) -> Self {
+    Self {
+      capacity: f64::from(host_limits.burst_max),
+      tokens: f64::from(host_limits.burst_max),
+      refill_per_second: host_limits.requests_per_second,
+      refilled_at: now,
+    }
+  }
+
+  // NOTE: Takes a token if there's any and otherwise returns how long until
+  // there's one.
+  fn try_take(&mut self, now: Instant) -> Result<(), Duration> {
+    let elapsed = now.saturating_duration_since(self.refilled_at);
+    self.tokens = elapsed
+      .as_secs_f64()
+      .mul_add(self.refill_per_second, self.tokens)
+      .min(self.capacity);
+    self.refilled_at = self.refilled_at.max(now);
+
+    if self.tokens >= 1.0 {
+      self.tokens -= 1.0;
+      Ok(())
+    } else {
+      Err(Duration::from_secs_f64(
+        (1.0 - self.tokens) / self.refill_per_second,
+      ))
+    }
+  }
+}
+
+/// Makes requests (asynchronously) within a policy's limits, timeouts and
+/// retries. A single one is meant to be shared by all of a fetch's requests
+/// and a new one is created per fetch as its overall timeout starts once it's
+/// created.
+#[must_use]
+pub(crate) struct AsyncHttpTransport {
+  // NOTE: Building the client can fail (e.g., TLS can't be initialized) in
+  // which case every request fails with why.
+  client: Result<Client, String>,
+  policy: FetchPolicy,
+  // NOTE: Fixed when it's created (i.e., isn't reset per request or fetch).
+  deadline: Instant,
+  // NOTE: By host and created on its first request.
+  hosts_limiters: Mutex<HashMap<String, Arc<HostLimiter>>>,
+}
+
+impl AsyncHttpTransport {
+  /// # Panics
+  pub(crate) fn new(policy: FetchPolicy) -> Self {
+    for host_limits in std::iter::once(&policy.default_host_limits)
+      .chain(policy.hosts_limits.values())
+    {
+      assert!(
+        host_limits.concurrent_requests_max >= 1
+          && host_limits.requests_per_second > 0.0
+          && host_limits.burst_max >= 1,
+        "Invalid host-limits ({:?}).",
+        host_limits,
+      );
+    }
+
+    // SEE: https://docs.rs/reqwest/0.11.7/reqwest/struct.ClientBuilder.html
+    let client = Client::builder()
+      .user_agent(USER_AGENT)
+      .referer(true)
+      .https_only(policy.https_only)
+      .gzip(true)
+      .deflate(true)
+      .brotli(true)
+      .default_headers(get_default_headers())
+      .build()
+      .map_err(|error| error.to_string());
+
+    Self {
+      client,
+      deadline: Instant::now() + policy.overall_timeout,
+      policy,
+      hosts_limiters: Mutex::new(HashMap::new()),
+    }
+  }
+
+  /// Same as `Transport::get_conditional()` but with the provider's origin
+  /// (which the client is shared between).
+  pub(crate) async fn get_conditional(
+    &self,
+    url: &str,
+    origin: &'static str,
+    validators: &Validators,
+  ) -> Result<ConditionalResponse, FetchError> {
+    let host_limiter = self.get_host_limiter(url)?;
+
+    let mut retries_count = 0;
+    loop {
+      let attempt = {
+        // NOTE: Only held while requesting (and not while backing off).
+        let _permit = host_limiter
+          .semaphore
+          .acquire()
+          .await
+          .expect("Semaphores are never closed");
+        self.wait_for_token(&host_limiter, url).await?;
+
+        self.request(url, origin, validators).await
+      };
+
+      match attempt {
+        Err((_, true)) if retries_count < self.policy.retries_max => {
+          self
+            .sleep(
+              url,
+              get_backoff(&self.policy, retries_count, get_random_fraction()),
+            )
+            .await?;
+          retries_count += 1;
+        },
+        _ => return attempt.map_err(|(error, _)| error),
+      }
+    }
+  }
+
+  // NOTE: Failures are returned with whether they may be temporary (and so
+  // worth retrying).
+  async fn request(
+    &self,
+    url: &str,
+    origin: &'static str,
+    validators: &Validators,
+  ) -> Result<ConditionalResponse, (FetchError, bool)> {
+    let to_fetch_error = |error: reqwest::Error| {
+      let is_temporary = error.is_timeout()
+        || error.is_connect()
+        || matches!(error.status(), Some(status) if is_status_temporary(status));
+
+      (to_fetch_error(url, &error), is_temporary)
+    };
+
+    let timeout = self
+      .get_remaining_time(url)
+      .map_err(|error| (error, false))?
+      .min(self.policy.request_timeout);
+    let client = self.client.as_ref().map_err(|message| {
+      (
+        FetchError::Network {
+          url: String::from(url),
+          message: message.clone(),
+        },
+        false,
+      )
+    })?;
+    let mut request = client.get(url).header("Origin", origin).timeout(timeout);
+    if let Some(etag) = &validators.etag {
+      request = request.header(IF_NONE_MATCH, etag);
+    }
+    if let Some(last_modified) = &validators.last_modified {
+      request = request.header(IF_MODIFIED_SINCE, last_modified);
+    }
+
+    let response = request.send().await.map_err(to_fetch_error)?;
+    if response.status() == StatusCode::NOT_MODIFIED {
+      return Ok(ConditionalResponse::NotModified);
+    }
+
+    let response = response.error_for_status().map_err(to_fetch_error)?;
+    let get_header = |name| {
+      response
+        .headers()
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(String::from)
+    };
+    let validators = Validators {
+      etag: get_header(ETAG),
+      last_modified: get_header(LAST_MODIFIED),
+    };
+
+    Ok(ConditionalResponse::Modified {
+      body: response.text().await.map_err(to_fetch_error)?,
+      validators,
+    })
+  }
+
+  fn get_host_limiter(
+    &self,
+    url: &str,
+  ) -> Result<Arc<HostLimiter>, FetchError> {
+    let host = Url::parse(url)
+      .ok()
+      .and_then(|url| url.host_str().map(String::from))
+      .ok_or_else(|| FetchError::Network {
+        url: String::from(url),
+        message: String::from("Invalid URL"),
+      })?;
+    let host_limits = *self
+      .policy
+      .hosts_limits
+      .get(&host)
+      .unwrap_or(&self.policy.default_host_limits);
+
+    Ok(Arc::clone(
+      self
+        .hosts_limiters
+        .lock()
+        .unwrap()
+        .entry(host)
+        .or_insert_with(|| {
+          Arc::new(HostLimiter {
+            semaphore: Semaphore::new(host_limits.concurrent_requests_max),
+            bucket: Mutex::new(TokenBucket::new(&host_limits, Instant::now())),
+          })
+        }),
+    ))
+  }
+
+  async fn wait_for_token(
+    &self,
+    host_limiter: &HostLimiter,
+    url: &str,
+  ) -> Result<(), FetchError> {
+    loop {
+      let token = host_limiter.bucket.lock().unwrap().try_take(Instant::now());
+      match token {
+        Ok(()) => return Ok(()),
+        Err(wait) => self.sleep(url, wait).await?,
+      }
+    }
+  }
+
+  // NOTE: Fails right away instead of sleeping past the deadline.
+  async fn sleep(
+    &self,
+    url: &str,
+    duration: Duration,
+  ) -> Result<(), FetchError> {
+    if duration >= self.get_remaining_time(url)? {
+      return Err(get_timeout_error(url));
+    }
+    tokio::time::sleep(duration).await;
+
+    Ok(())
+  }
+
+  fn get_remaining_time(&self, url: &str) -> Result<Duration, FetchError> {
+    self
+      .deadline
+      .checked_duration_since(Instant::now())
+      .filter(|remaining_time| !remaining_time.is_zero())
+      .ok_or_else(|| get_timeout_error(url))
+  }
+}
+
+/// Blocks on an `AsyncHttpTransport` so that providers (which are blocking)
+/// can use it. Must only be used outside of the runtime's workers (e.g., in
+/// `spawn_blocking()`).
+#[must_use]
+pub(crate) struct BlockingTransport {
+  runtime: Handle,
+  transport: Arc<AsyncHttpTransport>,
+  origin: &'static str,
+}
+
+impl BlockingTransport {
+  pub(crate) const fn new(
+    runtime: Handle,
+    transport: Arc<AsyncHttpTransport>,
+    origin: &'static str,
+  ) -> Self {
+    Self {
+      runtime,
+      transport,
+      origin,
+    }
+  }
+}
+
+impl Transport for BlockingTransport {
+  fn get(&self, url: &str) -> Result<String, FetchError> {
+    match self.get_conditional(url, &Validators::default())? {
+      ConditionalResponse::Modified { body, .. } => Ok(body),
+      // NOTE: Only possible with validators.
+      ConditionalResponse::NotModified => Err(FetchError::Network {
+        url: String::from(url),
+        message: String::from("Unexpected not-modified response"),
+      }),
+    }
+  }
+
+  fn get_conditional(
+    &self,
+    url: &str,
+    validators: &Validators,
+  ) -> Result<ConditionalResponse, FetchError> {
+    self.runtime.block_on(self.transport.get_conditional(
+      url,
+      self.origin,
+      validators,
+    ))
+  }
+}
+
+#[must_use]
+fn get_timeout_error(url: &str) -> FetchError {
+  FetchError::Network {
+    url: String::from(url),
+    message: String::from("Overall timeout exceeded"),
+  }
+}
+
+// NOTE: Rate-limiting (`429`) and servers' errors (e.g., `503`) are usually
+// temporary unlike others (e.g., `404`).
+#[must_use]
+fn is_status_temporary(status: StatusCode) -> bool {
+  status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+// NOTE: Exponential (and capped) with half of it random (i.e., "equal jitter")
+// so that concurrent requests' retries are spread out.
+// SEE: https://aws.amazon.com/blogs/architecture/exponential-backoff-and-jitter/
+#[must_use]
+fn get_backoff(
+  policy: &FetchPolicy,
+  retries_count: u32,
+  random_fraction: f64,
+) -> Duration {
+  let backoff = policy
+    .backoff_initial
+    .saturating_mul(2u32.saturating_pow(retries_count))
+    .min(policy.backoff_max);
+
+  backoff / 2 + (backoff / 2).mul_f64(random_fraction)
+}
+
+// NOTE: In `[0, 1)` and jitter doesn't need a proper random number generator
+// as std's hashers are randomly seeded.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+fn get_random_fraction() -> f64 {
+  (RandomState::new().build_hasher().finish() >> 11) as f64
+    / (1u64 << 53) as f64
+}
+
+#[allow(clippy::too_many_lines)]
+pub(super) fn test() {
+  #[cfg(test)]
+  use pretty_assertions::assert_eq;
+
+  let host_limits = HostLimits {
+    concurrent_requests_max: 1,
+    requests_per_second: 10.0,
+    burst_max: 2,
+  };
+  let now = Instant::now();
+  let mut bucket = TokenBucket::new(&host_limits, now);
+  assert_eq!(bucket.try_take(now), Ok(()));
+  assert_eq!(bucket.try_take(now), Ok(()));
+  assert_eq!(bucket.try_take(now), Err(Duration::from_millis(100)));
+  assert!(bucket.try_take(now + Duration::from_millis(50)).is_err());
+  assert_eq!(bucket.try_take(now + Duration::from_millis(100)), Ok(()));
+  // NOTE: Bursts are capped however long it's been.
+  let later = now + Duration::from_secs(10);
+  assert_eq!(bucket.try_take(later), Ok(()));
+  assert_eq!(bucket.try_take(later), Ok(()));
+  assert!(bucket.try_take(later).is_err());
+
+  let policy = FetchPolicy::default();
+  assert_eq!(get_backoff(&policy, 0, 0.0), Duration::from_millis(250));
+  assert_eq!(get_backoff(&policy, 0, 0.5), Duration::from_millis(375));
+  assert_eq!(get_backoff(&policy, 2, 0.0), Duration::from_secs(1));
+  assert_eq!(get_backoff(&policy, 4, 0.0), Duration::from_secs(4));
+  assert_eq!(get_backoff(&policy, 40, 0.0), Duration::from_secs(4));
+  assert!(get_backoff(&policy, 40, 0.99) < policy.backoff_max);
+  assert!((0..100)
+    .map(|_| get_random_fraction())
+    .all(|random_fraction| (0.0..1.0).contains(&random_fraction)));
+
+  assert!(is_status_temporary(StatusCode::TOO_MANY_REQUESTS));
+  assert!(is_status_temporary(StatusCode::SERVICE_UNAVAILABLE));
+  assert!(!is_status_temporary(StatusCode::NOT_FOUND));
+
+  // NOTE: Hosts' limiters are shared by all of their requests.
+  let transport = AsyncHttpTransport::new(FetchPolicy::default());
+  let goalzz_limiter =
+    transport.get_host_limiter("https://www.goalzz.com/main.aspx?c=1");
+  assert!(Arc::ptr_eq(
+    goalzz_limiter.as_ref().unwrap(),
+    &transport
+      .get_host_limiter("https://www.goalzz.com/main.aspx?c=2")
+      .unwrap(),
+  ));
+  assert_eq!(goalzz_limiter.unwrap().semaphore.available_permits(), 2);
+  assert_eq!(
+    transport
+      .get_host_limiter("https://footballapi.pulselive.com/football")
+      .unwrap()
+      .semaphore
+      .available_permits(),
+    4,
+  );
+  assert!(transport.get_host_limiter("not a url").is_err());
+
+  // NOTE: Nothing is requested after the overall timeout.
+  let runtime = tokio::runtime::Builder::new_current_thread()
+    .enable_all()
+    .build()
+    .unwrap();
+  let transport = AsyncHttpTransport::new(FetchPolicy {
+    overall_timeout: Duration::ZERO,
+    ..FetchPolicy::default()
+  });
+  assert_eq!(
+    runtime.block_on(transport.get_conditional(
+      "https://www.goalzz.com/",
+      "https://www.goalzz.com",
+      &Validators::default(),
+    )),
+    Err(get_timeout_error("https://www.goalzz.com/")),
+  );
+}
+
+#[cfg(test)]
+mod tests {
+  use super::test;
+
+  #[test]
+  fn test_tournament_fetching_async_transport() {
+    test();
+  }
+}
@@ -0,0 +1,397 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::tournament::fetching::common::FetchError;
+use crate::tournament::fetching::transport::ConditionalResponse;
+use crate::tournament::fetching::transport::Transport;
+use crate::tournament::fetching::transport::Validators;
+
+// NOTE: Fetched within the same run of an app (e.g., refreshing) and only
+// revalidated afterwards.
+pub(super) const DEFAULT_TTL_SECONDS: i64 = 15 * 60;
+const CACHE_DIR_NAME: &str = "pages";
+
+#[must_use]
+#[derive(Debug, Deserialize, Serialize)]
+struct CachedPage {
+  // NOTE: Checked when read as different pages may share a file.
+  url: String,
+  body: String,
+  validators: Validators,
+  // NOTE: A unix-timestamp of when it was last downloaded or revalidated.
+  fetched_at: i64,
+}
+
+/// Caches tournaments' pages (by provider, tournament and page) fetched through
+/// another transport for a TTL. Expired pages are revalidated with conditional
+/// requests and are still served when they can't be fetched (e.g., offline)
+/// in which case their tournaments are stale.
+#[must_use]
+pub(crate) struct CachingTransport<T: Transport> {
+  transport: T,
+  provider_dir: PathBuf,
+  ttl_seconds: i64,
+  now: i64,
+  // NOTE: By tournament-name with its oldest stale page's fetch time and why
+  // it couldn't be fetched.
+  stale_tournaments: Mutex<BTreeMap<Arc<String>, (i64, FetchError)>>,
+}
+
+impl<T: Transport> CachingTransport<T> {
+  pub(crate) fn new(
+    transport: T,
+    provider_name: &str,
+    cache_dir: &Path,
+    ttl_seconds: i64,
+    now: i64,
+  ) -> Self {
+    Self {
+      transport,
+      provider_dir: cache_dir
+        .join(CACHE_DIR_NAME)
+        .join(to_file_name(provider_name)),
+      ttl_seconds,
+      now,
+      stale_tournaments: Mutex::new(BTreeMap::new()),
+    }
+  }
+
+  /// # Panics
+  #[must_use]
+  pub(crate) fn take_stale_tournaments(
+    &self,
+  ) -> BTreeMap<Arc<String>, (i64, FetchError)> {
+    std::mem::take(&mut self.stale_tournaments.lock().unwrap())
+  }
+}
+
+impl<T: Transport> Transport for CachingTransport<T> {
+  // NOTE: Only pages are cached (e.g., not discoveries which have their own
+  // cache).
+  fn get(&self, url: &str) -> Result<String, FetchError> {
+    self.transport.get(url)
+  }
+
+  fn get_page(
+    &self,
+    url: &str,
+    tournament_name: &Arc<String>,
+    page: &str,
+  ) -> Result<String, FetchError> {
+    let cache_path = self
+      .provider_dir
+      .join(to_file_name(tournament_name))
+      .join(format!("{}.json", to_file_name(page)));
+    // NOTE: A missing or corrupt cache only costs an extra request.
+    let cached_page = fs::read_to_string(&cache_path)
+      .ok()
+      .and_then(|cached_page| {
+        serde_json::from_str::<CachedPage>(&cached_page).ok()
+      })
+      .filter(|cached_page| cached_page.url == url);
+
+    let validators = match &cached_page {
+      Some(cached_page)
+        if self.now - cached_page.fetched_at < self.ttl_seconds =>
+      {
+        return Ok(cached_page.body.clone());
+      },
+      Some(cached_page) => cached_page.validators.clone(),
+      None => Validators::default(),
+    };
+
+    let (body, validators) = match (
+      self.transport.get_conditional(url, &validators),
+      cached_page,
+    ) {
+      (Ok(ConditionalResponse::Modified { body, validators }), _) => {
+        (body, validators)
+      },
+      (Ok(ConditionalResponse::NotModified), Some(cached_page)) => {
+        (cached_page.body, cached_page.validators)
+      },
+      // NOTE: Validators are only sent for cached pages.
+      (Ok(ConditionalResponse::NotModified), None) => {
+        return Err(FetchError::Network {
+          url: String::from(url),
+          message: String::from("Unexpected not-modified response"),
+        });
+      },
+      (Err(error @ FetchError::Network { .. }), Some(cached_page)) => {
+        let mut stale_tournaments = self.stale_tournaments.lock().unwrap();
+        let stale_tournament = stale_tournaments
+          .entry(Arc::clone(tournament_name))
+          .or_insert_with(|| (cached_page.fetched_at, error.clone()));
+        if cached_page.fetched_at < stale_tournament.0 {
+          *stale_tournament = (cached_page.fetched_at, error);
+        }
+        drop(stale_tournaments);
+
+        return Ok(cached_page.body);
+      },
+      (Err(error), _) => return Err(error),
+    };
+
+    // NOTE: Failing to cache only costs extra requests next time.
+    let _ignored_result = cache_path
+      .parent()
+      .map_or(Ok(()), fs::create_dir_all)
+      .and_then(|()| {
+        fs::write(
+          &cache_path,
+          serde_json::to_string(&CachedPage {
+            url: String::from(url),
+            body: body.clone(),
+            validators,
+            fetched_at: self.now,
+          })?,
+        )
+      });
+
+    Ok(body)
+  }
+}
+
+// NOTE: Names are kept readable (e.g., `Saudi U-13 Premier League` is
+// `saudi-u-13-premier-league`).
+#[must_use]
+pub(super) fn to_file_name(name: &str) -> String {
+  name
+    .chars()
+    .map(|c| {
+      if c.is_ascii_alphanumeric() {
+        c.to_ascii_lowercase()
+      } else {
+        '-'
+      }
+    })
+    .collect()
+}
+
+#[allow(clippy::too_many_lines)]
+pub(super) fn test() {
+  #[cfg(test)]
+  use pretty_assertions::assert_eq;
+
+  use std::cell::RefCell;
+
+  struct StubTransport {
+    responses: RefCell<Vec<Result<ConditionalResponse, FetchError>>>,
+    requests: RefCell<Vec<(String, Validators)>>,
+  }
+  // NOTE: Borrowed so that its requests are checked after each caching one.
+  impl Transport for &StubTransport {
+    fn get(&self, url: &str) -> Result<String, FetchError> {
+      Err(FetchError::Network {
+        url: String::from(url),
+        message: String::from("Unconditional request"),
+      })
+    }
+
+    fn get_conditional(
+      &self,
+      url: &str,
+      validators: &Validators,
+    ) -> Result<ConditionalResponse, FetchError> {
+      self
+        .requests
+        .borrow_mut()
+        .push((String::from(url), validators.clone()));
+
+      self.responses.borrow_mut().remove(0)
+    }
+  }
+
+  // NOTE: Tests run in parallel so caches must not be shared.
+  let cache_dir = std::env::temp_dir().join(format!(
+    "boa-cache-{}-{:?}",
+    std::process::id(),
+    std::thread::current().id(),
+  ));
+  let url = "http://localhost/main.aspx?c=22551&smonth=202108";
+  let tournament_name = Arc::new(String::from("Saudi Professional League"));
+  let ttl_seconds = 60;
+  let now = 1_640_000_000;
+  let stub_transport = StubTransport {
+    responses: RefCell::new(vec![]),
+    requests: RefCell::new(vec![]),
+  };
+  let offline_error = FetchError::Network {
+    url: String::from(url),
+    message: String::from("Offline"),
+  };
+  let get_modified = |body: &str, etag: Option<&str>| {
+    Ok(ConditionalResponse::Modified {
+      body: String::from(body),
+      validators: Validators {
+        etag: etag.map(String::from),
+        last_modified: None,
+      },
+    })
+  };
+
+  let get_page =
+    |now: i64,
+     url: &str,
+     page: &str,
+     responses: Vec<Result<ConditionalResponse, FetchError>>| {
+      *stub_transport.responses.borrow_mut() = responses;
+      let transport = CachingTransport::new(
+        &stub_transport,
+        "Koora",
+        &cache_dir,
+        ttl_seconds,
+        now,
+      );
+
+      (
+        transport.get_page(url, &tournament_name, page),
+        transport.take_stale_tournaments(),
+      )
+    };
+  let get_requests_count = || stub_transport.requests.borrow().len();
+
+  assert_eq!(
+    get_page(now, url, "202108", vec![get_modified("1", Some("\"a\""))]),
+    (Ok(String::from("1")), BTreeMap::new()),
+  );
+  assert_eq!(
+    *stub_transport.requests.borrow(),
+    vec![(String::from(url), Validators::default())],
+  );
+  assert!(cache_dir
+    .join("pages/koora/saudi-professional-league/202108.json")
+    .is_file());
+  assert_eq!(
+    get_page(now + ttl_seconds - 1, url, "202108", vec![]),
+    (Ok(String::from("1")), BTreeMap::new()),
+  );
+  assert_eq!(get_requests_count(), 1);
+
+  // NOTE: Expired pages are revalidated (and fresh again if unchanged).
+  assert_eq!(
+    get_page(
+      now + ttl_seconds,
+      url,
+      "202108",
+      vec![Ok(ConditionalResponse::NotModified)],
+    ),
+    (Ok(String::from("1")), BTreeMap::new()),
+  );
+  assert_eq!(
+    stub_transport.requests.borrow()[1].1,
+    Validators {
+      etag: Some(String::from("\"a\"")),
+      last_modified: None,
+    },
+  );
+  assert_eq!(
+    get_page(now + 2 * ttl_seconds - 1, url, "202108", vec![]),
+    (Ok(String::from("1")), BTreeMap::new()),
+  );
+  assert_eq!(get_requests_count(), 2);
+  assert_eq!(
+    get_page(
+      now + 2 * ttl_seconds,
+      url,
+      "202108",
+      vec![get_modified("2", None)],
+    ),
+    (Ok(String::from("2")), BTreeMap::new()),
+  );
+
+  // NOTE: Offline, expired pages are served (as stale) but missing ones fail.
+  assert_eq!(
+    get_page(
+      now + 5 * ttl_seconds,
+      url,
+      "202108",
+      vec![Err(offline_error.clone())],
+    ),
+    (
+      Ok(String::from("2")),
+      BTreeMap::from([(
+        Arc::clone(&tournament_name),
+        (now + 2 * ttl_seconds, offline_error.clone()),
+      )]),
+    ),
+  );
+  assert_eq!(
+    get_page(
+      now + 5 * ttl_seconds,
+      url,
+      "202109",
+      vec![Err(offline_error.clone())],
+    ),
+    (Err(offline_error.clone()), BTreeMap::new()),
+  );
+  assert_eq!(
+    get_page(
+      now + 5 * ttl_seconds,
+      url,
+      "202109",
+      vec![Ok(ConditionalResponse::NotModified)],
+    )
+    .0,
+    Err(FetchError::Network {
+      url: String::from(url),
+      message: String::from("Unexpected not-modified response"),
+    }),
+  );
+  // NOTE: Only network errors fall back to the cache.
+  let parse_error = FetchError::Parse {
+    message: String::from("Invalid"),
+  };
+  assert_eq!(
+    get_page(
+      now + 5 * ttl_seconds,
+      url,
+      "202108",
+      vec![Err(parse_error.clone())],
+    ),
+    (Err(parse_error), BTreeMap::new()),
+  );
+  // NOTE: A page whose URL changed (e.g., another competition-id) is missing.
+  let other_url = "http://localhost/main.aspx?c=22279&smonth=202108";
+  assert_eq!(
+    get_page(
+      now + 5 * ttl_seconds,
+      other_url,
+      "202108",
+      vec![Err(offline_error.clone())],
+    ),
+    (Err(offline_error), BTreeMap::new()),
+  );
+
+  // NOTE: Other requests aren't cached.
+  assert!(CachingTransport::new(
+    &stub_transport,
+    "Koora",
+    &cache_dir,
+    ttl_seconds,
+    now,
+  )
+  .get(url)
+  .is_err());
+
+  assert_eq!(to_file_name("Premier League"), "premier-league");
+  assert_eq!(to_file_name("U-13 (2021/22)"), "u-13--2021-22-");
+  fs::remove_dir_all(&cache_dir).unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+  use super::test;
+
+  #[test]
+  fn test_tournament_fetching_cache() {
+    test();
+  }
+}
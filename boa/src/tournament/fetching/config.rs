@@ -0,0 +1,688 @@
+use std::collections::BTreeMap;
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use chrono::Datelike;
+use chrono::NaiveDate;
+
+use crate::common::CompetitionFormat;
+use crate::tournament::fetching::cache::DEFAULT_TTL_SECONDS;
+use crate::tournament::fetching::common::TournamentProvider;
+use crate::tournament::fetching::interchange::FormatJson;
+use crate::tournament::fetching::registry::ProvidersRegistry;
+use crate::tournament::fetching::teams::parse_teams_registry;
+use crate::tournament::fetching::teams::RegisteredTeamJson;
+use crate::tournament::fetching::teams::TeamsRegistry;
+use crate::tournament::fetching::FootballData;
+use crate::tournament::fetching::JsonFile;
+use crate::tournament::fetching::Koora;
+use crate::tournament::fetching::PremierLeague;
+
+/// Which competitions (and seasons) each provider fetches. The default one
+/// (`providers.json`) is shipped with the app and can be replaced by a file
+/// with the same format (e.g., for a new season).
+#[must_use]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProvidersConfig {
+  pub(crate) premier_league: Vec<PremierLeagueCompetition>,
+  pub(crate) koora: Vec<KooraCompetition>,
+  pub(crate) football_data: Vec<FileCompetition>,
+  pub(crate) json: Vec<FileCompetition>,
+  pub(crate) teams: TeamsRegistry,
+  // NOTE: By tournament-name and only for tournaments fetched by more than one
+  // provider (which are reconciled into a single tournament).
+  pub(crate) primary_providers: BTreeMap<Arc<String>, String>,
+  // NOTE: By tournament-name and only for tournaments that aren't a double
+  // round-robin (e.g., split leagues) in the interchange format's layout.
+  // Files of the JSON provider include their own formats instead.
+  pub(crate) formats: BTreeMap<Arc<String>, CompetitionFormat>,
+  // NOTE: Not part of the file as it depends on the platform (e.g., an app's
+  // cache directory on mobile). Nothing is cached without it.
+  pub(crate) cache_dir: Option<PathBuf>,
+  // NOTE: How long tournaments' pages are cached before being revalidated.
+  pub(crate) cache_ttl_seconds: i64,
+  // NOTE: Registered providers of which those disabled (e.g., by the file's
+  // `disabled_providers`) aren't fetched.
+  pub(crate) providers: ProvidersRegistry,
+  // NOTE: Not part of the file either. Tournaments (by name) that were synced
+  // before are only fetched from a unix-timestamp on by providers that can
+  // (see `ResultsStore`).
+  pub(crate) synced_since: BTreeMap<Arc<String>, i64>,
+}
+
+#[must_use]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct PremierLeagueCompetition {
+  pub(crate) name: Arc<String>,
+  pub(crate) competition_id: usize,
+  // NOTE: Both are discovered (the current season and its teams) unless
+  // pinned (e.g., to replay a historic season).
+  pub(crate) season_id: Option<usize>,
+  // NOTE: Only matches of these teams are returned by the API.
+  pub(crate) teams_ids: Option<Vec<usize>>,
+}
+
+#[must_use]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct KooraCompetition {
+  pub(crate) name: Arc<String>,
+  pub(crate) competition_id: usize,
+  pub(crate) season_start: NaiveDate,
+  pub(crate) season_end: NaiveDate,
+}
+
+// NOTE: A tournament's file (e.g., a season's `E0.csv`) which is relative to
+// its provider's base directory (the current one by default).
+#[must_use]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct FileCompetition {
+  pub(crate) name: Arc<String>,
+  pub(crate) path: PathBuf,
+}
+
+impl Default for ProvidersConfig {
+  fn default() -> Self {
+    parse_providers_config(include_str!("providers.json"))
+      .expect("Invalid default providers config.")
+  }
+}
+
+impl ProvidersConfig {
+  // NOTE: The same config but only with the given tournaments (of any
+  // provider) so that each is fetched independently (e.g., concurrently).
+  #[must_use]
+  pub(crate) fn with_tournaments(
+    &self,
+    tournaments_names: &[Arc<String>],
+  ) -> Self {
+    let is_included = |name: &Arc<String>| tournaments_names.contains(name);
+
+    Self {
+      premier_league: self
+        .premier_league
+        .iter()
+        .filter(|competition| is_included(&competition.name))
+        .cloned()
+        .collect(),
+      koora: self
+        .koora
+        .iter()
+        .filter(|competition| is_included(&competition.name))
+        .cloned()
+        .collect(),
+      football_data: self
+        .football_data
+        .iter()
+        .filter(|competition| is_included(&competition.name))
+        .cloned()
+        .collect(),
+      json: self
+        .json
+        .iter()
+        .filter(|competition| is_included(&competition.name))
+        .cloned()
+        .collect(),
+      ..self.clone()
+    }
+  }
+
+  #[must_use]
+  pub(crate) fn get_format(
+    &self,
+    tournament_name: &Arc<String>,
+  ) -> CompetitionFormat {
+    self
+      .formats
+      .get(tournament_name)
+      .cloned()
+      .unwrap_or_default()
+  }
+
+  // NOTE: The season of the stored test data (2021-2022) which is pinned so
+  // that it's replayed without any discovery.
+  #[must_use]
+  pub(crate) fn test_helper_pinned() -> Self {
+    read_providers_config(Path::new("data/providers-2021-2022.json"))
+      .expect("reading test config failed")
+  }
+}
+
+impl KooraCompetition {
+  // NOTE: Matches are requested by month (e.g., `202108`) so every month of
+  // the season (inclusive) is requested.
+  #[must_use]
+  pub(crate) fn get_months(&self) -> Vec<String> {
+    let get_months_count =
+      |date: &NaiveDate| i64::from(date.year()) * 12 + i64::from(date.month0());
+
+    (get_months_count(&self.season_start)..=get_months_count(&self.season_end))
+      .map(|months_count| {
+        format!(
+          "{:04}{:02}",
+          months_count.div_euclid(12),
+          months_count.rem_euclid(12) + 1,
+        )
+      })
+      .collect()
+  }
+}
+
+/// Reads a providers config from a JSON file like `providers.json`. `None` is
+/// returned when the file can't be read, parsed or is invalid (e.g., a season
+/// that ends before it starts).
+#[must_use]
+pub(crate) fn read_providers_config(path: &Path) -> Option<ProvidersConfig> {
+  parse_providers_config(&fs::read_to_string(path).ok()?)
+}
+
+#[must_use]
+#[allow(clippy::too_many_lines)]
+fn parse_providers_config(
+  providers_config_json: &str,
+) -> Option<ProvidersConfig> {
+  use serde::Deserialize;
+
+  #[must_use]
+  #[derive(Deserialize)]
+  struct ProvidersConfigJson {
+    premier_league: Vec<PremierLeagueCompetitionJson>,
+    koora: Vec<KooraCompetitionJson>,
+    // NOTE: Optional as historic seasons are only analysed on demand.
+    #[serde(default)]
+    football_data: Vec<FileCompetitionJson>,
+    #[serde(default)]
+    json: Vec<FileCompetitionJson>,
+    // NOTE: Optional as unregistered teams are still fetched (by name).
+    #[serde(default)]
+    teams: Vec<RegisteredTeamJson>,
+    #[serde(default)]
+    primary_providers: BTreeMap<String, String>,
+    #[serde(default)]
+    formats: BTreeMap<String, FormatJson>,
+    cache_ttl_seconds: Option<u32>,
+    #[serde(default)]
+    disabled_providers: Vec<String>,
+  }
+
+  #[must_use]
+  #[derive(Deserialize)]
+  struct PremierLeagueCompetitionJson {
+    name: String,
+    competition_id: usize,
+    season_id: Option<usize>,
+    teams_ids: Option<Vec<usize>>,
+  }
+
+  #[must_use]
+  #[derive(Deserialize)]
+  struct KooraCompetitionJson {
+    name: String,
+    competition_id: usize,
+    season_start: String,
+    season_end: String,
+  }
+
+  #[must_use]
+  #[derive(Deserialize)]
+  struct FileCompetitionJson {
+    name: String,
+    path: PathBuf,
+  }
+
+  let parse_date =
+    |date: &str| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok();
+
+  let providers_config: ProvidersConfigJson =
+    serde_json::from_str(providers_config_json).ok()?;
+
+  let premier_league: Vec<PremierLeagueCompetition> = providers_config
+    .premier_league
+    .into_iter()
+    .map(|competition| {
+      // NOTE: Teams are only pinned with their season.
+      let is_valid = match (&competition.season_id, &competition.teams_ids) {
+        (_, None) => true,
+        (None, Some(_)) => false,
+        (Some(_), Some(teams_ids)) => !teams_ids.is_empty(),
+      };
+
+      is_valid.then(|| PremierLeagueCompetition {
+        name: Arc::new(competition.name),
+        competition_id: competition.competition_id,
+        season_id: competition.season_id,
+        teams_ids: competition.teams_ids,
+      })
+    })
+    .collect::<Option<_>>()?;
+  let koora: Vec<KooraCompetition> = providers_config
+    .koora
+    .into_iter()
+    .map(|competition| {
+      let season_start = parse_date(&competition.season_start)?;
+      let season_end = parse_date(&competition.season_end)?;
+
+      (season_start <= season_end).then(|| KooraCompetition {
+        name: Arc::new(competition.name),
+        competition_id: competition.competition_id,
+        season_start,
+        season_end,
+      })
+    })
+    .collect::<Option<_>>()?;
+
+  let parse_file_competitions = |competitions: Vec<FileCompetitionJson>| {
+    competitions
+      .into_iter()
+      .map(|competition| {
+        (!competition.path.as_os_str().is_empty()).then(|| FileCompetition {
+          name: Arc::new(competition.name),
+          path: competition.path,
+        })
+      })
+      .collect::<Option<Vec<FileCompetition>>>()
+  };
+  let football_data = parse_file_competitions(providers_config.football_data)?;
+  let json = parse_file_competitions(providers_config.json)?;
+
+  // NOTE: Tournaments are identified by their names (per provider).
+  let all_providers_names: Vec<(&str, HashSet<&Arc<String>>)> = vec![
+    (
+      PremierLeague::NAME,
+      premier_league
+        .iter()
+        .map(|competition| &competition.name)
+        .collect(),
+    ),
+    (
+      Koora::NAME,
+      koora.iter().map(|competition| &competition.name).collect(),
+    ),
+    (
+      FootballData::NAME,
+      football_data
+        .iter()
+        .map(|competition| &competition.name)
+        .collect(),
+    ),
+    (
+      JsonFile::NAME,
+      json.iter().map(|competition| &competition.name).collect(),
+    ),
+  ];
+  if all_providers_names
+    .iter()
+    .map(|(_, names)| names.len())
+    .sum::<usize>()
+    != premier_league.len() + koora.len() + football_data.len() + json.len()
+  {
+    return None;
+  }
+
+  let primary_providers = parse_primary_providers(
+    providers_config.primary_providers,
+    &all_providers_names,
+  )?;
+
+  let formats: BTreeMap<Arc<String>, CompetitionFormat> = providers_config
+    .formats
+    .into_iter()
+    .map(|(tournament_name, format)| {
+      Some((Arc::new(tournament_name), format.parse().ok()?))
+    })
+    .collect::<Option<_>>()?;
+
+  let mut providers = ProvidersRegistry::default();
+  for provider_name in &providers_config.disabled_providers {
+    if !providers.set_enabled(provider_name, false) {
+      return None;
+    }
+  }
+
+  Some(ProvidersConfig {
+    premier_league,
+    koora,
+    football_data,
+    json,
+    teams: parse_teams_registry(providers_config.teams)?,
+    primary_providers,
+    formats,
+    cache_dir: None,
+    cache_ttl_seconds: providers_config
+      .cache_ttl_seconds
+      .map_or(DEFAULT_TTL_SECONDS, i64::from),
+    providers,
+    synced_since: BTreeMap::new(),
+  })
+}
+
+// NOTE: Every tournament fetched by more than one provider must have its
+// primary provider (and only those).
+#[must_use]
+fn parse_primary_providers(
+  primary_providers: BTreeMap<String, String>,
+  all_providers_names: &[(&str, HashSet<&Arc<String>>)],
+) -> Option<BTreeMap<Arc<String>, String>> {
+  let primary_providers: BTreeMap<Arc<String>, String> = primary_providers
+    .into_iter()
+    .map(|(tournament_name, provider_name)| {
+      (Arc::new(tournament_name), provider_name)
+    })
+    .collect();
+  let get_providers_count = |tournament_name: &Arc<String>| {
+    all_providers_names
+      .iter()
+      .filter(|(_, names)| names.contains(tournament_name))
+      .count()
+  };
+  let shared_names: HashSet<&Arc<String>> = all_providers_names
+    .iter()
+    .flat_map(|(_, names)| names.iter().copied())
+    .filter(|tournament_name| get_providers_count(tournament_name) > 1)
+    .collect();
+  let is_primary_provider_valid =
+    |tournament_name: &Arc<String>, provider_name: &str| {
+      all_providers_names.iter().any(|(name, names)| {
+        *name == provider_name && names.contains(tournament_name)
+      })
+    };
+
+  (shared_names.len() == primary_providers.len()
+    && primary_providers
+      .iter()
+      .all(|(tournament_name, provider_name)| {
+        shared_names.contains(tournament_name)
+          && is_primary_provider_valid(tournament_name, provider_name)
+      }))
+  .then_some(primary_providers)
+}
+
+#[allow(clippy::too_many_lines)]
+pub(super) fn test() {
+  #[cfg(test)]
+  use pretty_assertions::assert_eq;
+
+  let default_providers_config = ProvidersConfig::default();
+  assert_eq!(default_providers_config.premier_league.len(), 5);
+  assert_eq!(default_providers_config.koora.len(), 7);
+  assert_eq!(default_providers_config.football_data, vec![]);
+  assert_eq!(default_providers_config.json, vec![]);
+  assert_eq!(
+    default_providers_config.premier_league[0],
+    PremierLeagueCompetition {
+      name: Arc::new(String::from("First Team - Premier League")),
+      competition_id: 1,
+      season_id: None,
+      teams_ids: None,
+    },
+  );
+  assert_eq!(
+    ProvidersConfig::test_helper_pinned().premier_league[0],
+    PremierLeagueCompetition {
+      name: Arc::new(String::from("First Team - Premier League")),
+      competition_id: 1,
+      season_id: Some(418),
+      teams_ids: Some(vec![
+        1, 2, 130, 131, 43, 4, 6, 7, 9, 26, 10, 11, 12, 23, 14, 20, 21, 33, 25,
+        38,
+      ]),
+    },
+  );
+
+  assert_eq!(
+    default_providers_config
+      .teams
+      .get_display_name(&Arc::new(String::from("Al Hilal")), "ar"),
+    Arc::new(String::from("الهلال")),
+  );
+
+  assert_eq!(
+    default_providers_config.cache_ttl_seconds,
+    DEFAULT_TTL_SECONDS,
+  );
+  assert_eq!(
+    parse_providers_config(
+      r#"{"premier_league": [], "koora": [], "cache_ttl_seconds": 0}"#
+    )
+    .unwrap()
+    .cache_ttl_seconds,
+    0,
+  );
+  assert_eq!(
+    parse_providers_config(
+      r#"{"premier_league": [], "koora": [], "cache_ttl_seconds": -1}"#
+    ),
+    None,
+  );
+
+  // NOTE: Tournaments without a format are a double round-robin.
+  let get_formats_config = |split: &str| {
+    parse_providers_config(&format!(
+      r#"{{"premier_league": [], "koora": [], "formats": {{"Split League": {{
+        "matches_per_team_pair": 2,
+        "split": {},
+        "abandoned_match_rule": "replayed"
+      }}}}}}"#,
+      split,
+    ))
+  };
+  let formats_providers_config = get_formats_config(
+    r#"{"groups_sizes": [6, 6], "matches_per_team_pair": 1}"#,
+  )
+  .unwrap();
+  assert_eq!(
+    formats_providers_config
+      .get_format(&Arc::new(String::from("Split League"))),
+    CompetitionFormat::new(
+      2,
+      Some(crate::common::CompetitionSplit::new(vec![6, 6], 1)),
+    ),
+  );
+  assert_eq!(
+    formats_providers_config.get_format(&Arc::new(String::from("Other"))),
+    CompetitionFormat::default(),
+  );
+  assert_eq!(
+    get_formats_config(r#"{"groups_sizes": [12], "matches_per_team_pair": 1}"#),
+    None,
+  );
+
+  let competition_name = Arc::clone(&default_providers_config.koora[1].name);
+  let competition_providers_config =
+    default_providers_config.with_tournaments(&[Arc::clone(&competition_name)]);
+  assert_eq!(
+    competition_providers_config,
+    ProvidersConfig {
+      premier_league: vec![],
+      koora: vec![default_providers_config.koora[1].clone()],
+      ..default_providers_config.clone()
+    },
+  );
+  assert_eq!(
+    default_providers_config.with_tournaments(&[]).koora.len(),
+    0,
+  );
+
+  assert_eq!(
+    parse_providers_config(
+      r#"{"premier_league": [], "koora": [], "disabled_providers": ["Koora"]}"#
+    )
+    .unwrap()
+    .providers
+    .get_providers()[0],
+    (Koora::NAME, false),
+  );
+  assert_eq!(
+    parse_providers_config(
+      r#"{"premier_league": [], "koora": [], "disabled_providers": ["Unknown"]}"#
+    ),
+    None,
+  );
+
+  let koora_months = default_providers_config.koora[0].get_months();
+  assert_eq!(koora_months.len(), 24);
+  assert_eq!(koora_months.first().unwrap(), "202101");
+  assert_eq!(koora_months.last().unwrap(), "202212");
+
+  let get_koora_config = |season_start: &str, season_end: &str| {
+    parse_providers_config(&format!(
+      r#"{{
+        "premier_league": [],
+        "koora": [{{
+          "name": "Saudi Professional League",
+          "competition_id": 22551,
+          "season_start": "{}",
+          "season_end": "{}"
+        }}]
+      }}"#,
+      season_start, season_end,
+    ))
+  };
+  assert_eq!(
+    get_koora_config("2021-08-11", "2022-06-27").unwrap().koora[0].get_months(),
+    vec![
+      "202108", "202109", "202110", "202111", "202112", "202201", "202202",
+      "202203", "202204", "202205", "202206",
+    ],
+  );
+  assert_eq!(
+    get_koora_config("2022-03-01", "2022-03-31").unwrap().koora[0].get_months(),
+    vec!["202203"],
+  );
+  assert_eq!(get_koora_config("2022-06-27", "2021-08-11"), None);
+  assert_eq!(get_koora_config("2021-08", "2022-06-27"), None);
+
+  assert_eq!(parse_providers_config("not json"), None);
+  assert_eq!(
+    parse_providers_config(
+      r#"{"premier_league": [], "koora": [], "teams": [
+        {"id": "a", "name": "A"},
+        {"id": "a", "name": "B"}
+      ]}"#
+    ),
+    None,
+  );
+  assert_eq!(
+    parse_providers_config(
+      r#"{"premier_league": [
+        {"name": "A", "competition_id": 1, "season_id": 1, "teams_ids": [1]},
+        {"name": "A", "competition_id": 2, "season_id": 2, "teams_ids": [2]}
+      ], "koora": []}"#
+    ),
+    None,
+  );
+
+  let get_shared_config = |primary_providers: &str| {
+    parse_providers_config(&format!(
+      r#"{{
+        "premier_league": [{{"name": "A", "competition_id": 1}}],
+        "koora": [
+          {{
+            "name": "A",
+            "competition_id": 2,
+            "season_start": "2021-08-11",
+            "season_end": "2022-06-27"
+          }},
+          {{
+            "name": "B",
+            "competition_id": 3,
+            "season_start": "2021-08-11",
+            "season_end": "2022-06-27"
+          }}
+        ],
+        "primary_providers": {}
+      }}"#,
+      primary_providers,
+    ))
+  };
+  assert_eq!(
+    get_shared_config(r#"{"A": "Koora"}"#)
+      .unwrap()
+      .primary_providers,
+    BTreeMap::from([(Arc::new(String::from("A")), String::from("Koora"))]),
+  );
+  assert!(get_shared_config(r#"{"A": "Premier League"}"#).is_some());
+  for invalid_primary_providers in [
+    "{}",
+    r#"{"A": "Unknown"}"#,
+    r#"{"A": "Koora", "B": "Koora"}"#,
+  ] {
+    assert_eq!(
+      get_shared_config(invalid_primary_providers),
+      None,
+      "{}",
+      invalid_primary_providers,
+    );
+  }
+  let get_football_data_config = |path: &str, primary_providers: &str| {
+    parse_providers_config(&format!(
+      r#"{{
+        "premier_league": [{{"name": "A", "competition_id": 1}}],
+        "koora": [],
+        "football_data": [{{"name": "A", "path": "{}"}}],
+        "primary_providers": {}
+      }}"#,
+      path, primary_providers,
+    ))
+  };
+  assert_eq!(
+    get_football_data_config("E0.csv", r#"{"A": "football-data.co.uk"}"#)
+      .unwrap()
+      .football_data,
+    vec![FileCompetition {
+      name: Arc::new(String::from("A")),
+      path: PathBuf::from("E0.csv"),
+    }],
+  );
+  assert_eq!(get_football_data_config("E0.csv", "{}"), None);
+  assert_eq!(
+    parse_providers_config(
+      r#"{
+        "premier_league": [],
+        "koora": [],
+        "football_data": [{"name": "A", "path": "E0.csv"}],
+        "json": [{"name": "A", "path": "en.1.json"}],
+        "primary_providers": {"A": "JSON"}
+      }"#
+    )
+    .unwrap()
+    .json,
+    vec![FileCompetition {
+      name: Arc::new(String::from("A")),
+      path: PathBuf::from("en.1.json"),
+    }],
+  );
+  assert_eq!(
+    get_football_data_config("", r#"{"A": "football-data.co.uk"}"#),
+    None,
+  );
+  assert_eq!(
+    parse_providers_config(
+      r#"{"premier_league": [
+        {"name": "A", "competition_id": 1, "season_id": 1, "teams_ids": []}
+      ], "koora": []}"#
+    ),
+    None,
+  );
+  assert_eq!(
+    parse_providers_config(
+      r#"{"premier_league": [
+        {"name": "A", "competition_id": 1, "teams_ids": [1]}
+      ], "koora": []}"#
+    ),
+    None,
+  );
+  assert_eq!(
+    parse_providers_config(
+      r#"{"premier_league": [
+        {"name": "A", "competition_id": 1, "season_id": 1}
+      ], "koora": []}"#
+    )
+    .unwrap()
+    .premier_league[0]
+      .season_id,
+    Some(1),
+  );
+}
@@ -0,0 +1,293 @@
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::tournament::fetching::common::FetchError;
+use crate::tournament::fetching::config::PremierLeagueCompetition;
+use crate::tournament::fetching::transport::Transport;
+
+// NOTE: The current season only changes once a year but it's rechecked daily
+// so that a new season is picked up soon after it starts.
+const CURRENT_SEASON_TTL_SECONDS: i64 = 24 * 60 * 60;
+const CACHE_FILE_NAME: &str = "premier-league-seasons.json";
+
+#[must_use]
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct DiscoveryCache {
+  // NOTE: By competition-id.
+  current_seasons: BTreeMap<usize, CachedSeason>,
+  // NOTE: By season-id and never expires as a season's teams don't change.
+  seasons_teams_ids: BTreeMap<usize, Vec<usize>>,
+}
+
+#[must_use]
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+struct CachedSeason {
+  season_id: usize,
+  // NOTE: A unix-timestamp.
+  discovered_at: i64,
+}
+
+/// Gets the season and teams of a Premier League competition. Pinned ones are
+/// used as is and otherwise the current season (and its teams) is discovered
+/// from the API. Discoveries are cached in `cache_dir` (if any) to avoid extra
+/// requests.
+pub(super) fn discover_premier_league_season(
+  transport: &dyn Transport,
+  base_url: &str,
+  competition: &PremierLeagueCompetition,
+  cache_dir: Option<&Path>,
+  now: i64,
+) -> Result<(usize, Vec<usize>), FetchError> {
+  if let (Some(season_id), Some(teams_ids)) =
+    (competition.season_id, &competition.teams_ids)
+  {
+    return Ok((season_id, teams_ids.clone()));
+  }
+
+  let cache_path = cache_dir.map(|cache_dir| cache_dir.join(CACHE_FILE_NAME));
+  // NOTE: A missing or corrupt cache only costs extra requests.
+  let mut cache: DiscoveryCache = cache_path
+    .as_ref()
+    .and_then(|cache_path| fs::read_to_string(cache_path).ok())
+    .and_then(|cache| serde_json::from_str(&cache).ok())
+    .unwrap_or_default();
+
+  let season_id = match competition.season_id {
+    Some(season_id) => season_id,
+    None => match cache.current_seasons.get(&competition.competition_id) {
+      Some(cached_season)
+        if now - cached_season.discovered_at < CURRENT_SEASON_TTL_SECONDS =>
+      {
+        cached_season.season_id
+      },
+      _ => {
+        // NOTE: Seasons are listed newest first.
+        let season_id = *get_content_ids(
+          transport,
+          &format!(
+            "{}/football/competitions/{}/compseasons?page=0&pageSize=100",
+            base_url, competition.competition_id,
+          ),
+        )?
+        .first()
+        .ok_or_else(|| FetchError::Parse {
+          message: format!("No seasons ({:?}).", competition.competition_id),
+        })?;
+        cache.current_seasons.insert(
+          competition.competition_id,
+          CachedSeason {
+            season_id,
+            discovered_at: now,
+          },
+        );
+
+        season_id
+      },
+    },
+  };
+
+  let teams_ids = if let Some(teams_ids) =
+    cache.seasons_teams_ids.get(&season_id)
+  {
+    teams_ids.clone()
+  } else {
+    let teams_ids = get_content_ids(
+      transport,
+      &format!(
+        "{}/football/teams?pageSize=100&compSeasons={}&comps={}&altIds=true&page=0",
+        base_url, season_id, competition.competition_id,
+      ),
+    )?;
+    if teams_ids.is_empty() {
+      return Err(FetchError::Parse {
+        message: format!("No teams ({:?}).", season_id),
+      });
+    }
+    cache.seasons_teams_ids.insert(season_id, teams_ids.clone());
+
+    teams_ids
+  };
+
+  if let Some(cache_path) = cache_path {
+    // NOTE: Failing to cache only costs extra requests next time.
+    let _ignored_result = fs::write(cache_path, serde_json::to_string(&cache)?);
+  }
+
+  Ok((season_id, teams_ids))
+}
+
+// NOTE: Both seasons and teams are listed as `{"content": [{"id": 418.0}]}`.
+fn get_content_ids(
+  transport: &dyn Transport,
+  url: &str,
+) -> Result<Vec<usize>, FetchError> {
+  #[must_use]
+  #[derive(Deserialize)]
+  struct Page {
+    content: Vec<ContentItem>,
+  }
+
+  #[must_use]
+  #[derive(Deserialize)]
+  struct ContentItem {
+    id: f64,
+  }
+
+  let page: Page = serde_json::from_str(&transport.get(url)?)?;
+
+  page
+    .content
+    .into_iter()
+    .map(|ContentItem { id }| f64_id_to_usize(id))
+    .collect()
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub(super) fn f64_id_to_usize(id: f64) -> Result<usize, FetchError> {
+  const EPSILON: f64 = 0.00001;
+  const MAX: f64 = 1_000_000_000.0;
+
+  if id >= 0f64 && (id.round() - id).abs() < EPSILON && id <= MAX {
+    Ok(id as usize)
+  } else {
+    Err(FetchError::Parse {
+      message: format!("Invalid id ({:?}).", id),
+    })
+  }
+}
+
+#[allow(clippy::too_many_lines)]
+pub(super) fn test() {
+  #[cfg(test)]
+  use pretty_assertions::assert_eq;
+
+  use std::sync::Arc;
+
+  struct StubTransport {
+    seasons: &'static str,
+    urls: RefCell<Vec<String>>,
+  }
+  impl Transport for StubTransport {
+    fn get(&self, url: &str) -> Result<String, FetchError> {
+      self.urls.borrow_mut().push(String::from(url));
+
+      Ok(String::from(if url.contains("/compseasons?") {
+        self.seasons
+      } else {
+        r#"{"content":[{"id":1.0,"name":"Arsenal"},{"id":2.0,"name":"Aston Villa"}]}"#
+      }))
+    }
+  }
+
+  let transport = StubTransport {
+    seasons: r#"{"content":[{"id":418.0,"label":"2021/22"},{"id":363.0,"label":"2020/21"}]}"#,
+    urls: RefCell::new(vec![]),
+  };
+  let get_requests_count = || transport.urls.borrow().len();
+  let get_competition =
+    |season_id: Option<usize>, teams_ids: Option<Vec<usize>>| {
+      PremierLeagueCompetition {
+        name: Arc::new(String::from("First Team - Premier League")),
+        competition_id: 1,
+        season_id,
+        teams_ids,
+      }
+    };
+  let discover = |competition: &PremierLeagueCompetition,
+                  cache_dir: Option<&Path>,
+                  now: i64| {
+    discover_premier_league_season(
+      &transport,
+      "http://localhost",
+      competition,
+      cache_dir,
+      now,
+    )
+  };
+
+  assert_eq!(
+    discover(&get_competition(None, None), None, 0),
+    Ok((418, vec![1, 2])),
+  );
+  assert_eq!(
+    *transport.urls.borrow(),
+    vec![
+      "http://localhost/football/competitions/1/compseasons?page=0&pageSize=100",
+      "http://localhost/football/teams?pageSize=100&compSeasons=418&comps=1&altIds=true&page=0",
+    ],
+  );
+  // NOTE: A pinned (historic) season only needs its teams.
+  assert_eq!(
+    discover(&get_competition(Some(363), None), None, 0),
+    Ok((363, vec![1, 2])),
+  );
+  assert_eq!(get_requests_count(), 3);
+  assert_eq!(
+    discover(&get_competition(Some(363), Some(vec![7])), None, 0),
+    Ok((363, vec![7])),
+  );
+  assert_eq!(get_requests_count(), 3);
+
+  // NOTE: Tests run in parallel so caches must not be shared.
+  let cache_dir = std::env::temp_dir().join(format!(
+    "boa-discovery-{}-{:?}",
+    std::process::id(),
+    std::thread::current().id(),
+  ));
+  fs::create_dir_all(&cache_dir).unwrap();
+  let now = 1_640_000_000;
+  assert_eq!(
+    discover(&get_competition(None, None), Some(&cache_dir), now),
+    Ok((418, vec![1, 2])),
+  );
+  assert_eq!(get_requests_count(), 5);
+  assert_eq!(
+    discover(&get_competition(None, None), Some(&cache_dir), now + 1),
+    Ok((418, vec![1, 2])),
+  );
+  assert_eq!(get_requests_count(), 5);
+  // NOTE: Only the current season expires (and not its teams).
+  assert_eq!(
+    discover(
+      &get_competition(None, None),
+      Some(&cache_dir),
+      now + CURRENT_SEASON_TTL_SECONDS,
+    ),
+    Ok((418, vec![1, 2])),
+  );
+  assert_eq!(get_requests_count(), 6);
+  fs::remove_dir_all(&cache_dir).unwrap();
+
+  let transport = StubTransport {
+    seasons: r#"{"content":[]}"#,
+    urls: RefCell::new(vec![]),
+  };
+  assert!(matches!(
+    discover_premier_league_season(
+      &transport,
+      "http://localhost",
+      &get_competition(None, None),
+      None,
+      0,
+    ),
+    Err(FetchError::Parse { .. }),
+  ));
+  assert_eq!(f64_id_to_usize(1420.0), Ok(1420));
+  assert!(f64_id_to_usize(-1.0).is_err());
+  assert!(f64_id_to_usize(1.5).is_err());
+}
+
+#[cfg(test)]
+mod tests {
+  use super::test;
+
+  #[test]
+  fn test_tournament_fetching_discovery() {
+    test();
+  }
+}
@@ -0,0 +1,271 @@
+use std::cmp::Ordering;
+
+use chrono::Datelike;
+use chrono::NaiveDate;
+use chrono::NaiveTime;
+use chrono::TimeZone;
+use chrono::Utc;
+
+use crate::tournament::fetching::common::FetchError;
+use crate::tournament::fetching::common::MatchOutcome;
+use crate::tournament::fetching::common::MatchStatus;
+use crate::tournament::fetching::common::TournamentProvider;
+use crate::tournament::fetching::teams::TeamsRegistry;
+use crate::tournament::fetching::FootballData;
+
+// NOTE: Older files (and the extra leagues' ones) use the short names.
+// SEE: https://www.football-data.co.uk/notes.txt
+const DIVISION_COLUMNS: &[&str] = &["Div"];
+const DATE_COLUMNS: &[&str] = &["Date"];
+const TIME_COLUMNS: &[&str] = &["Time"];
+const HOME_TEAM_COLUMNS: &[&str] = &["HomeTeam", "HT", "Home"];
+const AWAY_TEAM_COLUMNS: &[&str] = &["AwayTeam", "AT", "Away"];
+const HOME_GOALS_COLUMNS: &[&str] = &["FTHG", "HG"];
+const AWAY_GOALS_COLUMNS: &[&str] = &["FTAG", "AG"];
+const RESULT_COLUMNS: &[&str] = &["FTR", "Res"];
+
+/// A match (a row of a football-data.co.uk CSV file) like:
+/// `E0,13/08/2021,20:00,Brentford,Arsenal,2,0,H` which is its division, date,
+/// time (only in newer files), home-team, away-team, full-time score and
+/// result. Other columns (e.g., half-time scores and odds) are ignored.
+#[must_use]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct FootballDataMatch {
+  pub(super) division: String,
+  // NOTE: A unix-timestamp of UK's local time which is treated as UTC as it's
+  // only used to order matches.
+  pub(super) date: i64,
+  pub(super) home_team_name: String,
+  pub(super) away_team_name: String,
+  pub(super) score: (usize, usize),
+}
+
+impl FootballDataMatch {
+  // NOTE: Only leagues are covered so there's neither extra-time nor
+  // shootouts.
+  #[must_use]
+  pub(super) fn get_match_outcome(
+    &self,
+    teams_registry: &TeamsRegistry,
+  ) -> MatchOutcome {
+    let resolve = |team_name: &str| {
+      teams_registry.resolve(FootballData::NAME, None, team_name)
+    };
+
+    MatchOutcome {
+      home_team_name: resolve(&self.home_team_name),
+      away_team_name: resolve(&self.away_team_name),
+      regulation_score: Some(self.score),
+      extra_time_score: None,
+      shootout_score: None,
+      status: MatchStatus::Finished,
+      date: Some(self.date),
+    }
+  }
+}
+
+/// Parses the lines of a football-data.co.uk CSV file (with its header)
+/// rejecting it as a whole if any of its rows doesn't match the expected
+/// layout or isn't of the same division as the others.
+pub(super) fn parse_football_data_csv(
+  lines: &[String],
+) -> Result<Vec<FootballDataMatch>, FetchError> {
+  let to_parse_error = |message: String| FetchError::Parse { message };
+
+  // NOTE: Some files end with empty rows (e.g., `,,,,`).
+  let mut rows = lines
+    .iter()
+    .map(|line| line.trim_start_matches('\u{feff}').trim_end())
+    .filter(|line| !line.chars().all(|c| c == ','));
+  let header: Vec<&str> = match rows.next() {
+    Some(header) => header.split(',').map(str::trim).collect(),
+    // NOTE: Assumed to be a season that hasn't started yet.
+    None => return Ok(vec![]),
+  };
+  let get_column =
+    |names: &[&str]| header.iter().position(|column| names.contains(column));
+  let get_required_column = |names: &[&str]| {
+    get_column(names).ok_or_else(|| {
+      to_parse_error(format!("Missing column ({:?}).", names[0]))
+    })
+  };
+
+  let division_column = get_required_column(DIVISION_COLUMNS)?;
+  let date_column = get_required_column(DATE_COLUMNS)?;
+  let time_column = get_column(TIME_COLUMNS);
+  let home_team_column = get_required_column(HOME_TEAM_COLUMNS)?;
+  let away_team_column = get_required_column(AWAY_TEAM_COLUMNS)?;
+  let home_goals_column = get_required_column(HOME_GOALS_COLUMNS)?;
+  let away_goals_column = get_required_column(AWAY_GOALS_COLUMNS)?;
+  let result_column = get_required_column(RESULT_COLUMNS)?;
+
+  let matches = rows
+    .map(|row| -> Result<FootballDataMatch, FetchError> {
+      // NOTE: Cells are never quoted (not even team names) so a quote means
+      // the layout changed.
+      if row.contains('"') {
+        return Err(to_parse_error(format!("Unsupported quotes ({:?}).", row)));
+      }
+
+      let cells: Vec<&str> = row.split(',').map(str::trim).collect();
+      let get_cell = |column: usize| -> Result<&str, FetchError> {
+        match cells.get(column) {
+          Some(&cell) if !cell.is_empty() => Ok(cell),
+          _ => Err(to_parse_error(format!(
+            "Missing cell ({:?}, {:?}).",
+            header[column], row,
+          ))),
+        }
+      };
+      let parse_goals = |column: usize| -> Result<usize, FetchError> {
+        let cell = get_cell(column)?;
+        cell.parse().map_err(|_| {
+          to_parse_error(format!("Invalid goals ({:?}, {:?}).", cell, row))
+        })
+      };
+
+      let date = parse_date(get_cell(date_column)?)
+        .ok_or_else(|| to_parse_error(format!("Invalid date ({:?}).", row)))?;
+      // NOTE: Missing (or empty) times are assumed to be at midnight.
+      let time = match time_column.and_then(|column| cells.get(column)) {
+        Some(&time) if !time.is_empty() => {
+          NaiveTime::parse_from_str(time, "%H:%M")
+            .map_err(|_| to_parse_error(format!("Invalid time ({:?}).", row)))?
+        },
+        _ => NaiveTime::MIN,
+      };
+
+      let score = (
+        parse_goals(home_goals_column)?,
+        parse_goals(away_goals_column)?,
+      );
+      let expected_result = match score.0.cmp(&score.1) {
+        Ordering::Greater => "H",
+        Ordering::Equal => "D",
+        Ordering::Less => "A",
+      };
+      if get_cell(result_column)? != expected_result {
+        return Err(to_parse_error(format!("Invalid result ({:?}).", row)));
+      }
+
+      Ok(FootballDataMatch {
+        division: String::from(get_cell(division_column)?),
+        date: Utc.from_utc_datetime(&date.and_time(time)).timestamp(),
+        home_team_name: String::from(get_cell(home_team_column)?),
+        away_team_name: String::from(get_cell(away_team_column)?),
+        score,
+      })
+    })
+    .collect::<Result<Vec<_>, _>>()?;
+
+  // NOTE: Each file is a single division's season.
+  if let Some(football_data_match) =
+    matches.iter().find(|football_data_match| {
+      football_data_match.division != matches[0].division
+    })
+  {
+    return Err(to_parse_error(format!(
+      "Invalid division ({:?}).",
+      football_data_match.division,
+    )));
+  }
+
+  Ok(matches)
+}
+
+// NOTE: Older files have 2-digit years (e.g., `13/08/05`).
+#[must_use]
+fn parse_date(date: &str) -> Option<NaiveDate> {
+  NaiveDate::parse_from_str(date, "%d/%m/%Y")
+    .ok()
+    .filter(|date| date.year() >= 1000)
+    .or_else(|| NaiveDate::parse_from_str(date, "%d/%m/%y").ok())
+}
+
+pub(super) fn test() {
+  #[cfg(test)]
+  use pretty_assertions::assert_eq;
+
+  let parse = |csv: &str| {
+    parse_football_data_csv(
+      &csv.lines().map(String::from).collect::<Vec<String>>(),
+    )
+  };
+
+  let (_, lines) = FootballData::test_helper_download_tournaments()
+    .pop()
+    .unwrap();
+  let matches = parse_football_data_csv(&lines).unwrap();
+  assert_eq!(matches.len(), 10);
+  assert!(matches
+    .iter()
+    .all(|football_data_match| football_data_match.division == "E0"));
+  assert_eq!(
+    matches[0],
+    FootballDataMatch {
+      division: String::from("E0"),
+      date: 1_628_884_800,
+      home_team_name: String::from("Brentford"),
+      away_team_name: String::from("Arsenal"),
+      score: (2, 0),
+    },
+  );
+  assert_eq!(
+    matches[9],
+    FootballDataMatch {
+      division: String::from("E0"),
+      date: 1_629_045_000,
+      home_team_name: String::from("Tottenham"),
+      away_team_name: String::from("Man City"),
+      score: (1, 0),
+    },
+  );
+  assert!(matches.windows(2).all(|pair| pair[0].date <= pair[1].date));
+
+  // NOTE: An older layout (with a BOM, short column names, 2-digit years and
+  // without times) and extra columns and empty rows.
+  assert_eq!(
+    parse(
+      "\u{feff}Div,Date,HT,AT,FTHG,FTAG,FTR,HTHG,HTAG,HTR,Referee\r\n\
+       E0,13/08/05,Aston Villa,Bolton,2,2,D,2,2,D,M Riley\r\n\
+       ,,,,,,,,,,\r\n",
+    ),
+    Ok(vec![FootballDataMatch {
+      division: String::from("E0"),
+      date: 1_123_891_200,
+      home_team_name: String::from("Aston Villa"),
+      away_team_name: String::from("Bolton"),
+      score: (2, 2),
+    }]),
+  );
+  assert_eq!(parse(""), Ok(vec![]));
+
+  for invalid_csv in [
+    "Div,Date,HomeTeam,AwayTeam,FTHG,FTAG",
+    "Div,Date,HomeTeam,AwayTeam,FTHG,FTAG,FTR\nE0,13/08/2021,A,B,2,0,A",
+    "Div,Date,HomeTeam,AwayTeam,FTHG,FTAG,FTR\nE0,13/08/2021,A,B,,,",
+    "Div,Date,HomeTeam,AwayTeam,FTHG,FTAG,FTR\nE0,13/08/2021,A,B,-1,0,A",
+    "Div,Date,HomeTeam,AwayTeam,FTHG,FTAG,FTR\nE0,2021-08-13,A,B,2,0,H",
+    "Div,Date,Time,HomeTeam,AwayTeam,FTHG,FTAG,FTR\nE0,13/08/2021,8pm,A,B,2,0,H",
+    "Div,Date,HomeTeam,AwayTeam,FTHG,FTAG,FTR\nE0,13/08/2021,\"A\",B,2,0,H",
+    "Div,Date,HomeTeam,AwayTeam,FTHG,FTAG,FTR\n\
+     E0,13/08/2021,A,B,2,0,H\n\
+     E1,14/08/2021,C,D,0,0,D",
+  ] {
+    assert!(
+      matches!(parse(invalid_csv), Err(FetchError::Parse { .. })),
+      "{}",
+      invalid_csv,
+    );
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::test;
+
+  #[test]
+  fn test_tournament_fetching_football_data() {
+    test();
+  }
+}
@@ -0,0 +1,814 @@
+use std::collections::BTreeSet;
+
+use chrono::NaiveDate;
+use chrono::NaiveTime;
+use chrono::TimeZone;
+use chrono::Utc;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::common::AbandonedMatchRule;
+use crate::common::CompetitionFormat;
+use crate::common::CompetitionSplit;
+use crate::tournament::fetching::common::FetchError;
+use crate::tournament::fetching::common::FetchedTournament;
+use crate::tournament::fetching::common::Fixture;
+use crate::tournament::fetching::common::FixtureStatus;
+use crate::tournament::fetching::common::MatchOutcome;
+use crate::tournament::fetching::common::MatchStatus;
+use crate::tournament::fetching::common::TournamentProvider;
+use crate::tournament::fetching::common::DRAW_FACTOR;
+use crate::tournament::fetching::common::WIN_FACTOR;
+use crate::tournament::fetching::teams::TeamsRegistry;
+use crate::tournament::fetching::JsonFile;
+
+// NOTE: Bumped on any change that older readers can't read (e.g., a renamed
+// field). Adding optional fields doesn't need a new version.
+pub(super) const INTERCHANGE_VERSION: u64 = 1;
+
+/// A tournament in the interchange format (version 1) which is what
+/// `write_interchange_tournament()` writes and `parse_interchange_tournament()`
+/// reads (besides openfootball's layout), e.g.:
+/// ```json
+/// {
+///   "version": 1,
+///   "name": "English Premier League",
+///   "teams": ["Arsenal", "Brentford"],
+///   "scoring": {"win": 3, "draw": 1, "loss": 0},
+///   "format": {
+///     "matches_per_team_pair": 2,
+///     "split": null,
+///     "abandoned_match_rule": "replayed"
+///   },
+///   "matches": [{
+///     "home_team": "Brentford",
+///     "away_team": "Arsenal",
+///     "regulation_score": [2, 0],
+///     "extra_time_score": null,
+///     "shootout_score": null,
+///     "status": "finished",
+///     "date": 1628884800
+///   }],
+///   "fixtures": [{
+///     "home_team": "Arsenal",
+///     "away_team": "Brentford",
+///     "date": 1644678000,
+///     "venue": null,
+///     "status": "rescheduled",
+///     "original_date": 1639931400
+///   }]
+/// }
+/// ```
+#[must_use]
+#[derive(Deserialize, Serialize)]
+struct TournamentJson {
+  version: u64,
+  name: String,
+  // NOTE: Every team that has a match or a fixture (and only those).
+  teams: Vec<String>,
+  // NOTE: Only the default one (3-1-0) is supported for now.
+  scoring: ScoringJson,
+  format: FormatJson,
+  // NOTE: Ordered chronologically (oldest first) with `(home, away)` scores
+  // and unix-timestamps as dates. Extra-time scores include regulation goals.
+  matches: Vec<MatchJson>,
+  // NOTE: `null` when unplayed matches aren't listed in which case they're
+  // assumed from the format.
+  fixtures: Option<Vec<FixtureJson>>,
+}
+
+#[must_use]
+#[derive(Deserialize, Serialize)]
+struct ScoringJson {
+  win: usize,
+  draw: usize,
+  loss: usize,
+}
+
+// NOTE: Also the layout of formats in providers' configs.
+#[must_use]
+#[derive(Deserialize, Serialize)]
+pub(super) struct FormatJson {
+  matches_per_team_pair: usize,
+  split: Option<SplitJson>,
+  abandoned_match_rule: AbandonedMatchRuleJson,
+}
+
+impl FormatJson {
+  pub(super) fn parse(self) -> Result<CompetitionFormat, String> {
+    let split = self
+      .split
+      .map(|split| {
+        CompetitionSplit::try_new(
+          split.groups_sizes,
+          split.matches_per_team_pair,
+        )
+        .ok_or_else(|| String::from("Invalid split."))
+      })
+      .transpose()?;
+
+    Ok(
+      CompetitionFormat::try_new(self.matches_per_team_pair, split)
+        .ok_or_else(|| {
+          format!(
+            "Invalid matches per team-pair ({:?}).",
+            self.matches_per_team_pair,
+          )
+        })?
+        .with_abandoned_match_rule(match self.abandoned_match_rule {
+          AbandonedMatchRuleJson::Replayed => AbandonedMatchRule::Replayed,
+          AbandonedMatchRuleJson::ResultStands => {
+            AbandonedMatchRule::ResultStands
+          },
+        }),
+    )
+  }
+}
+
+#[must_use]
+#[derive(Deserialize, Serialize)]
+struct SplitJson {
+  groups_sizes: Vec<usize>,
+  matches_per_team_pair: usize,
+}
+
+#[must_use]
+#[derive(Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum AbandonedMatchRuleJson {
+  Replayed,
+  ResultStands,
+}
+
+#[must_use]
+#[derive(Deserialize, Serialize)]
+struct MatchJson {
+  home_team: String,
+  away_team: String,
+  regulation_score: Option<(usize, usize)>,
+  extra_time_score: Option<(usize, usize)>,
+  shootout_score: Option<(usize, usize)>,
+  status: MatchStatusJson,
+  date: Option<i64>,
+}
+
+#[must_use]
+#[derive(Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum MatchStatusJson {
+  Finished,
+  Abandoned,
+  Awarded,
+}
+
+#[must_use]
+#[derive(Deserialize, Serialize)]
+struct FixtureJson {
+  home_team: String,
+  away_team: String,
+  date: Option<i64>,
+  venue: Option<String>,
+  #[serde(flatten)]
+  status: FixtureStatusJson,
+}
+
+#[must_use]
+#[derive(Deserialize, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+enum FixtureStatusJson {
+  Scheduled,
+  Postponed,
+  Rescheduled { original_date: i64 },
+}
+
+/// A tournament read from an interchange (or openfootball) document.
+#[must_use]
+#[derive(Debug, PartialEq, Eq)]
+pub(super) struct ImportedTournament {
+  pub(super) matches_outcomes: Vec<MatchOutcome>,
+  pub(super) fixtures: Option<Vec<Fixture>>,
+  pub(super) format: CompetitionFormat,
+}
+
+/// Writes a fetched tournament in the interchange format (see
+/// `TournamentJson`).
+/// # Panics
+#[must_use]
+pub(super) fn write_interchange_tournament(
+  fetched_tournament: &FetchedTournament,
+) -> String {
+  let format = &fetched_tournament.format;
+  let teams: BTreeSet<&String> = fetched_tournament
+    .matches_outcomes
+    .iter()
+    .flat_map(|match_outcome| {
+      [
+        &*match_outcome.home_team_name,
+        &*match_outcome.away_team_name,
+      ]
+    })
+    .chain(
+      fetched_tournament
+        .fixtures
+        .iter()
+        .flatten()
+        .flat_map(|fixture| {
+          [&*fixture.home_team_name, &*fixture.away_team_name]
+        }),
+    )
+    .collect();
+
+  let tournament = TournamentJson {
+    version: INTERCHANGE_VERSION,
+    name: String::clone(&fetched_tournament.name),
+    teams: teams.into_iter().cloned().collect(),
+    scoring: ScoringJson {
+      win: WIN_FACTOR,
+      draw: DRAW_FACTOR,
+      loss: 0,
+    },
+    format: FormatJson {
+      matches_per_team_pair: format.matches_per_team_pair,
+      split: format.split.as_ref().map(|split| SplitJson {
+        groups_sizes: split.groups_sizes.clone(),
+        matches_per_team_pair: split.matches_per_team_pair,
+      }),
+      abandoned_match_rule: match format.abandoned_match_rule {
+        AbandonedMatchRule::Replayed => AbandonedMatchRuleJson::Replayed,
+        AbandonedMatchRule::ResultStands => {
+          AbandonedMatchRuleJson::ResultStands
+        },
+      },
+    },
+    matches: fetched_tournament
+      .matches_outcomes
+      .iter()
+      .map(|match_outcome| MatchJson {
+        home_team: String::clone(&match_outcome.home_team_name),
+        away_team: String::clone(&match_outcome.away_team_name),
+        regulation_score: match_outcome.regulation_score,
+        extra_time_score: match_outcome.extra_time_score,
+        shootout_score: match_outcome.shootout_score,
+        status: match match_outcome.status {
+          MatchStatus::Finished => MatchStatusJson::Finished,
+          MatchStatus::Abandoned => MatchStatusJson::Abandoned,
+          MatchStatus::Awarded => MatchStatusJson::Awarded,
+        },
+        date: match_outcome.date,
+      })
+      .collect(),
+    fixtures: fetched_tournament.fixtures.as_ref().map(|fixtures| {
+      fixtures
+        .iter()
+        .map(|fixture| FixtureJson {
+          home_team: String::clone(&fixture.home_team_name),
+          away_team: String::clone(&fixture.away_team_name),
+          date: fixture.date,
+          venue: fixture.venue.clone(),
+          status: match fixture.status {
+            FixtureStatus::Scheduled => FixtureStatusJson::Scheduled,
+            FixtureStatus::Postponed => FixtureStatusJson::Postponed,
+            FixtureStatus::Rescheduled { original_date } => {
+              FixtureStatusJson::Rescheduled { original_date }
+            },
+          },
+        })
+        .collect()
+    }),
+  };
+
+  // NOTE: The document is only made of strings, numbers, arrays and objects
+  // with string keys which can always be serialized.
+  serde_json::to_string_pretty(&tournament)
+    .expect("Serializing an interchange tournament can't fail")
+}
+
+/// Reads a tournament from an interchange document (of a supported version)
+/// or (when it has no version) from openfootball's `football.json` layout.
+pub(super) fn parse_interchange_tournament(
+  document: &str,
+  teams_registry: &TeamsRegistry,
+) -> Result<ImportedTournament, FetchError> {
+  let document: Value = serde_json::from_str(document)?;
+  // NOTE: Otherwise, an array would be read as a tournament (by position).
+  if !document.is_object() {
+    return Err(FetchError::Parse {
+      message: String::from("Invalid document."),
+    });
+  }
+
+  match document.get("version").map(Value::as_u64) {
+    None => parse_open_football_tournament(
+      &serde_json::from_value(document)?,
+      teams_registry,
+    ),
+    Some(Some(INTERCHANGE_VERSION)) => {
+      parse_tournament_json(serde_json::from_value(document)?, teams_registry)
+    },
+    Some(version) => Err(FetchError::Parse {
+      message: format!("Unsupported version ({:?}).", version),
+    }),
+  }
+}
+
+#[must_use]
+#[allow(clippy::too_many_lines)]
+fn parse_tournament_json(
+  tournament: TournamentJson,
+  teams_registry: &TeamsRegistry,
+) -> Result<ImportedTournament, FetchError> {
+  let to_parse_error = |message: String| FetchError::Parse { message };
+  let resolve =
+    |team_name: &str| teams_registry.resolve(JsonFile::NAME, None, team_name);
+
+  let ScoringJson { win, draw, loss } = tournament.scoring;
+  if (win, draw, loss) != (WIN_FACTOR, DRAW_FACTOR, 0) {
+    return Err(to_parse_error(format!(
+      "Unsupported scoring ({:?}).",
+      (win, draw, loss),
+    )));
+  }
+
+  let format = tournament.format.parse().map_err(to_parse_error)?;
+
+  let teams: BTreeSet<&str> =
+    tournament.teams.iter().map(String::as_str).collect();
+  let playing_teams: BTreeSet<&str> = tournament
+    .matches
+    .iter()
+    .map(|match_json| (&match_json.home_team, &match_json.away_team))
+    .chain(
+      tournament
+        .fixtures
+        .iter()
+        .flatten()
+        .map(|fixture| (&fixture.home_team, &fixture.away_team)),
+    )
+    .flat_map(|(home_team, away_team)| [home_team.as_str(), away_team.as_str()])
+    .collect();
+  if teams.len() != tournament.teams.len() || teams != playing_teams {
+    return Err(to_parse_error(format!(
+      "Invalid teams ({:?}).",
+      teams
+        .symmetric_difference(&playing_teams)
+        .collect::<Vec<_>>(),
+    )));
+  }
+
+  let matches_outcomes = tournament
+    .matches
+    .iter()
+    .map(|match_json| -> Result<MatchOutcome, FetchError> {
+      let match_outcome = MatchOutcome {
+        home_team_name: resolve(&match_json.home_team),
+        away_team_name: resolve(&match_json.away_team),
+        regulation_score: match_json.regulation_score,
+        extra_time_score: match_json.extra_time_score,
+        shootout_score: match_json.shootout_score,
+        status: match match_json.status {
+          MatchStatusJson::Finished => MatchStatus::Finished,
+          MatchStatusJson::Abandoned => MatchStatus::Abandoned,
+          MatchStatusJson::Awarded => MatchStatus::Awarded,
+        },
+        date: match_json.date,
+      };
+
+      // NOTE: Only abandoned matches may have no score.
+      if match_json.home_team == match_json.away_team
+        || (match_outcome.status != MatchStatus::Abandoned
+          && match_outcome.get_score().is_none())
+      {
+        return Err(to_parse_error(format!(
+          "Invalid match ({:?}, {:?}).",
+          match_json.home_team, match_json.away_team,
+        )));
+      }
+
+      Ok(match_outcome)
+    })
+    .collect::<Result<Vec<_>, _>>()?;
+  let fixtures = tournament
+    .fixtures
+    .map(|fixtures| {
+      fixtures
+        .into_iter()
+        .map(|fixture| -> Result<Fixture, FetchError> {
+          if fixture.home_team == fixture.away_team {
+            return Err(to_parse_error(format!(
+              "Invalid fixture ({:?}).",
+              fixture.home_team,
+            )));
+          }
+
+          Ok(Fixture {
+            home_team_name: resolve(&fixture.home_team),
+            away_team_name: resolve(&fixture.away_team),
+            date: fixture.date,
+            venue: fixture.venue,
+            status: match fixture.status {
+              FixtureStatusJson::Scheduled => FixtureStatus::Scheduled,
+              FixtureStatusJson::Postponed => FixtureStatus::Postponed,
+              FixtureStatusJson::Rescheduled { original_date } => {
+                FixtureStatus::Rescheduled { original_date }
+              },
+            },
+          })
+        })
+        .collect::<Result<Vec<_>, _>>()
+    })
+    .transpose()?;
+
+  Ok(ImportedTournament {
+    matches_outcomes,
+    fixtures,
+    format,
+  })
+}
+
+/// Reads openfootball's layout (both the newer one with top-level `matches`
+/// and the older one with `rounds`). Matches without a score are its fixtures
+/// and the format is assumed to be the default one (a double round-robin).
+// SEE: https://github.com/openfootball/football.json
+#[must_use]
+fn parse_open_football_tournament(
+  tournament: &OpenFootballJson,
+  teams_registry: &TeamsRegistry,
+) -> Result<ImportedTournament, FetchError> {
+  let resolve = |team: &OpenFootballTeamJson| {
+    teams_registry.resolve(JsonFile::NAME, None, team.get_name())
+  };
+
+  let mut matches_outcomes: Vec<MatchOutcome> = vec![];
+  let mut fixtures: Vec<Fixture> = vec![];
+  for open_football_match in tournament
+    .matches
+    .iter()
+    .chain(tournament.rounds.iter().flat_map(|round| &round.matches))
+  {
+    let date = open_football_match.get_date()?;
+    let score = open_football_match.get_score();
+    if open_football_match.team1.get_name()
+      == open_football_match.team2.get_name()
+    {
+      return Err(FetchError::Parse {
+        message: format!(
+          "Invalid match ({:?}).",
+          open_football_match.team1.get_name(),
+        ),
+      });
+    }
+
+    if score.ft.is_none() && score.et.is_none() {
+      fixtures.push(Fixture {
+        home_team_name: resolve(&open_football_match.team1),
+        away_team_name: resolve(&open_football_match.team2),
+        date,
+        venue: None,
+        status: FixtureStatus::Scheduled,
+      });
+    } else {
+      matches_outcomes.push(MatchOutcome {
+        home_team_name: resolve(&open_football_match.team1),
+        away_team_name: resolve(&open_football_match.team2),
+        regulation_score: score.ft,
+        extra_time_score: score.et,
+        shootout_score: score.p,
+        status: MatchStatus::Finished,
+        date,
+      });
+    }
+  }
+  // NOTE: Rounds aren't necessarily chronological (e.g., postponed matches).
+  matches_outcomes.sort_by_key(|match_outcome| match_outcome.date);
+
+  Ok(ImportedTournament {
+    matches_outcomes,
+    fixtures: Some(fixtures),
+    format: CompetitionFormat::default(),
+  })
+}
+
+#[must_use]
+#[derive(Deserialize)]
+struct OpenFootballJson {
+  #[serde(default)]
+  matches: Vec<OpenFootballMatchJson>,
+  #[serde(default)]
+  rounds: Vec<OpenFootballRoundJson>,
+}
+
+#[must_use]
+#[derive(Deserialize)]
+struct OpenFootballRoundJson {
+  matches: Vec<OpenFootballMatchJson>,
+}
+
+#[must_use]
+#[derive(Deserialize)]
+struct OpenFootballMatchJson {
+  date: Option<String>,
+  time: Option<String>,
+  team1: OpenFootballTeamJson,
+  team2: OpenFootballTeamJson,
+  score: Option<OpenFootballScoreJson>,
+  // NOTE: The older layout's scores (instead of `score`).
+  score1: Option<usize>,
+  score2: Option<usize>,
+  score1et: Option<usize>,
+  score2et: Option<usize>,
+  score1p: Option<usize>,
+  score2p: Option<usize>,
+}
+
+// NOTE: Just a name in the newer layout.
+#[must_use]
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OpenFootballTeamJson {
+  Name(String),
+  Team { name: String },
+}
+
+#[must_use]
+#[derive(Clone, Copy, Default, Deserialize)]
+struct OpenFootballScoreJson {
+  ft: Option<(usize, usize)>,
+  et: Option<(usize, usize)>,
+  p: Option<(usize, usize)>,
+}
+
+impl OpenFootballMatchJson {
+  // NOTE: Times are local (sometimes with their UTC offset like
+  // `20:00 UTC+1`) and are treated as UTC as they're only used to order
+  // matches.
+  fn get_date(&self) -> Result<Option<i64>, FetchError> {
+    let Some(date) = &self.date else {
+      return Ok(None);
+    };
+
+    let to_parse_error = || FetchError::Parse {
+      message: format!("Invalid date ({:?}, {:?}).", date, self.time),
+    };
+    let date = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+      .map_err(|_| to_parse_error())?;
+    let time = match self
+      .time
+      .as_deref()
+      .and_then(|time| time.split_whitespace().next())
+    {
+      Some(time) => NaiveTime::parse_from_str(time, "%H:%M")
+        .map_err(|_| to_parse_error())?,
+      None => NaiveTime::MIN,
+    };
+
+    Ok(Some(
+      Utc.from_utc_datetime(&date.and_time(time)).timestamp(),
+    ))
+  }
+
+  #[must_use]
+  fn get_score(&self) -> OpenFootballScoreJson {
+    let get_pair =
+      |first: Option<usize>, second: Option<usize>| first.zip(second);
+
+    self.score.unwrap_or_else(|| OpenFootballScoreJson {
+      ft: get_pair(self.score1, self.score2),
+      et: get_pair(self.score1et, self.score2et),
+      p: get_pair(self.score1p, self.score2p),
+    })
+  }
+}
+
+impl OpenFootballTeamJson {
+  #[must_use]
+  fn get_name(&self) -> &str {
+    match self {
+      Self::Name(name) | Self::Team { name } => name,
+    }
+  }
+}
+
+#[allow(clippy::too_many_lines)]
+pub(super) fn test() {
+  #[cfg(test)]
+  use pretty_assertions::assert_eq;
+
+  use std::sync::Arc;
+
+  let teams_registry = TeamsRegistry::default();
+  let parse =
+    |document: &str| parse_interchange_tournament(document, &teams_registry);
+  let team = |name: &str| Arc::new(String::from(name));
+
+  let fetched_tournament = FetchedTournament {
+    name: team("A League"),
+    provider_name: JsonFile::NAME,
+    matches_outcomes: vec![
+      MatchOutcome {
+        home_team_name: team("A"),
+        away_team_name: team("B"),
+        regulation_score: Some((1, 1)),
+        extra_time_score: Some((2, 1)),
+        shootout_score: None,
+        status: MatchStatus::Finished,
+        date: Some(1_628_884_800),
+      },
+      MatchOutcome {
+        home_team_name: team("B"),
+        away_team_name: team("C"),
+        regulation_score: None,
+        extra_time_score: None,
+        shootout_score: None,
+        status: MatchStatus::Abandoned,
+        date: None,
+      },
+    ],
+    fixtures: Some(vec![Fixture {
+      home_team_name: team("C"),
+      away_team_name: team("D"),
+      date: Some(1_644_678_000),
+      venue: Some(String::from("Stadium")),
+      status: FixtureStatus::Rescheduled {
+        original_date: 1_639_931_400,
+      },
+    }]),
+    format: CompetitionFormat::new(
+      1,
+      Some(CompetitionSplit::new(vec![2, 2], 1)),
+    )
+    .with_abandoned_match_rule(AbandonedMatchRule::ResultStands),
+  };
+  let document = write_interchange_tournament(&fetched_tournament);
+  assert_eq!(
+    parse(&document),
+    Ok(ImportedTournament {
+      matches_outcomes: fetched_tournament.matches_outcomes,
+      fixtures: fetched_tournament.fixtures,
+      format: CompetitionFormat::new(
+        1,
+        Some(CompetitionSplit::new(vec![2, 2], 1)),
+      )
+      .with_abandoned_match_rule(AbandonedMatchRule::ResultStands),
+    }),
+  );
+  let document_json: Value = serde_json::from_str(&document).unwrap();
+  assert_eq!(document_json["version"], 1);
+  assert_eq!(
+    document_json["teams"],
+    serde_json::json!(["A", "B", "C", "D"]),
+  );
+  assert_eq!(document_json["fixtures"][0]["status"], "rescheduled");
+
+  // NOTE: Both of openfootball's layouts (unplayed matches are fixtures).
+  let open_football_tournament = ImportedTournament {
+    matches_outcomes: vec![MatchOutcome {
+      home_team_name: team("Brentford FC"),
+      away_team_name: team("Arsenal FC"),
+      regulation_score: Some((2, 0)),
+      extra_time_score: None,
+      shootout_score: None,
+      status: MatchStatus::Finished,
+      date: Some(1_628_884_800),
+    }],
+    fixtures: Some(vec![Fixture {
+      home_team_name: team("Arsenal FC"),
+      away_team_name: team("Brentford FC"),
+      date: Some(1_644_710_400),
+      venue: None,
+      status: FixtureStatus::Scheduled,
+    }]),
+    format: CompetitionFormat::default(),
+  };
+  assert_eq!(
+    parse(
+      r#"{
+        "name": "English Premier League 2021/22",
+        "matches": [
+          {
+            "round": "Matchday 24",
+            "date": "2022-02-13",
+            "team1": "Arsenal FC",
+            "team2": "Brentford FC"
+          },
+          {
+            "round": "Matchday 1",
+            "date": "2021-08-13",
+            "time": "20:00 UTC+1",
+            "team1": "Brentford FC",
+            "team2": "Arsenal FC",
+            "score": {"ht": [1, 0], "ft": [2, 0]}
+          }
+        ]
+      }"#
+    ),
+    Ok(open_football_tournament),
+  );
+  assert_eq!(
+    parse(
+      r#"{
+        "name": "English Premier League 2021/22",
+        "rounds": [{
+          "name": "Matchday 1",
+          "matches": [{
+            "date": "2021-08-13",
+            "team1": {"key": "brentford", "name": "Brentford FC"},
+            "team2": {"key": "arsenal", "name": "Arsenal FC"},
+            "score1": 2,
+            "score2": 0,
+            "score1et": 3,
+            "score2et": 0
+          }]
+        }]
+      }"#
+    )
+    .unwrap()
+    .matches_outcomes[0]
+      .get_score(),
+    Some((3, 0)),
+  );
+
+  let get_document =
+    |version: &str, scoring: &str, format: &str, teams: &str| {
+      format!(
+        r#"{{
+        "version": {},
+        "name": "A League",
+        "teams": {},
+        "scoring": {},
+        "format": {},
+        "matches": [{{
+          "home_team": "A",
+          "away_team": "B",
+          "regulation_score": [1, 0],
+          "extra_time_score": null,
+          "shootout_score": null,
+          "status": "finished",
+          "date": null
+        }}]
+      }}"#,
+        version, teams, scoring, format,
+      )
+    };
+  let scoring = r#"{"win": 3, "draw": 1, "loss": 0}"#;
+  let format = r#"{
+    "matches_per_team_pair": 2,
+    "split": null,
+    "abandoned_match_rule": "replayed"
+  }"#;
+  let teams = r#"["A", "B"]"#;
+  assert_eq!(
+    parse(&get_document("1", scoring, format, teams))
+      .unwrap()
+      .fixtures,
+    None,
+  );
+  for invalid_document in [
+    get_document("2", scoring, format, teams),
+    get_document("1", r#"{"win": 2, "draw": 1, "loss": 0}"#, format, teams),
+    get_document(
+      "1",
+      scoring,
+      r#"{
+        "matches_per_team_pair": 5,
+        "split": null,
+        "abandoned_match_rule": "replayed"
+      }"#,
+      teams,
+    ),
+    get_document(
+      "1",
+      scoring,
+      r#"{
+        "matches_per_team_pair": 2,
+        "split": {"groups_sizes": [2], "matches_per_team_pair": 1},
+        "abandoned_match_rule": "replayed"
+      }"#,
+      teams,
+    ),
+    get_document("1", scoring, format, r#"["A"]"#),
+    get_document("1", scoring, format, r#"["A", "B", "C"]"#),
+    get_document("1", scoring, format, r#"["A", "B", "B"]"#),
+    String::from(
+      r#"{"matches": [{"date": "13/08/2021", "team1": "A", "team2": "B"}]}"#,
+    ),
+    String::from(r#"{"matches": [{"team1": "A", "team2": "A"}]}"#),
+    String::from("[]"),
+  ] {
+    assert!(
+      matches!(parse(&invalid_document), Err(FetchError::Parse { .. })),
+      "{}",
+      invalid_document,
+    );
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::test;
+
+  #[test]
+  fn test_tournament_fetching_interchange() {
+    test();
+  }
+}
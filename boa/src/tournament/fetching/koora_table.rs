@@ -0,0 +1,430 @@
+use std::string;
+use std::sync::Arc;
+
+use chrono::NaiveDate;
+use serde_json::Value;
+
+use crate::common::TeamId;
+use crate::tournament::fetching::common::FetchError;
+use crate::tournament::fetching::common::Fixture;
+use crate::tournament::fetching::common::FixtureStatus;
+use crate::tournament::fetching::common::MatchOutcome;
+use crate::tournament::fetching::common::MatchStatus;
+use crate::tournament::fetching::common::TournamentProvider;
+use crate::tournament::fetching::teams::TeamsRegistry;
+use crate::tournament::fetching::Koora;
+
+// NOTE: Each match is a row of cells and each response ends with a single
+// cell (`-1`) that isn't part of any row.
+const ROW_LENGTH: usize = 20;
+const END_CELL: i64 = -1;
+
+/// A match (a row of goalzz's `matches_list`) like:
+/// `[22551, 487, 0, 2282267, 1628698500, "", "16:15~$f", 1263, "w",
+/// "Al Fayha", "", "1|0", 145, "l", "Al Ittihad", "", "", "", "q",
+/// "w|1~s|0"]` which is its competition, stage, (unknown), id, date,
+/// (unknown), time (with `$f` when finished), home-team (id, outcome, name and
+/// events), score, away-team (same as home-team), 3 unknown cells and info.
+#[must_use]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct KooraMatch {
+  pub(super) competition_id: u64,
+  pub(super) stage_id: u64,
+  pub(super) match_id: u64,
+  // NOTE: A unix-timestamp.
+  pub(super) date: i64,
+  pub(super) is_finished: bool,
+  pub(super) home_team: KooraTeam,
+  pub(super) away_team: KooraTeam,
+  // NOTE: Only played matches have a score.
+  pub(super) score: Option<(usize, usize)>,
+  pub(super) round: Option<usize>,
+  pub(super) is_postponed: bool,
+  // NOTE: A unix-timestamp of rescheduled matches only.
+  pub(super) original_date: Option<i64>,
+  pub(super) venue: Option<String>,
+  // NOTE: Only for knockout matches (none of which are in the stored data).
+  pub(super) is_extra_time: bool,
+  pub(super) penalties: Option<(usize, usize)>,
+}
+
+#[must_use]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct KooraTeam {
+  pub(super) id: u64,
+  pub(super) name: TeamId,
+}
+
+impl KooraMatch {
+  // NOTE: Scores are at the end of extra-time (if played).
+  #[must_use]
+  pub(super) fn get_match_outcome(
+    &self,
+    teams_registry: &TeamsRegistry,
+  ) -> Option<MatchOutcome> {
+    self.score.map(|score| MatchOutcome {
+      home_team_name: self.home_team.resolve(teams_registry),
+      away_team_name: self.away_team.resolve(teams_registry),
+      regulation_score: (!self.is_extra_time).then_some(score),
+      extra_time_score: self.is_extra_time.then_some(score),
+      shootout_score: self.penalties,
+      status: MatchStatus::Finished,
+      date: Some(self.date),
+    })
+  }
+
+  #[must_use]
+  pub(super) fn get_fixture(
+    &self,
+    teams_registry: &TeamsRegistry,
+  ) -> Option<Fixture> {
+    if self.score.is_some() {
+      return None;
+    }
+
+    let status = match (self.original_date, self.is_postponed) {
+      (Some(original_date), _) => FixtureStatus::Rescheduled { original_date },
+      (None, true) => FixtureStatus::Postponed,
+      (None, false) => FixtureStatus::Scheduled,
+    };
+
+    Some(Fixture {
+      home_team_name: self.home_team.resolve(teams_registry),
+      away_team_name: self.away_team.resolve(teams_registry),
+      date: Some(self.date),
+      venue: self.venue.clone(),
+      status,
+    })
+  }
+}
+
+impl KooraTeam {
+  #[must_use]
+  fn resolve(&self, teams_registry: &TeamsRegistry) -> TeamId {
+    teams_registry.resolve(Koora::NAME, Some(self.id), &self.name)
+  }
+}
+
+/// Parses a goalzz response (`{"matches_list": [...]}`) rejecting it as a whole
+/// if any of its rows doesn't match the expected layout.
+pub(super) fn parse_koora_table(
+  response: &str,
+) -> Result<Vec<KooraMatch>, FetchError> {
+  use serde::Deserialize;
+
+  #[must_use]
+  #[derive(Deserialize)]
+  struct Table {
+    matches_list: Vec<Value>,
+  }
+
+  let table: Table = serde_json::from_str(response)?;
+  let cells = match table.matches_list.split_last() {
+    Some((end_cell, cells)) if end_cell.as_i64() == Some(END_CELL) => cells,
+    // NOTE: Assumed to be a month without matches.
+    None => &[],
+    Some((end_cell, _)) => {
+      return Err(FetchError::Parse {
+        message: format!("Invalid end-cell ({:?}).", end_cell),
+      })
+    },
+  };
+  if cells.len() % ROW_LENGTH != 0 {
+    return Err(FetchError::Parse {
+      message: format!("Invalid cells-count ({:?}).", cells.len()),
+    });
+  }
+
+  cells
+    .chunks_exact(ROW_LENGTH)
+    .enumerate()
+    .map(|(i, row)| {
+      parse_koora_row(row).map_err(|message| FetchError::Parse {
+        message: format!("Invalid row ({:?}): {}", i, message),
+      })
+    })
+    .collect()
+}
+
+#[allow(clippy::too_many_lines)]
+fn parse_koora_row(row: &[Value]) -> Result<KooraMatch, String> {
+  let get_id = |i: usize| {
+    row[i]
+      .as_u64()
+      .ok_or_else(|| format!("Invalid id ({:?}, {:?}).", i, row[i]))
+  };
+  let get_str = |i: usize| {
+    row[i]
+      .as_str()
+      .ok_or_else(|| format!("Invalid text ({:?}, {:?}).", i, row[i]))
+  };
+  let get_team = |id_index: usize| -> Result<KooraTeam, String> {
+    let name = get_str(id_index + 2)?;
+    if name.is_empty() {
+      return Err(format!("Empty team-name ({:?}).", id_index + 2));
+    }
+    // NOTE: Events (e.g., goals and cards) aren't needed but still validated.
+    get_str(id_index + 3)?;
+
+    Ok(KooraTeam {
+      id: get_id(id_index)?,
+      name: Arc::new(String::from(name)),
+    })
+  };
+  let parse_pair = |pair: &str| -> Result<(usize, usize), String> {
+    match pair.split('|').collect::<Vec<_>>()[..] {
+      [first, second] => first
+        .parse()
+        .and_then(|first| second.parse().map(|second| (first, second)))
+        .map_err(|_| format!("Invalid scores ({:?}).", pair)),
+      _ => Err(format!("Invalid scores ({:?}).", pair)),
+    }
+  };
+
+  // NOTE: Info looks like `w|19~s|1~dd|21/11/2021~a|257|SA|Name|` where `w` is
+  // the round, `s|1` marks a postponed match, `dd` the original date of a
+  // rescheduled one and `a` its venue. And extra-time and penalties are
+  // `et|1` and `pn|4|3`.
+  let info: Vec<&str> = get_str(19)?.split('~').collect();
+  let get_info = |key: &str| {
+    info.iter().find_map(|item| {
+      item
+        .strip_prefix(key)
+        .and_then(|item| item.strip_prefix('|'))
+    })
+  };
+
+  let score = match get_str(11)? {
+    "" => None,
+    score => Some(parse_pair(score)?),
+  };
+  let penalties = get_info("pn").map(parse_pair).transpose()?;
+  let original_date = get_info("dd")
+    .map(|original_date| {
+      NaiveDate::parse_from_str(original_date, "%d/%m/%Y")
+        .map(|original_date| {
+          // NOTE: The default date is the Unix epoch.
+          original_date
+            .signed_duration_since(NaiveDate::default())
+            .num_seconds()
+        })
+        .map_err(|_| format!("Invalid original-date ({:?}).", original_date))
+    })
+    .transpose()?;
+  let round = get_info("w")
+    .map(|round| {
+      round
+        .parse()
+        .map_err(|_| format!("Invalid round ({:?}).", round))
+    })
+    .transpose()?;
+
+  // NOTE: Outcomes are redundant but used to make sure scores are read from
+  // the right cells.
+  let expected_outcomes = match (score, penalties) {
+    (None, _) => ("", ""),
+    (Some((home_score, away_score)), penalties) => {
+      match home_score.cmp(&away_score).then_with(|| {
+        penalties
+          .map_or(std::cmp::Ordering::Equal, |(home, away)| home.cmp(&away))
+      }) {
+        std::cmp::Ordering::Greater => ("w", "l"),
+        std::cmp::Ordering::Less => ("l", "w"),
+        std::cmp::Ordering::Equal => ("t", "t"),
+      }
+    },
+  };
+  let outcomes = (get_str(8)?, get_str(13)?);
+  if outcomes != expected_outcomes {
+    return Err(format!(
+      "Invalid outcomes ({:?}, {:?}).",
+      outcomes, expected_outcomes,
+    ));
+  }
+  for i in [5, 16, 17, 18] {
+    get_str(i)?;
+  }
+  get_id(2)?;
+
+  Ok(KooraMatch {
+    competition_id: get_id(0)?,
+    stage_id: get_id(1)?,
+    match_id: get_id(3)?,
+    date: row[4]
+      .as_i64()
+      .ok_or_else(|| format!("Invalid date ({:?}).", row[4]))?,
+    is_finished: get_str(6)?.contains("$f"),
+    home_team: get_team(7)?,
+    away_team: get_team(12)?,
+    score,
+    round,
+    is_postponed: get_info("s") == Some("1"),
+    original_date,
+    venue: get_info("a").and_then(|venue| {
+      venue.split('|').nth(2).map(string::ToString::to_string)
+    }),
+    is_extra_time: get_info("et") == Some("1"),
+    penalties,
+  })
+}
+
+#[allow(clippy::too_many_lines)]
+pub(super) fn test() {
+  #[cfg(test)]
+  use pretty_assertions::assert_eq;
+
+  use crate::tournament::fetching::config::ProvidersConfig;
+
+  let teams_registry = ProvidersConfig::default().teams;
+  let (_, responses) = Koora::test_helper_download_tournaments().pop().unwrap();
+  let matches: Vec<KooraMatch> = responses
+    .iter()
+    .flat_map(|response| parse_koora_table(response).unwrap())
+    .collect();
+  assert_eq!(matches.len(), 200);
+  assert_eq!(
+    matches
+      .iter()
+      .filter(|koora_match| koora_match.score.is_some())
+      .count(),
+    156,
+  );
+  assert!(matches.iter().all(|koora_match| koora_match.competition_id
+    == 22551
+    && koora_match.is_finished == koora_match.score.is_some()
+    && !koora_match.is_extra_time
+    && koora_match.penalties.is_none()));
+  assert_eq!(
+    matches[0],
+    KooraMatch {
+      competition_id: 22551,
+      stage_id: 487,
+      match_id: 2_282_267,
+      date: 1_628_698_500,
+      is_finished: true,
+      home_team: KooraTeam {
+        id: 1263,
+        name: Arc::new(String::from("Al Fayha")),
+      },
+      away_team: KooraTeam {
+        id: 145,
+        name: Arc::new(String::from("Al Ittihad")),
+      },
+      score: Some((1, 0)),
+      round: Some(1),
+      is_postponed: false,
+      original_date: None,
+      venue: None,
+      is_extra_time: false,
+      penalties: None,
+    },
+  );
+  assert_eq!(
+    matches
+      .iter()
+      .filter_map(|koora_match| koora_match.get_fixture(&teams_registry))
+      .filter(|fixture| fixture.status == FixtureStatus::Postponed)
+      .count(),
+    3,
+  );
+
+  let get_table = |row: &str| format!(r#"{{"matches_list":[{},-1]}}"#, row);
+  let knockout_row = r#"1,2,0,3,1628698500,"","16:15~$f",10,"w","A","","1|1",20,"l","B","","","","q","w|1~s|0~et|1~pn|4|3""#;
+  let knockout_match = parse_koora_table(&get_table(knockout_row))
+    .unwrap()
+    .pop()
+    .unwrap();
+  assert!(knockout_match.is_extra_time);
+  assert_eq!(knockout_match.penalties, Some((4, 3)));
+  assert_eq!(
+    knockout_match.get_match_outcome(&teams_registry),
+    Some(MatchOutcome {
+      home_team_name: Arc::new(String::from("A")),
+      away_team_name: Arc::new(String::from("B")),
+      regulation_score: None,
+      extra_time_score: Some((1, 1)),
+      shootout_score: Some((4, 3)),
+      status: MatchStatus::Finished,
+      date: Some(1_628_698_500),
+    }),
+  );
+
+  let fixture_row = r#"1,2,0,4,1637452800,"","20:00",10,"","A","","",20,"","B","","","","q","w|2~s|1~dd|21/11/2021~a|257|SA|Stadium|""#;
+  assert_eq!(
+    parse_koora_table(&get_table(fixture_row))
+      .unwrap()
+      .pop()
+      .unwrap()
+      .get_fixture(&teams_registry),
+    Some(Fixture {
+      home_team_name: Arc::new(String::from("A")),
+      away_team_name: Arc::new(String::from("B")),
+      date: Some(1_637_452_800),
+      venue: Some(String::from("Stadium")),
+      status: FixtureStatus::Rescheduled {
+        original_date: 1_637_452_800,
+      },
+    }),
+  );
+
+  // NOTE: kooora.com uses the same ids but Arabic names.
+  let arabic_match = parse_koora_table(&get_table(
+    &knockout_row
+      .replacen("10,", "144,", 1)
+      .replace(r#""A""#, r#""الهلال""#),
+  ))
+  .unwrap()
+  .pop()
+  .unwrap();
+  assert_eq!(
+    arabic_match
+      .get_match_outcome(&teams_registry)
+      .map(|match_outcome| match_outcome.home_team_name),
+    Some(Arc::new(String::from("Al Hilal"))),
+  );
+
+  assert_eq!(parse_koora_table(r#"{"matches_list":[]}"#), Ok(vec![]));
+  assert_eq!(parse_koora_table(r#"{"matches_list":[-1]}"#), Ok(vec![]));
+  for invalid_table in [
+    String::from("not json"),
+    String::from(r#"{"matches_list":[1,2,3]}"#),
+    String::from(r#"{"matches_list":[1,2,3,-1]}"#),
+    // NOTE: The outcomes don't match the score.
+    get_table(&knockout_row.replace("~et|1~pn|4|3", "")),
+    // NOTE: A layout shifted by a cell.
+    get_table(
+      &knockout_row
+        .replacen("1,2,0,", "1,2,", 1)
+        .replace(r#""q","#, r#""q","","#),
+    ),
+    get_table(&knockout_row.replace(r#""A""#, r#""""#)),
+    get_table(&knockout_row.replace(r#""1|1""#, r#""1|x""#)),
+    get_table(&knockout_row.replace("16:15~$f", "16:15~$f\",0,\"")),
+    get_table(&fixture_row.replace("21/11/2021", "31/02/2021")),
+    get_table(&fixture_row.replace(r#""20:00""#, "0")),
+  ] {
+    assert!(
+      matches!(
+        parse_koora_table(&invalid_table),
+        Err(FetchError::Parse { .. })
+      ),
+      "Invalid table ({:?}).",
+      invalid_table,
+    );
+  }
+
+  // NOTE: Malformed fixtures fail their tournament instead of panicking.
+  let tournament_name = Arc::new(String::from("Koora League"));
+  assert!(matches!(
+    Koora::process_tournaments_fixtures(
+      &[(
+        Arc::clone(&tournament_name),
+        vec![get_table(&fixture_row.replace("21/11/2021", "21-11-2021"))],
+      )],
+      &teams_registry,
+    )
+    .as_slice(),
+    [(fetched_tournament_name, Err(FetchError::Parse { .. }))]
+      if *fetched_tournament_name == tournament_name,
+  ));
+}
@@ -0,0 +1,104 @@
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Write;
+use std::net::TcpListener;
+use std::net::TcpStream;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::thread;
+use std::thread::JoinHandle;
+
+/// A minimal HTTP server (bound to localhost) that responds to every request
+/// using a handler of the request's path (and query). Responses are `404` when
+/// the handler returns `None`.
+#[must_use]
+pub(super) struct MockServer {
+  pub(super) base_url: String,
+  requests_count: Arc<AtomicUsize>,
+  is_stopped: Arc<AtomicBool>,
+  handle: Option<JoinHandle<()>>,
+}
+
+impl MockServer {
+  /// # Panics
+  pub(super) fn new(
+    handler: impl Fn(&str) -> Option<String> + Send + 'static,
+  ) -> Self {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let requests_count = Arc::new(AtomicUsize::new(0));
+    let is_stopped = Arc::new(AtomicBool::new(false));
+
+    let handle = {
+      let requests_count = Arc::clone(&requests_count);
+      let is_stopped = Arc::clone(&is_stopped);
+
+      thread::spawn(move || {
+        for stream in listener.incoming() {
+          if is_stopped.load(Ordering::SeqCst) {
+            break;
+          }
+
+          requests_count.fetch_add(1, Ordering::SeqCst);
+          respond(stream.unwrap(), &handler);
+        }
+      })
+    };
+
+    Self {
+      base_url,
+      requests_count,
+      is_stopped,
+      handle: Some(handle),
+    }
+  }
+
+  #[must_use]
+  pub(super) fn requests_count(&self) -> usize {
+    self.requests_count.load(Ordering::SeqCst)
+  }
+}
+
+impl Drop for MockServer {
+  fn drop(&mut self) {
+    self.is_stopped.store(true, Ordering::SeqCst);
+    // NOTE: Accepting connections blocks so a last one is needed to stop.
+    let _ignored_stream =
+      TcpStream::connect(self.base_url.trim_start_matches("http://"));
+    if let Some(handle) = self.handle.take() {
+      handle.join().unwrap();
+    }
+  }
+}
+
+fn respond(mut stream: TcpStream, handler: &impl Fn(&str) -> Option<String>) {
+  let mut reader = BufReader::new(&stream);
+  let mut request_line = String::new();
+  reader.read_line(&mut request_line).unwrap();
+  // NOTE: Headers (and the empty line after them) are ignored.
+  loop {
+    let mut header_line = String::new();
+    if reader.read_line(&mut header_line).unwrap() == 0
+      || header_line.trim().is_empty()
+    {
+      break;
+    }
+  }
+
+  // NOTE: Looks like `GET /path?query HTTP/1.1`.
+  let path = request_line.split(' ').nth(1).unwrap_or_default();
+  let (status, body) =
+    handler(path).map_or((404, String::new()), |response| (200, response));
+
+  write!(
+    stream,
+    "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+    status,
+    if status == 200 { "OK" } else { "Not Found" },
+    body.len(),
+    body,
+  )
+  .unwrap();
+}
@@ -0,0 +1,442 @@
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::common::TeamId;
+use crate::tournament::fetching::common::FetchedTournament;
+use crate::tournament::fetching::common::MatchOutcome;
+
+/// A disagreement between providers fetching the same tournament. Only the
+/// primary provider's matches are used (with others only filling in its
+/// missing matches).
+#[must_use]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Discrepancy {
+  pub tournament_name: Arc<String>,
+  pub home_team_name: TeamId,
+  pub away_team_name: TeamId,
+  pub kind: DiscrepancyKind,
+}
+
+#[must_use]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DiscrepancyKind {
+  // NOTE: Scores are the final ones (`None` for abandoned matches that were
+  // never scored).
+  ScoreConflict {
+    primary_provider_name: &'static str,
+    primary_score: Option<(usize, usize)>,
+    provider_name: &'static str,
+    score: Option<(usize, usize)>,
+  },
+  // NOTE: The provider doesn't have a match that another provider has.
+  MissingMatch {
+    provider_name: &'static str,
+  },
+  // NOTE: The provider changed a match's result since it was last synced
+  // (`None` if it's gone).
+  CorrectedResult {
+    provider_name: &'static str,
+    previous_score: Option<(usize, usize)>,
+    score: Option<(usize, usize)>,
+  },
+}
+
+impl fmt::Display for Discrepancy {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{} vs {}: ", self.home_team_name, self.away_team_name)?;
+
+    match &self.kind {
+      DiscrepancyKind::ScoreConflict {
+        primary_provider_name,
+        primary_score,
+        provider_name,
+        score,
+      } => write!(
+        f,
+        "score ({:?}) conflicts with {} ({:?}) and {} is used",
+        primary_score, provider_name, score, primary_provider_name,
+      ),
+      DiscrepancyKind::MissingMatch { provider_name } => {
+        write!(f, "missing from {}", provider_name)
+      },
+      DiscrepancyKind::CorrectedResult {
+        provider_name,
+        previous_score,
+        score,
+      } => write!(
+        f,
+        "score ({:?}) was corrected by {} ({:?})",
+        previous_score, provider_name, score,
+      ),
+    }
+  }
+}
+
+// NOTE: A match is identified by its teams (in order) and how many times they
+// met before (as teams can meet more than once at the same home).
+type MatchKey = (TeamId, TeamId, usize);
+
+/// Merges tournaments fetched by more than one provider into a single one
+/// (per tournament) preferring the configured primary provider (or the first
+/// one if the primary one wasn't fetched).
+#[must_use]
+pub(super) fn reconcile_tournaments(
+  fetched_tournaments: Vec<FetchedTournament>,
+  primary_providers: &BTreeMap<Arc<String>, String>,
+) -> (Vec<FetchedTournament>, Vec<Discrepancy>) {
+  let mut all_tournaments_fetched: Vec<(Arc<String>, Vec<FetchedTournament>)> =
+    vec![];
+  for fetched_tournament in fetched_tournaments {
+    match all_tournaments_fetched
+      .iter_mut()
+      .find(|(tournament_name, _)| *tournament_name == fetched_tournament.name)
+    {
+      Some((_, tournament_fetched)) => {
+        tournament_fetched.push(fetched_tournament);
+      },
+      None => all_tournaments_fetched.push((
+        Arc::clone(&fetched_tournament.name),
+        vec![fetched_tournament],
+      )),
+    }
+  }
+
+  let mut discrepancies = vec![];
+  let reconciled_tournaments = all_tournaments_fetched
+    .into_iter()
+    .map(|(tournament_name, mut tournament_fetched)| {
+      let primary_index = primary_providers
+        .get(&tournament_name)
+        .and_then(|primary_provider_name| {
+          tournament_fetched.iter().position(|fetched_tournament| {
+            fetched_tournament.provider_name == primary_provider_name
+          })
+        })
+        .unwrap_or(0);
+      let primary = tournament_fetched.remove(primary_index);
+
+      reconcile_tournament(primary, &tournament_fetched, &mut discrepancies)
+    })
+    .collect();
+
+  (reconciled_tournaments, discrepancies)
+}
+
+#[must_use]
+fn reconcile_tournament(
+  mut primary: FetchedTournament,
+  others: &[FetchedTournament],
+  discrepancies: &mut Vec<Discrepancy>,
+) -> FetchedTournament {
+  let mut add_discrepancy =
+    |(home_team_name, away_team_name, _): &MatchKey, kind: DiscrepancyKind| {
+      discrepancies.push(Discrepancy {
+        tournament_name: Arc::clone(&primary.name),
+        home_team_name: Arc::clone(home_team_name),
+        away_team_name: Arc::clone(away_team_name),
+        kind,
+      });
+    };
+
+  let primary_matches = get_keyed_matches(&primary.matches_outcomes);
+  let mut added_matches: BTreeMap<MatchKey, MatchOutcome> = BTreeMap::new();
+  for other in others {
+    let other_matches = get_keyed_matches(&other.matches_outcomes);
+
+    for match_key in primary_matches.keys() {
+      if !other_matches.contains_key(match_key) {
+        add_discrepancy(
+          match_key,
+          DiscrepancyKind::MissingMatch {
+            provider_name: other.provider_name,
+          },
+        );
+      }
+    }
+    for (match_key, other_match) in other_matches {
+      match primary_matches.get(&match_key) {
+        Some(primary_match)
+          if primary_match.get_score() != other_match.get_score() =>
+        {
+          add_discrepancy(
+            &match_key,
+            DiscrepancyKind::ScoreConflict {
+              primary_provider_name: primary.provider_name,
+              primary_score: primary_match.get_score(),
+              provider_name: other.provider_name,
+              score: other_match.get_score(),
+            },
+          );
+        },
+        Some(_) => {},
+        None => {
+          if let Entry::Vacant(entry) = added_matches.entry(match_key) {
+            add_discrepancy(
+              entry.key(),
+              DiscrepancyKind::MissingMatch {
+                provider_name: primary.provider_name,
+              },
+            );
+            entry.insert(other_match.clone());
+          }
+        },
+      }
+    }
+  }
+
+  if added_matches.is_empty() {
+    return primary;
+  }
+
+  // NOTE: Added matches were played so they're no longer fixtures. Only the
+  // primary provider's fixtures are used as others' can be outdated.
+  for added_match in added_matches.values() {
+    if let Some(fixtures) = &mut primary.fixtures {
+      if let Some(index) = fixtures.iter().position(|fixture| {
+        fixture.home_team_name == added_match.home_team_name
+          && fixture.away_team_name == added_match.away_team_name
+      }) {
+        let _removed_fixture = fixtures.remove(index);
+      }
+    }
+  }
+  primary.matches_outcomes.extend(added_matches.into_values());
+  // NOTE: Stable so that matches on the same date keep their order.
+  primary
+    .matches_outcomes
+    .sort_by_key(|match_outcome| match_outcome.date);
+
+  primary
+}
+
+#[must_use]
+fn get_keyed_matches(
+  matches_outcomes: &[MatchOutcome],
+) -> BTreeMap<MatchKey, &MatchOutcome> {
+  let mut meetings_counts: HashMap<(&TeamId, &TeamId), usize> = HashMap::new();
+
+  matches_outcomes
+    .iter()
+    .map(|match_outcome| {
+      let meetings_count = meetings_counts
+        .entry((&match_outcome.home_team_name, &match_outcome.away_team_name))
+        .or_default();
+      *meetings_count += 1;
+
+      (
+        (
+          Arc::clone(&match_outcome.home_team_name),
+          Arc::clone(&match_outcome.away_team_name),
+          *meetings_count,
+        ),
+        match_outcome,
+      )
+    })
+    .collect()
+}
+
+#[allow(clippy::too_many_lines)]
+pub(super) fn test() {
+  #[cfg(test)]
+  use pretty_assertions::assert_eq;
+
+  use crate::common::CompetitionFormat;
+  use crate::tournament::fetching::common::Fixture;
+  use crate::tournament::fetching::common::FixtureStatus;
+  use crate::tournament::fetching::common::MatchStatus;
+
+  let tournament_name = Arc::new(String::from("dummy-tournament"));
+  let get_match_outcome =
+    |home_team_name: &str, away_team_name: &str, score, date| MatchOutcome {
+      home_team_name: Arc::new(String::from(home_team_name)),
+      away_team_name: Arc::new(String::from(away_team_name)),
+      regulation_score: Some(score),
+      extra_time_score: None,
+      shootout_score: None,
+      status: MatchStatus::Finished,
+      date: Some(date),
+    };
+  let get_fetched_tournament =
+    |provider_name: &'static str,
+     matches_outcomes: Vec<MatchOutcome>,
+     fixtures: Option<Vec<Fixture>>| FetchedTournament {
+      name: Arc::clone(&tournament_name),
+      provider_name,
+      matches_outcomes,
+      fixtures,
+      format: CompetitionFormat::default(),
+    };
+  let get_fixture = |home_team_name: &str, away_team_name: &str| Fixture {
+    home_team_name: Arc::new(String::from(home_team_name)),
+    away_team_name: Arc::new(String::from(away_team_name)),
+    date: None,
+    venue: None,
+    status: FixtureStatus::Scheduled,
+  };
+  let get_discrepancy =
+    |home_team_name: &str, away_team_name: &str, kind| Discrepancy {
+      tournament_name: Arc::clone(&tournament_name),
+      home_team_name: Arc::new(String::from(home_team_name)),
+      away_team_name: Arc::new(String::from(away_team_name)),
+      kind,
+    };
+  let get_fetched_tournaments = || {
+    vec![
+      get_fetched_tournament(
+        "A",
+        vec![
+          get_match_outcome("a", "b", (1, 0), 1),
+          get_match_outcome("c", "d", (2, 2), 3),
+        ],
+        Some(vec![get_fixture("b", "c"), get_fixture("a", "c")]),
+      ),
+      get_fetched_tournament(
+        "B",
+        vec![
+          get_match_outcome("a", "b", (1, 0), 1),
+          get_match_outcome("b", "c", (0, 1), 2),
+          get_match_outcome("c", "d", (2, 1), 3),
+        ],
+        None,
+      ),
+    ]
+  };
+
+  // NOTE: A tournament of a single provider is used as is.
+  let (reconciled_tournaments, discrepancies) = reconcile_tournaments(
+    vec![get_fetched_tournament(
+      "B",
+      vec![get_match_outcome("a", "b", (1, 0), 1)],
+      None,
+    )],
+    &BTreeMap::new(),
+  );
+  assert_eq!(reconciled_tournaments.len(), 1);
+  assert_eq!(reconciled_tournaments[0].provider_name, "B");
+  assert_eq!(discrepancies, vec![]);
+
+  let (reconciled_tournaments, discrepancies) =
+    reconcile_tournaments(get_fetched_tournaments(), &BTreeMap::new());
+  assert_eq!(reconciled_tournaments.len(), 1);
+  let reconciled_tournament = &reconciled_tournaments[0];
+  assert_eq!(reconciled_tournament.provider_name, "A");
+  assert_eq!(
+    reconciled_tournament.matches_outcomes,
+    vec![
+      get_match_outcome("a", "b", (1, 0), 1),
+      get_match_outcome("b", "c", (0, 1), 2),
+      get_match_outcome("c", "d", (2, 2), 3),
+    ],
+  );
+  assert_eq!(
+    reconciled_tournament.fixtures,
+    Some(vec![get_fixture("a", "c")]),
+  );
+  assert_eq!(
+    discrepancies,
+    vec![
+      get_discrepancy(
+        "b",
+        "c",
+        DiscrepancyKind::MissingMatch { provider_name: "A" },
+      ),
+      get_discrepancy(
+        "c",
+        "d",
+        DiscrepancyKind::ScoreConflict {
+          primary_provider_name: "A",
+          primary_score: Some((2, 2)),
+          provider_name: "B",
+          score: Some((2, 1)),
+        },
+      ),
+    ],
+  );
+  assert_eq!(
+    discrepancies[1].to_string(),
+    "c vs d: score (Some((2, 2))) conflicts with B (Some((2, 1))) and A is used",
+  );
+
+  let (reconciled_tournaments, discrepancies) = reconcile_tournaments(
+    get_fetched_tournaments(),
+    &BTreeMap::from([(Arc::clone(&tournament_name), String::from("B"))]),
+  );
+  assert_eq!(reconciled_tournaments[0].provider_name, "B");
+  assert_eq!(reconciled_tournaments[0].matches_outcomes.len(), 3);
+  assert_eq!(reconciled_tournaments[0].fixtures, None);
+  assert_eq!(
+    discrepancies,
+    vec![
+      get_discrepancy(
+        "b",
+        "c",
+        DiscrepancyKind::MissingMatch { provider_name: "A" },
+      ),
+      get_discrepancy(
+        "c",
+        "d",
+        DiscrepancyKind::ScoreConflict {
+          primary_provider_name: "B",
+          primary_score: Some((2, 1)),
+          provider_name: "A",
+          score: Some((2, 2)),
+        },
+      ),
+    ],
+  );
+  assert_eq!(discrepancies[0].to_string(), "b vs c: missing from A");
+  assert_eq!(
+    Discrepancy {
+      kind: DiscrepancyKind::CorrectedResult {
+        provider_name: "A",
+        previous_score: Some((1, 0)),
+        score: None,
+      },
+      ..discrepancies[0].clone()
+    }
+    .to_string(),
+    "b vs c: score (Some((1, 0))) was corrected by A (None)",
+  );
+
+  // NOTE: The same teams meeting again (at the same home) is another match.
+  let (reconciled_tournaments, discrepancies) = reconcile_tournaments(
+    vec![
+      get_fetched_tournament(
+        "A",
+        vec![get_match_outcome("a", "b", (1, 0), 1)],
+        None,
+      ),
+      get_fetched_tournament(
+        "B",
+        vec![
+          get_match_outcome("a", "b", (1, 0), 1),
+          get_match_outcome("a", "b", (3, 3), 2),
+        ],
+        None,
+      ),
+    ],
+    &BTreeMap::new(),
+  );
+  assert_eq!(reconciled_tournaments[0].matches_outcomes.len(), 2);
+  assert_eq!(
+    discrepancies,
+    vec![get_discrepancy(
+      "a",
+      "b",
+      DiscrepancyKind::MissingMatch { provider_name: "A" },
+    )],
+  );
+}
+
+#[cfg(test)]
+mod tests {
+  use super::test;
+
+  #[test]
+  fn test_tournament_fetching_reconciliation() {
+    test();
+  }
+}
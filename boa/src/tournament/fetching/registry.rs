@@ -0,0 +1,402 @@
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use crate::common::CompetitionFormat;
+use crate::tournament::fetching::common::FetchFailure;
+use crate::tournament::fetching::common::FetchedTournament;
+use crate::tournament::fetching::common::TournamentProvider;
+use crate::tournament::fetching::config::ProvidersConfig;
+use crate::tournament::fetching::transport::Transport;
+use crate::tournament::fetching::FootballData;
+use crate::tournament::fetching::JsonFile;
+use crate::tournament::fetching::Koora;
+use crate::tournament::fetching::PremierLeague;
+
+/// A provider of tournaments that (unlike `TournamentProvider`) is an instance
+/// so that it can be configured at runtime and registered along with others.
+///
+/// Apps can register their own (see `register_provider`).
+pub trait Provider: Send + Sync {
+  #[must_use]
+  fn name(&self) -> &'static str;
+  // NOTE: Requests are limited per origin (see `FetchPolicy`).
+  #[must_use]
+  fn origin(&self) -> &'static str;
+  // NOTE: See `TournamentProvider::IS_INCREMENTAL`.
+  #[must_use]
+  fn is_incremental(&self) -> bool {
+    false
+  }
+  // NOTE: Each tournament is fetched independently (e.g., concurrently).
+  #[must_use]
+  fn tournaments_names(
+    &self,
+    providers_config: &ProvidersConfig,
+  ) -> Vec<Arc<String>>;
+  // NOTE: Only fetches the given tournaments (of those of the config) and
+  // returns them before they're built (see
+  // `TournamentProvider::fetch_tournaments_outcomes()`).
+  #[must_use]
+  fn fetch_tournaments_outcomes(
+    &self,
+    transport: &dyn Transport,
+    providers_config: &ProvidersConfig,
+    tournaments_names: &[Arc<String>],
+  ) -> (Vec<FetchedTournament>, Vec<FetchFailure>);
+}
+
+/// A built-in provider configured with its base URL (its default one unless
+/// it's pointed at another server or directory).
+#[must_use]
+pub(super) struct StaticProvider<P: TournamentProvider> {
+  base_url: String,
+  // NOTE: A function pointer so that it's `Send` and `Sync` regardless of `P`.
+  provider: PhantomData<fn() -> P>,
+}
+
+impl<P: TournamentProvider> StaticProvider<P> {
+  pub(super) fn new(base_url: &str) -> Self {
+    Self {
+      base_url: String::from(base_url),
+      provider: PhantomData,
+    }
+  }
+}
+
+impl<P: TournamentProvider> Default for StaticProvider<P> {
+  fn default() -> Self {
+    Self::new(P::BASE_URL)
+  }
+}
+
+impl<P: TournamentProvider> Provider for StaticProvider<P> {
+  fn name(&self) -> &'static str {
+    P::NAME
+  }
+
+  fn origin(&self) -> &'static str {
+    P::ORIGIN
+  }
+
+  fn is_incremental(&self) -> bool {
+    P::IS_INCREMENTAL
+  }
+
+  fn tournaments_names(
+    &self,
+    providers_config: &ProvidersConfig,
+  ) -> Vec<Arc<String>> {
+    P::tournaments_names(providers_config)
+  }
+
+  fn fetch_tournaments_outcomes(
+    &self,
+    transport: &dyn Transport,
+    providers_config: &ProvidersConfig,
+    tournaments_names: &[Arc<String>],
+  ) -> (Vec<FetchedTournament>, Vec<FetchFailure>) {
+    P::fetch_tournaments_outcomes(
+      transport,
+      &self.base_url,
+      &providers_config.with_tournaments(tournaments_names),
+    )
+  }
+}
+
+/// Providers (by name) in the order they're fetched in (which is the order of
+/// their tournaments before they're reconciled). Only enabled ones are
+/// fetched.
+#[must_use]
+#[derive(Clone)]
+pub(crate) struct ProvidersRegistry {
+  providers: Vec<(Arc<dyn Provider>, bool)>,
+}
+
+impl Default for ProvidersRegistry {
+  fn default() -> Self {
+    let mut providers_registry = Self { providers: vec![] };
+    for provider in [
+      Arc::new(StaticProvider::<Koora>::default()) as Arc<dyn Provider>,
+      Arc::new(StaticProvider::<PremierLeague>::default()),
+      Arc::new(StaticProvider::<FootballData>::default()),
+      Arc::new(StaticProvider::<JsonFile>::default()),
+    ] {
+      assert!(
+        providers_registry.register(provider),
+        "Duplicate built-in providers.",
+      );
+    }
+
+    providers_registry
+  }
+}
+
+// NOTE: Providers are compared (and shown) by their names as they can't be
+// otherwise.
+impl PartialEq for ProvidersRegistry {
+  fn eq(&self, other: &Self) -> bool {
+    self.get_providers() == other.get_providers()
+  }
+}
+
+impl Eq for ProvidersRegistry {}
+
+impl fmt::Debug for ProvidersRegistry {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_map().entries(self.get_providers()).finish()
+  }
+}
+
+impl ProvidersRegistry {
+  /// Registers a provider (enabled) after the others.
+  ///
+  /// Returns `false` (and keeps the registered one) if a provider with the
+  /// same name is already registered.
+  #[must_use]
+  pub(crate) fn register(&mut self, provider: Arc<dyn Provider>) -> bool {
+    if self.is_registered(provider.name()) {
+      return false;
+    }
+    self.providers.push((provider, true));
+
+    true
+  }
+
+  /// Returns `false` if there's no provider with that name.
+  #[must_use]
+  pub(crate) fn set_enabled(
+    &mut self,
+    provider_name: &str,
+    is_enabled: bool,
+  ) -> bool {
+    match self
+      .providers
+      .iter_mut()
+      .find(|(provider, _)| provider.name() == provider_name)
+    {
+      Some((_, is_provider_enabled)) => {
+        *is_provider_enabled = is_enabled;
+
+        true
+      },
+      None => false,
+    }
+  }
+
+  #[must_use]
+  fn is_registered(&self, provider_name: &str) -> bool {
+    self
+      .providers
+      .iter()
+      .any(|(provider, _)| provider.name() == provider_name)
+  }
+
+  // NOTE: By name with whether each is enabled.
+  #[must_use]
+  pub(crate) fn get_providers(&self) -> Vec<(&'static str, bool)> {
+    self
+      .providers
+      .iter()
+      .map(|(provider, is_enabled)| (provider.name(), *is_enabled))
+      .collect()
+  }
+
+  #[must_use]
+  pub(super) fn get_enabled_providers(&self) -> Vec<Arc<dyn Provider>> {
+    self
+      .providers
+      .iter()
+      .filter(|(_, is_enabled)| *is_enabled)
+      .map(|(provider, _)| Arc::clone(provider))
+      .collect()
+  }
+}
+
+#[allow(clippy::too_many_lines)]
+pub(super) fn test() {
+  #[cfg(test)]
+  use pretty_assertions::assert_eq;
+
+  use std::path::Path;
+
+  use crate::tournament::fetching::common::FetchError;
+  use crate::tournament::fetching::config::FileCompetition;
+  use crate::tournament::fetching::transport::HttpTransport;
+
+  // NOTE: A provider that isn't built-in (i.e., isn't a `TournamentProvider`)
+  // fetches a single empty tournament of its own.
+  struct StubProvider {
+    tournament_name: Arc<String>,
+  }
+  impl Provider for StubProvider {
+    fn name(&self) -> &'static str {
+      "Stub"
+    }
+
+    fn origin(&self) -> &'static str {
+      "http://localhost"
+    }
+
+    fn tournaments_names(
+      &self,
+      _providers_config: &ProvidersConfig,
+    ) -> Vec<Arc<String>> {
+      vec![Arc::clone(&self.tournament_name)]
+    }
+
+    fn fetch_tournaments_outcomes(
+      &self,
+      _transport: &dyn Transport,
+      _providers_config: &ProvidersConfig,
+      tournaments_names: &[Arc<String>],
+    ) -> (Vec<FetchedTournament>, Vec<FetchFailure>) {
+      (
+        tournaments_names
+          .iter()
+          .map(|tournament_name| FetchedTournament {
+            name: Arc::clone(tournament_name),
+            provider_name: self.name(),
+            matches_outcomes: vec![],
+            fixtures: None,
+            format: CompetitionFormat::default(),
+          })
+          .collect(),
+        vec![],
+      )
+    }
+  }
+
+  let mut providers_registry = ProvidersRegistry::default();
+  assert_eq!(
+    providers_registry.get_providers(),
+    vec![
+      (Koora::NAME, true),
+      (PremierLeague::NAME, true),
+      (FootballData::NAME, true),
+      (JsonFile::NAME, true),
+    ],
+  );
+  assert_eq!(providers_registry, ProvidersRegistry::default());
+
+  let stub_provider = Arc::new(StubProvider {
+    tournament_name: Arc::new(String::from("Stub League")),
+  });
+  assert!(providers_registry.register(stub_provider.clone()));
+  assert!(!providers_registry.register(stub_provider));
+  assert!(providers_registry.set_enabled(Koora::NAME, false));
+  assert!(!providers_registry.set_enabled("Unknown", false));
+  assert_ne!(providers_registry, ProvidersRegistry::default());
+  assert_eq!(
+    providers_registry
+      .get_enabled_providers()
+      .iter()
+      .map(|provider| provider.name())
+      .collect::<Vec<_>>(),
+    vec![
+      PremierLeague::NAME,
+      FootballData::NAME,
+      JsonFile::NAME,
+      "Stub"
+    ],
+  );
+
+  let providers_config = ProvidersConfig::default();
+  let stub_provider = providers_registry.get_enabled_providers().pop().unwrap();
+  let tournaments_names = stub_provider.tournaments_names(&providers_config);
+  let (fetched_tournaments, failures) = stub_provider
+    .fetch_tournaments_outcomes(
+      &HttpTransport::new(stub_provider.origin()),
+      &providers_config,
+      &tournaments_names,
+    );
+  assert_eq!(
+    (
+      fetched_tournaments
+        .iter()
+        .map(|fetched_tournament| fetched_tournament.name.as_str())
+        .collect::<Vec<_>>(),
+      failures,
+    ),
+    (vec!["Stub League"], vec![]),
+  );
+
+  // NOTE: Built-in providers are configured with their base URL (a directory
+  // for files) and only fetch the given tournaments.
+  let missing_tournament_name = Arc::new(String::from("Missing"));
+  let providers_config = ProvidersConfig {
+    json: vec![
+      FileCompetition {
+        name: Arc::new(String::from(JsonFile::TEST_TOURNAMENT_NAME)),
+        path: Path::new(&format!(
+          "{}-{}",
+          JsonFile::TEST_DATA_PREFIX,
+          JsonFile::TEST_DATA_FILE_ID,
+        ))
+        .to_path_buf(),
+      },
+      FileCompetition {
+        name: Arc::clone(&missing_tournament_name),
+        path: Path::new("missing.json").to_path_buf(),
+      },
+    ],
+    ..ProvidersConfig::default()
+  };
+  let json_provider = StaticProvider::<JsonFile>::new("data");
+  let tournaments_names = json_provider.tournaments_names(&providers_config);
+  assert_eq!(tournaments_names.len(), 2);
+  let fetch = |tournaments_names: &[Arc<String>]| {
+    let (fetched_tournaments, failures) = json_provider
+      .fetch_tournaments_outcomes(
+        &HttpTransport::new(json_provider.origin()),
+        &providers_config,
+        tournaments_names,
+      );
+
+    (
+      fetched_tournaments
+        .into_iter()
+        .map(|fetched_tournament| fetched_tournament.name)
+        .collect::<Vec<_>>(),
+      failures
+        .into_iter()
+        .map(|failure| (failure.tournament_name, failure.error))
+        .collect::<Vec<_>>(),
+    )
+  };
+  assert_eq!(
+    fetch(&tournaments_names[..1]),
+    (vec![Arc::clone(&tournaments_names[0])], vec![]),
+  );
+  assert!(matches!(
+    fetch(&tournaments_names[1..]),
+    (fetched_tournaments_names, failures)
+      if fetched_tournaments_names.is_empty()
+        && matches!(
+          failures.as_slice(),
+          [(tournament_name, FetchError::Io { .. })]
+            if *tournament_name == missing_tournament_name,
+        ),
+  ));
+  assert_eq!(
+    StaticProvider::<JsonFile>::default()
+      .fetch_tournaments_outcomes(
+        &HttpTransport::new(JsonFile::ORIGIN),
+        &providers_config,
+        &tournaments_names[..1],
+      )
+      .1
+      .len(),
+    1,
+  );
+}
+
+#[cfg(test)]
+mod tests {
+  use super::test;
+
+  #[test]
+  fn test_tournament_fetching_registry() {
+    test();
+  }
+}
@@ -0,0 +1,442 @@
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::tournament::fetching::cache::to_file_name;
+use crate::tournament::fetching::common::FetchedTournament;
+use crate::tournament::fetching::common::MatchOutcome;
+use crate::tournament::fetching::interchange::parse_interchange_tournament;
+use crate::tournament::fetching::interchange::write_interchange_tournament;
+use crate::tournament::fetching::interchange::ImportedTournament;
+use crate::tournament::fetching::reconciliation::Discrepancy;
+use crate::tournament::fetching::reconciliation::DiscrepancyKind;
+use crate::tournament::fetching::teams::TeamsRegistry;
+
+const SYNC_DIR_NAME: &str = "results";
+// NOTE: Results are usually corrected within days (e.g., awarded after an
+// appeal) so the recent ones are fetched again anyway.
+const OVERLAP_SECONDS: i64 = 14 * 24 * 60 * 60;
+// NOTE: Older corrections are only detected by fetching everything again.
+const FULL_SYNC_INTERVAL_SECONDS: i64 = 7 * 24 * 60 * 60;
+
+#[must_use]
+#[derive(Deserialize, Serialize)]
+struct StoredTournament {
+  // NOTE: A unix-timestamp of when it was last fetched as a whole.
+  full_synced_at: i64,
+  // NOTE: In the interchange format (with canonical teams' names).
+  document: String,
+}
+
+/// A tournament as of its last sync and (unless it has to be fetched as a
+/// whole) the unix-timestamp from which it's fetched again.
+#[must_use]
+pub(super) struct SyncedTournament {
+  full_synced_at: i64,
+  tournament: ImportedTournament,
+  pub(super) since: Option<i64>,
+}
+
+/// Stores tournaments (by provider and tournament) as they were last fetched
+/// so that only their newer matches are fetched and merged into them. Results
+/// that changed since they were stored are corrections.
+#[must_use]
+pub(crate) struct ResultsStore {
+  provider_name: &'static str,
+  provider_dir: PathBuf,
+  now: i64,
+}
+
+impl ResultsStore {
+  pub(crate) fn new(
+    provider_name: &'static str,
+    cache_dir: &Path,
+    now: i64,
+  ) -> Self {
+    Self {
+      provider_name,
+      provider_dir: cache_dir
+        .join(SYNC_DIR_NAME)
+        .join(to_file_name(provider_name)),
+      now,
+    }
+  }
+
+  // NOTE: A missing or corrupt tournament only costs fetching it as a whole.
+  #[must_use]
+  pub(super) fn read(
+    &self,
+    tournament_name: &Arc<String>,
+  ) -> Option<SyncedTournament> {
+    let stored_tournament: StoredTournament = serde_json::from_str(
+      &fs::read_to_string(self.get_path(tournament_name)).ok()?,
+    )
+    .ok()?;
+    // NOTE: Teams' names are stored canonical so they aren't resolved again.
+    let tournament = parse_interchange_tournament(
+      &stored_tournament.document,
+      &TeamsRegistry::default(),
+    )
+    .ok()?;
+    let since = (self.now - stored_tournament.full_synced_at
+      < FULL_SYNC_INTERVAL_SECONDS)
+      .then(|| get_since(&tournament))
+      .flatten();
+
+    Some(SyncedTournament {
+      full_synced_at: stored_tournament.full_synced_at,
+      tournament,
+      since,
+    })
+  }
+
+  /// Merges a fetched tournament (from its synced one's `since` on) into its
+  /// synced one (if any) and stores it. Synced results that were fetched again
+  /// but changed (or are missing) are returned as corrections.
+  /// # Panics
+  pub(super) fn merge(
+    &self,
+    fetched_tournament: FetchedTournament,
+    synced_tournament: Option<SyncedTournament>,
+  ) -> (FetchedTournament, Vec<Discrepancy>) {
+    let since = synced_tournament
+      .as_ref()
+      .and_then(|synced_tournament| synced_tournament.since);
+    let is_fetched_again = |date: Option<i64>| match (since, date) {
+      (Some(since), Some(date)) => date >= since,
+      (Some(_), None) => false,
+      (None, _) => true,
+    };
+
+    let (full_synced_at, synced_matches_outcomes, synced_fixtures) =
+      match synced_tournament {
+        Some(SyncedTournament {
+          full_synced_at,
+          tournament,
+          since: Some(_),
+        }) => (
+          full_synced_at,
+          tournament.matches_outcomes,
+          tournament.fixtures.unwrap_or_default(),
+        ),
+        Some(SyncedTournament { tournament, .. }) => {
+          (self.now, tournament.matches_outcomes, vec![])
+        },
+        None => (self.now, vec![], vec![]),
+      };
+    let (previous_matches_outcomes, kept_matches_outcomes): (Vec<_>, Vec<_>) =
+      synced_matches_outcomes
+        .into_iter()
+        .partition(|match_outcome| is_fetched_again(match_outcome.date));
+    let matches_outcomes: Vec<MatchOutcome> = fetched_tournament
+      .matches_outcomes
+      .into_iter()
+      .filter(|match_outcome| is_fetched_again(match_outcome.date))
+      .collect();
+
+    let corrections = previous_matches_outcomes
+      .iter()
+      .filter_map(|previous_match_outcome| {
+        let match_outcome = matches_outcomes.iter().find(|match_outcome| {
+          match_outcome.home_team_name == previous_match_outcome.home_team_name
+            && match_outcome.away_team_name
+              == previous_match_outcome.away_team_name
+            && match_outcome.date == previous_match_outcome.date
+        });
+        if matches!(
+          match_outcome,
+          Some(match_outcome)
+            if match_outcome.get_score() == previous_match_outcome.get_score()
+              && match_outcome.status == previous_match_outcome.status
+        ) {
+          return None;
+        }
+
+        Some(Discrepancy {
+          tournament_name: Arc::clone(&fetched_tournament.name),
+          home_team_name: Arc::clone(&previous_match_outcome.home_team_name),
+          away_team_name: Arc::clone(&previous_match_outcome.away_team_name),
+          kind: DiscrepancyKind::CorrectedResult {
+            provider_name: self.provider_name,
+            previous_score: previous_match_outcome.get_score(),
+            score: match_outcome.and_then(MatchOutcome::get_score),
+          },
+        })
+      })
+      .collect();
+
+    let merged_tournament = FetchedTournament {
+      matches_outcomes: kept_matches_outcomes
+        .into_iter()
+        .chain(matches_outcomes)
+        .collect(),
+      fixtures: fetched_tournament.fixtures.map(|fixtures| {
+        synced_fixtures
+          .into_iter()
+          .filter(|fixture| !is_fetched_again(fixture.date))
+          .chain(
+            fixtures
+              .into_iter()
+              .filter(|fixture| is_fetched_again(fixture.date)),
+          )
+          .collect()
+      }),
+      ..fetched_tournament
+    };
+
+    // NOTE: Failing to store only costs fetching it as a whole next time.
+    let path = self.get_path(&merged_tournament.name);
+    let _ignored_result = path
+      .parent()
+      .map_or(Ok(()), fs::create_dir_all)
+      .and_then(|()| {
+        fs::write(
+          &path,
+          serde_json::to_string(&StoredTournament {
+            full_synced_at,
+            document: write_interchange_tournament(&merged_tournament),
+          })?,
+        )
+      });
+
+    (merged_tournament, corrections)
+  }
+
+  #[must_use]
+  fn get_path(&self, tournament_name: &str) -> PathBuf {
+    self
+      .provider_dir
+      .join(format!("{}.json", to_file_name(tournament_name)))
+  }
+}
+
+// NOTE: From the last completed match (or the first remaining one if it's
+// earlier, e.g., a postponed one) minus the overlap. `None` when it can't be
+// known (e.g., matches without dates) so the tournament is fetched as a whole.
+#[must_use]
+fn get_since(tournament: &ImportedTournament) -> Option<i64> {
+  let last_completed_date = tournament
+    .matches_outcomes
+    .iter()
+    .map(|match_outcome| match_outcome.date)
+    .collect::<Option<Vec<i64>>>()?
+    .into_iter()
+    .max();
+  let first_remaining_date = tournament
+    .fixtures
+    .iter()
+    .flatten()
+    .map(|fixture| fixture.date)
+    .collect::<Option<Vec<i64>>>()?
+    .into_iter()
+    .min();
+
+  let since = match (last_completed_date, first_remaining_date) {
+    (Some(last_completed_date), Some(first_remaining_date)) => {
+      last_completed_date.min(first_remaining_date)
+    },
+    (last_completed_date, first_remaining_date) => {
+      last_completed_date.or(first_remaining_date)?
+    },
+  };
+
+  Some(since - OVERLAP_SECONDS)
+}
+
+#[allow(clippy::too_many_lines)]
+pub(super) fn test() {
+  #[cfg(test)]
+  use pretty_assertions::assert_eq;
+
+  use crate::common::CompetitionFormat;
+  use crate::tournament::fetching::common::Fixture;
+  use crate::tournament::fetching::common::FixtureStatus;
+  use crate::tournament::fetching::common::MatchStatus;
+
+  const DAY_SECONDS: i64 = 24 * 60 * 60;
+
+  let get_match_outcome = |home_team_name: &str,
+                           away_team_name: &str,
+                           score: (usize, usize),
+                           date: i64| MatchOutcome {
+    home_team_name: Arc::new(String::from(home_team_name)),
+    away_team_name: Arc::new(String::from(away_team_name)),
+    regulation_score: Some(score),
+    extra_time_score: None,
+    shootout_score: None,
+    status: MatchStatus::Finished,
+    date: Some(date),
+  };
+  let get_fixture =
+    |home_team_name: &str, away_team_name: &str, date: i64| Fixture {
+      home_team_name: Arc::new(String::from(home_team_name)),
+      away_team_name: Arc::new(String::from(away_team_name)),
+      date: Some(date),
+      venue: None,
+      status: FixtureStatus::Scheduled,
+    };
+  let tournament_name = Arc::new(String::from("Saudi Professional League"));
+  let get_fetched_tournament =
+    |matches_outcomes: Vec<MatchOutcome>, fixtures: Vec<Fixture>| {
+      FetchedTournament {
+        name: Arc::clone(&tournament_name),
+        provider_name: "Koora",
+        matches_outcomes,
+        fixtures: Some(fixtures),
+        format: CompetitionFormat::default(),
+      }
+    };
+  let get_matches = |fetched_tournament: &FetchedTournament| {
+    (
+      fetched_tournament.matches_outcomes.clone(),
+      fetched_tournament.fixtures.clone(),
+    )
+  };
+
+  // NOTE: Tests run in parallel so stores must not be shared.
+  let cache_dir = std::env::temp_dir().join(format!(
+    "boa-sync-{}-{:?}",
+    std::process::id(),
+    std::thread::current().id(),
+  ));
+  let now = 1_640_000_000;
+  let results_store = ResultsStore::new("Koora", &cache_dir, now);
+  let first_match = get_match_outcome("A", "B", (1, 0), now - 60 * DAY_SECONDS);
+  let second_match =
+    get_match_outcome("B", "C", (2, 2), now - 30 * DAY_SECONDS);
+  let third_match = get_match_outcome("C", "A", (0, 1), now - 2 * DAY_SECONDS);
+  let fixture = get_fixture("A", "C", now + 5 * DAY_SECONDS);
+
+  assert!(results_store.read(&tournament_name).is_none());
+  let fetched_tournament = get_fetched_tournament(
+    vec![first_match.clone(), second_match.clone(), third_match],
+    vec![fixture],
+  );
+  let expected_matches = get_matches(&fetched_tournament);
+  let (merged_tournament, corrections) =
+    results_store.merge(fetched_tournament, None);
+  assert_eq!(get_matches(&merged_tournament), expected_matches);
+  assert_eq!(corrections, vec![]);
+  assert!(cache_dir
+    .join("results/koora/saudi-professional-league.json")
+    .is_file());
+
+  // NOTE: Only matches since the last completed one (minus the overlap) are
+  // fetched again and the older ones fetched anyway (e.g., in the same month)
+  // are the synced ones.
+  let synced_tournament = results_store.read(&tournament_name).unwrap();
+  let since = now - 2 * DAY_SECONDS - OVERLAP_SECONDS;
+  assert_eq!(synced_tournament.since, Some(since));
+  let corrected_third_match =
+    get_match_outcome("C", "A", (3, 0), now - 2 * DAY_SECONDS);
+  let fourth_match = get_match_outcome("A", "C", (1, 1), now + 5 * DAY_SECONDS);
+  let (merged_tournament, corrections) = results_store.merge(
+    get_fetched_tournament(
+      vec![
+        get_match_outcome("B", "C", (9, 9), now - 30 * DAY_SECONDS),
+        corrected_third_match.clone(),
+        fourth_match.clone(),
+      ],
+      vec![],
+    ),
+    Some(synced_tournament),
+  );
+  assert_eq!(
+    get_matches(&merged_tournament),
+    (
+      vec![
+        first_match.clone(),
+        second_match,
+        corrected_third_match.clone(),
+        fourth_match.clone(),
+      ],
+      Some(vec![]),
+    ),
+  );
+  assert_eq!(
+    corrections,
+    vec![Discrepancy {
+      tournament_name: Arc::clone(&tournament_name),
+      home_team_name: Arc::new(String::from("C")),
+      away_team_name: Arc::new(String::from("A")),
+      kind: DiscrepancyKind::CorrectedResult {
+        provider_name: "Koora",
+        previous_score: Some((0, 1)),
+        score: Some((3, 0)),
+      },
+    }],
+  );
+  assert_eq!(
+    results_store.read(&tournament_name).unwrap().since,
+    Some(now + 5 * DAY_SECONDS - OVERLAP_SECONDS),
+  );
+
+  // NOTE: Tournaments are fetched as a whole after the interval (which finds
+  // any correction) and missing results are corrections too.
+  let later_results_store =
+    ResultsStore::new("Koora", &cache_dir, now + FULL_SYNC_INTERVAL_SECONDS);
+  let synced_tournament = later_results_store.read(&tournament_name).unwrap();
+  assert_eq!(synced_tournament.since, None);
+  let (merged_tournament, corrections) = later_results_store.merge(
+    get_fetched_tournament(
+      vec![first_match.clone(), corrected_third_match, fourth_match],
+      vec![],
+    ),
+    Some(synced_tournament),
+  );
+  assert_eq!(merged_tournament.matches_outcomes.len(), 3);
+  assert_eq!(
+    corrections
+      .iter()
+      .map(|correction| correction.kind.clone())
+      .collect::<Vec<_>>(),
+    vec![DiscrepancyKind::CorrectedResult {
+      provider_name: "Koora",
+      previous_score: Some((2, 2)),
+      score: None,
+    }],
+  );
+  assert_eq!(
+    later_results_store
+      .read(&tournament_name)
+      .unwrap()
+      .full_synced_at,
+    now + FULL_SYNC_INTERVAL_SECONDS,
+  );
+
+  // NOTE: Matches without dates can't be fetched incrementally.
+  let (_, corrections) = results_store.merge(
+    get_fetched_tournament(
+      vec![MatchOutcome {
+        date: None,
+        ..first_match
+      }],
+      vec![],
+    ),
+    None,
+  );
+  assert_eq!(corrections, vec![]);
+  assert_eq!(results_store.read(&tournament_name).unwrap().since, None);
+
+  fs::write(
+    cache_dir.join("results/koora/saudi-professional-league.json"),
+    "not json",
+  )
+  .unwrap();
+  assert!(results_store.read(&tournament_name).is_none());
+  fs::remove_dir_all(&cache_dir).unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+  use super::test;
+
+  #[test]
+  fn test_tournament_fetching_sync() {
+    test();
+  }
+}
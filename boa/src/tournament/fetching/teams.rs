@@ -0,0 +1,250 @@
+use std::collections::BTreeMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use serde::Deserialize;
+
+use crate::common::TeamId;
+
+/// Canonical teams (and how each provider refers to them) so that the same team
+/// isn't treated as different ones (e.g., "Al Hilal" and "الهلال"). Teams are
+/// still keyed by their canonical names while unregistered teams keep the
+/// names their providers gave them.
+#[must_use]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct TeamsRegistry {
+  teams: Vec<RegisteredTeam>,
+}
+
+#[must_use]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct RegisteredTeam {
+  // NOTE: Stays the same even if the canonical name changes.
+  pub(crate) id: Arc<String>,
+  pub(crate) name: TeamId,
+  // NOTE: By locale (e.g., `ar`).
+  pub(crate) localized_names: BTreeMap<String, Arc<String>>,
+  // NOTE: By provider (e.g., `Koora`).
+  pub(crate) aliases: BTreeMap<String, TeamAliases>,
+}
+
+#[must_use]
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq)]
+pub(crate) struct TeamAliases {
+  // NOTE: Preferred over names as they don't change with the language (e.g.,
+  // kooora.com and goalzz.com share them).
+  #[serde(default)]
+  pub(crate) ids: Vec<u64>,
+  #[serde(default)]
+  pub(crate) names: Vec<String>,
+}
+
+// NOTE: The `teams` of a providers config file.
+#[must_use]
+#[derive(Deserialize)]
+pub(super) struct RegisteredTeamJson {
+  id: String,
+  name: String,
+  #[serde(default)]
+  localized_names: BTreeMap<String, String>,
+  #[serde(default)]
+  aliases: BTreeMap<String, TeamAliases>,
+}
+
+impl TeamsRegistry {
+  /// Gets the canonical name of a provider's team by its id (if any) or name.
+  /// Unregistered teams keep their names.
+  #[must_use]
+  pub(crate) fn resolve(
+    &self,
+    provider_name: &str,
+    provider_team_id: Option<u64>,
+    provider_team_name: &str,
+  ) -> TeamId {
+    self
+      .teams
+      .iter()
+      .find(
+        |team| match (provider_team_id, team.aliases.get(provider_name)) {
+          (Some(provider_team_id), Some(aliases)) => {
+            aliases.ids.contains(&provider_team_id)
+          },
+          _ => false,
+        },
+      )
+      .or_else(|| {
+        self.teams.iter().find(|team| {
+          *team.name == provider_team_name
+            || team
+              .aliases
+              .get(provider_name)
+              .into_iter()
+              .flat_map(|aliases| &aliases.names)
+              .any(|name| name == provider_team_name)
+        })
+      })
+      .map_or_else(
+        || Arc::new(String::from(provider_team_name)),
+        |team| Arc::clone(&team.name),
+      )
+  }
+
+  /// Gets a team's name in a locale falling back to its canonical name.
+  #[must_use]
+  pub(crate) fn get_display_name(
+    &self,
+    team_name: &TeamId,
+    locale: &str,
+  ) -> TeamId {
+    self
+      .teams
+      .iter()
+      .find(|team| team.name == *team_name)
+      .and_then(|team| team.localized_names.get(locale))
+      .map_or_else(|| Arc::clone(team_name), Arc::clone)
+  }
+
+  #[must_use]
+  pub(crate) fn get_team(&self, team_name: &TeamId) -> Option<&RegisteredTeam> {
+    self.teams.iter().find(|team| team.name == *team_name)
+  }
+}
+
+/// Builds a registry rejecting duplicate ids or names and aliases that refer to
+/// more than one team.
+#[must_use]
+pub(super) fn parse_teams_registry(
+  teams: Vec<RegisteredTeamJson>,
+) -> Option<TeamsRegistry> {
+  let teams: Vec<RegisteredTeam> = teams
+    .into_iter()
+    .map(|team| RegisteredTeam {
+      id: Arc::new(team.id),
+      name: Arc::new(team.name),
+      localized_names: team
+        .localized_names
+        .into_iter()
+        .map(|(locale, name)| (locale, Arc::new(name)))
+        .collect(),
+      aliases: team.aliases,
+    })
+    .collect();
+
+  let ids: HashSet<&Arc<String>> = teams.iter().map(|team| &team.id).collect();
+  let names: HashSet<&str> =
+    teams.iter().map(|team| team.name.as_str()).collect();
+  if ids.len() != teams.len()
+    || names.len() != teams.len()
+    || teams
+      .iter()
+      .any(|team| team.id.is_empty() || team.name.is_empty())
+  {
+    return None;
+  }
+
+  let mut aliases_ids: HashSet<(&String, u64)> = HashSet::new();
+  let mut aliases_names: HashSet<(&String, &String)> = HashSet::new();
+  for team in &teams {
+    for (provider_name, aliases) in &team.aliases {
+      for &id in &aliases.ids {
+        if !aliases_ids.insert((provider_name, id)) {
+          return None;
+        }
+      }
+      for name in &aliases.names {
+        // NOTE: Another team's canonical name would make it unreachable.
+        if (names.contains(name.as_str()) && *name != *team.name)
+          || !aliases_names.insert((provider_name, name))
+        {
+          return None;
+        }
+      }
+    }
+  }
+
+  Some(TeamsRegistry { teams })
+}
+
+pub(super) fn test() {
+  #[cfg(test)]
+  use pretty_assertions::assert_eq;
+
+  let parse = |teams_json: &str| {
+    parse_teams_registry(
+      serde_json::from_str::<Vec<RegisteredTeamJson>>(teams_json).unwrap(),
+    )
+  };
+
+  let registry = parse(
+    r#"[
+      {
+        "id": "al-hilal",
+        "name": "Al Hilal",
+        "localized_names": {"ar": "الهلال"},
+        "aliases": {"Koora": {"ids": [144], "names": ["Al-Hilal SFC"]}}
+      },
+      {"id": "al-nassr", "name": "Al Nassr"}
+    ]"#,
+  )
+  .unwrap();
+  let resolve = |provider_team_id: Option<u64>, provider_team_name: &str| {
+    registry.resolve("Koora", provider_team_id, provider_team_name)
+  };
+
+  let al_hilal = Arc::new(String::from("Al Hilal"));
+  assert_eq!(resolve(Some(144), "الهلال"), al_hilal);
+  assert_eq!(resolve(None, "Al-Hilal SFC"), al_hilal);
+  assert_eq!(resolve(Some(1), "Al Hilal"), al_hilal);
+  assert_eq!(
+    resolve(None, "Al Nassr"),
+    Arc::new(String::from("Al Nassr"))
+  );
+  assert_eq!(resolve(Some(2), "Abha"), Arc::new(String::from("Abha")));
+  // NOTE: Aliases are per provider.
+  assert_eq!(
+    registry.resolve("Premier League", Some(144), "Al-Hilal SFC"),
+    Arc::new(String::from("Al-Hilal SFC")),
+  );
+
+  assert_eq!(
+    registry.get_display_name(&al_hilal, "ar"),
+    Arc::new(String::from("الهلال")),
+  );
+  assert_eq!(registry.get_display_name(&al_hilal, "fr"), al_hilal);
+  assert_eq!(
+    registry.get_display_name(&Arc::new(String::from("Abha")), "ar"),
+    Arc::new(String::from("Abha")),
+  );
+  assert_eq!(
+    registry
+      .get_team(&al_hilal)
+      .map(|team| Arc::clone(&team.id)),
+    Some(Arc::new(String::from("al-hilal"))),
+  );
+
+  for invalid_teams_json in [
+    r#"[{"id": "a", "name": "A"}, {"id": "a", "name": "B"}]"#,
+    r#"[{"id": "a", "name": "A"}, {"id": "b", "name": "A"}]"#,
+    r#"[{"id": "", "name": "A"}]"#,
+    r#"[
+      {"id": "a", "name": "A", "aliases": {"Koora": {"ids": [1]}}},
+      {"id": "b", "name": "B", "aliases": {"Koora": {"ids": [1]}}}
+    ]"#,
+    r#"[
+      {"id": "a", "name": "A", "aliases": {"Koora": {"names": ["B"]}}},
+      {"id": "b", "name": "B"}
+    ]"#,
+  ] {
+    assert_eq!(parse(invalid_teams_json), None, "{}", invalid_teams_json);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::test;
+
+  #[test]
+  fn test_tournament_fetching_teams() {
+    test();
+  }
+}
@@ -0,0 +1,333 @@
+use std::collections::HashMap;
+use std::fs;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use chrono::prelude::Utc;
+use reqwest::blocking::Client;
+use reqwest::header::HeaderMap;
+use reqwest::header::HeaderValue;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::tournament::fetching::common::FetchError;
+
+/// A previous response's validators which are sent back (as `If-None-Match`
+/// and `If-Modified-Since`) so that an unchanged response isn't downloaded
+/// again.
+#[must_use]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Validators {
+  pub(crate) etag: Option<String>,
+  pub(crate) last_modified: Option<String>,
+}
+
+#[must_use]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConditionalResponse {
+  Modified {
+    body: String,
+    validators: Validators,
+  },
+  NotModified,
+}
+
+// NOTE: Providers only make GET requests and only need the response's body.
+pub trait Transport {
+  /// # Errors
+  ///
+  /// When the request fails (e.g., offline or an unsuccessful status).
+  fn get(&self, url: &str) -> Result<String, FetchError>;
+
+  /// A page (e.g., a month) of a tournament which is what's cached (see
+  /// `CachingTransport`). Other transports ignore the page.
+  /// # Errors
+  ///
+  /// The same conditions `get()` fails on.
+  fn get_page(
+    &self,
+    url: &str,
+    _tournament_name: &Arc<String>,
+    _page: &str,
+  ) -> Result<String, FetchError> {
+    self.get(url)
+  }
+
+  /// Transports without conditional requests always respond in full.
+  /// # Errors
+  ///
+  /// The same conditions `get()` fails on.
+  fn get_conditional(
+    &self,
+    url: &str,
+    _validators: &Validators,
+  ) -> Result<ConditionalResponse, FetchError> {
+    self.get(url).map(|body| ConditionalResponse::Modified {
+      body,
+      validators: Validators::default(),
+    })
+  }
+}
+
+#[must_use]
+pub(crate) struct HttpTransport {
+  // NOTE: Building the client can fail (e.g., TLS can't be initialized) in
+  // which case every request fails with why (like `AsyncHttpTransport`).
+  client: Result<Client, String>,
+}
+
+impl HttpTransport {
+  pub(crate) fn new(origin: &'static str) -> Self {
+    Self::with_https_only(origin, true)
+  }
+
+  // NOTE: Plain HTTP is only meant for local servers (e.g., in tests).
+  pub(crate) fn with_https_only(
+    origin: &'static str,
+    https_only: bool,
+  ) -> Self {
+    Self {
+      client: get_client(origin, https_only),
+    }
+  }
+
+  fn get_client(&self, url: &str) -> Result<&Client, FetchError> {
+    self.client.as_ref().map_err(|message| FetchError::Network {
+      url: String::from(url),
+      message: message.clone(),
+    })
+  }
+}
+
+impl Transport for HttpTransport {
+  fn get(&self, url: &str) -> Result<String, FetchError> {
+    let to_fetch_error = |error: reqwest::Error| to_fetch_error(url, &error);
+
+    // SEE: https://docs.rs/reqwest/0.11.7/reqwest/struct.RequestBuilder.html#method.send
+    // SEE: https://docs.rs/reqwest/0.11.7/reqwest/blocking/struct.Response.html#method.error_for_status
+    self
+      .get_client(url)?
+      .get(url)
+      .send()
+      .and_then(reqwest::blocking::Response::error_for_status)
+      .map_err(to_fetch_error)?
+      .text()
+      .map_err(to_fetch_error)
+  }
+
+  fn get_conditional(
+    &self,
+    url: &str,
+    validators: &Validators,
+  ) -> Result<ConditionalResponse, FetchError> {
+    use reqwest::header::ETAG;
+    use reqwest::header::IF_MODIFIED_SINCE;
+    use reqwest::header::IF_NONE_MATCH;
+    use reqwest::header::LAST_MODIFIED;
+    use reqwest::StatusCode;
+
+    let to_fetch_error = |error: reqwest::Error| to_fetch_error(url, &error);
+
+    let mut request = self.get_client(url)?.get(url);
+    if let Some(etag) = &validators.etag {
+      request = request.header(IF_NONE_MATCH, etag);
+    }
+    if let Some(last_modified) = &validators.last_modified {
+      request = request.header(IF_MODIFIED_SINCE, last_modified);
+    }
+
+    let response = request.send().map_err(to_fetch_error)?;
+    if response.status() == StatusCode::NOT_MODIFIED {
+      return Ok(ConditionalResponse::NotModified);
+    }
+
+    let response = response.error_for_status().map_err(to_fetch_error)?;
+    let get_header = |name| {
+      response
+        .headers()
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(String::from)
+    };
+    let validators = Validators {
+      etag: get_header(ETAG),
+      last_modified: get_header(LAST_MODIFIED),
+    };
+
+    Ok(ConditionalResponse::Modified {
+      body: response.text().map_err(to_fetch_error)?,
+      validators,
+    })
+  }
+}
+
+// NOTE: A cassette is a file with a recorded request per line (as JSON) and
+// `recorded_at` is when it was recorded (a unix-timestamp).
+#[must_use]
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub(crate) struct RecordedRequest {
+  pub(crate) url: String,
+  pub(crate) recorded_at: i64,
+  pub(crate) response: String,
+}
+
+/// Records every request (and its response) made through another transport to
+/// a cassette.
+#[must_use]
+pub(crate) struct RecordingTransport<T: Transport> {
+  transport: T,
+  cassette_path: String,
+  cassette: Mutex<File>,
+}
+
+impl<T: Transport> RecordingTransport<T> {
+  /// Fails if the cassette can't be created.
+  pub(crate) fn new(
+    transport: T,
+    cassette_path: &Path,
+  ) -> Result<Self, FetchError> {
+    let to_fetch_error = |error: std::io::Error| FetchError::Io {
+      path: cassette_path.display().to_string(),
+      message: error.to_string(),
+    };
+
+    Ok(Self {
+      transport,
+      cassette_path: cassette_path.display().to_string(),
+      cassette: Mutex::new(
+        File::create(cassette_path).map_err(to_fetch_error)?,
+      ),
+    })
+  }
+}
+
+// NOTE: Failed requests aren't recorded and replaying them fails as missing.
+// Requests whose responses can't be recorded fail too as the cassette would be
+// incomplete otherwise.
+impl<T: Transport> Transport for RecordingTransport<T> {
+  fn get(&self, url: &str) -> Result<String, FetchError> {
+    let response = self.transport.get(url)?;
+
+    let mut recorded_request = serde_json::to_string(&RecordedRequest {
+      url: String::from(url),
+      recorded_at: Utc::now().timestamp(),
+      response: response.clone(),
+    })?;
+    recorded_request.push('\n');
+    self
+      .cassette
+      .lock()
+      .unwrap()
+      .write_all(recorded_request.as_bytes())
+      .map_err(|error| FetchError::Io {
+        path: self.cassette_path.clone(),
+        message: error.to_string(),
+      })?;
+
+    Ok(response)
+  }
+}
+
+/// Serves requests from a cassette without any network access.
+#[must_use]
+pub(crate) struct ReplayTransport {
+  responses: HashMap<String, String>,
+}
+
+impl ReplayTransport {
+  /// Fails if the cassette can't be read or any of its lines isn't a recorded
+  /// request.
+  pub(crate) fn new(cassette_path: &Path) -> Result<Self, FetchError> {
+    let responses = read_cassette(cassette_path)?
+      .into_iter()
+      .map(|recorded_request| (recorded_request.url, recorded_request.response))
+      .collect();
+
+    Ok(Self { responses })
+  }
+}
+
+/// Reads a cassette's recorded requests (in the order they were made).
+pub(crate) fn read_cassette(
+  cassette_path: &Path,
+) -> Result<Vec<RecordedRequest>, FetchError> {
+  fs::read_to_string(cassette_path)
+    .map_err(|error| FetchError::Io {
+      path: cassette_path.display().to_string(),
+      message: error.to_string(),
+    })?
+    .lines()
+    .map(|line| Ok(serde_json::from_str(line)?))
+    .collect()
+}
+
+impl Transport for ReplayTransport {
+  fn get(&self, url: &str) -> Result<String, FetchError> {
+    self
+      .responses
+      .get(url)
+      .cloned()
+      .ok_or_else(|| FetchError::Network {
+        url: String::from(url),
+        message: String::from("Missing recorded response"),
+      })
+  }
+}
+
+#[must_use]
+pub(super) fn to_fetch_error(url: &str, error: &reqwest::Error) -> FetchError {
+  FetchError::Network {
+    url: String::from(url),
+    message: error.to_string(),
+  }
+}
+
+// NOTE: Same as the official pages' (e.g., a browser's).
+pub(super) const USER_AGENT: &str =
+  "Mozilla/5.0 (X11; Ubuntu; Linux x86_64; rv:95.0) Gecko/20100101 Firefox/95.0";
+
+#[must_use]
+fn get_client(
+  origin: &'static str,
+  https_only: bool,
+) -> Result<Client, String> {
+  let mut headers = get_default_headers();
+  headers.insert("Origin", HeaderValue::from_static(origin));
+
+  // SEE: https://docs.rs/reqwest/0.11.7/reqwest/struct.ClientBuilder.html
+  Client::builder()
+    .user_agent(USER_AGENT)
+    .referer(true)
+    .https_only(https_only)
+    .gzip(true)
+    .deflate(true)
+    .brotli(true)
+    .default_headers(headers)
+    .build()
+    .map_err(|error| error.to_string())
+}
+
+// NOTE: All but the origin which is per provider.
+// SEE: https://docs.rs/reqwest/0.11.7/reqwest/struct.ClientBuilder.html#method.default_headers
+#[must_use]
+pub(super) fn get_default_headers() -> HeaderMap {
+  let mut headers = HeaderMap::new();
+  headers.insert(
+    "Content-Type",
+    HeaderValue::from_static(
+      "application/x-www-form-urlencoded; charset=UTF-8",
+    ),
+  );
+  headers.insert("Sec-Fetch-Dest", HeaderValue::from_static("empty"));
+  headers.insert("Sec-Fetch-Mode", HeaderValue::from_static("cors"));
+  headers.insert("Sec-Fetch-Site", HeaderValue::from_static("cross-site"));
+  headers.insert(
+    "Accept-Language",
+    HeaderValue::from_static("en-US,en;q=0.5"),
+  );
+
+  headers
+}
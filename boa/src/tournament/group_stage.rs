@@ -0,0 +1,375 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use crate::common::AdvancementStatus;
+use crate::common::GroupStage;
+use crate::common::Team;
+use crate::common::TeamId;
+use crate::common::Tournament;
+use crate::tournament::fetching::common::postprocess_tournament_results;
+use crate::tournament::fetching::common::MatchResult;
+use crate::tournament::fetching::common::DRAW_FACTOR;
+use crate::tournament::fetching::common::WIN_FACTOR;
+use crate::tournament::prediction::get_max_points;
+
+#[must_use]
+struct GroupPrediction<'a> {
+  teams: Vec<&'a Arc<Team>>,
+  // NOTE: Counts of other teams that are (strictly) above a team in the best
+  // case and (not strictly) above it in the worst case.
+  best_places: Vec<usize>,
+  worst_places: Vec<usize>,
+  // NOTE: The fewest points the team placed right after the advancing places
+  // can end up with.
+  best_placed_points_min: isize,
+}
+
+/// Predicts teams' advancement-statuses in each group of a group-stage (only
+/// points are considered so ties count in the team's favour for whether it can
+/// still advance and against it for whether it clinched). `None` is returned
+/// when any group has too many remaining matches to visit all their outcomes.
+/// # Panics
+#[must_use]
+pub(super) fn predict_group_stage(
+  group_stage: &GroupStage,
+) -> Option<BTreeMap<Arc<String>, BTreeMap<TeamId, AdvancementStatus>>> {
+  let groups_predictions: Vec<GroupPrediction> = group_stage
+    .groups
+    .iter()
+    .map(|group| predict_group(group, group_stage.advancing_teams_count))
+    .collect::<Option<_>>()?;
+
+  let groups_statuses = groups_predictions
+    .iter()
+    .enumerate()
+    .map(|(i, group_prediction)| {
+      let teams_statuses = group_prediction
+        .teams
+        .iter()
+        .enumerate()
+        .map(|(j, team)| {
+          let status = if group_prediction.worst_places[j]
+            < group_stage.advancing_teams_count
+          {
+            AdvancementStatus::Clinched
+          } else if group_prediction.best_places[j]
+            < group_stage.advancing_teams_count
+          {
+            AdvancementStatus::Undecided
+          } else if group_prediction.best_places[j]
+            > group_stage.advancing_teams_count
+            || group_stage.best_placed_teams_count == 0
+          {
+            AdvancementStatus::Eliminated
+          } else {
+            // NOTE: The team can only advance as one of the best-placed teams
+            // which isn't possible if enough other groups are guaranteed to
+            // have better ones.
+            let max_points = get_max_points(team);
+            let better_groups_count = groups_predictions
+              .iter()
+              .enumerate()
+              .filter(|&(k, other_group_prediction)| {
+                k != i
+                  && other_group_prediction.best_placed_points_min > max_points
+              })
+              .count();
+
+            if better_groups_count >= group_stage.best_placed_teams_count {
+              AdvancementStatus::Eliminated
+            } else {
+              AdvancementStatus::Undecided
+            }
+          };
+
+          (Arc::clone(&team.name), status)
+        })
+        .collect();
+
+      (Arc::clone(&group_stage.groups[i].name), teams_statuses)
+    })
+    .collect();
+
+  Some(groups_statuses)
+}
+
+// NOTE: All possible outcomes of the group's remaining matches are visited
+// (only wins, draws and losses matter for points) which is only feasible for
+// small groups (e.g., a 4-team double round-robin) so larger ones are `None`.
+#[must_use]
+fn predict_group(
+  group: &Tournament,
+  advancing_teams_count: usize,
+) -> Option<GroupPrediction<'_>> {
+  const REMAINING_MATCHES_COUNT_MAX: usize = 12;
+
+  let teams: Vec<&Arc<Team>> = group.teams.iter().collect();
+  let get_team_index = |team_name: &TeamId| {
+    teams
+      .iter()
+      .position(|team| team.name == *team_name)
+      .unwrap()
+  };
+  let remaining_matches: Vec<(usize, usize)> = group
+    .remaining_points
+    .as_ref()
+    .unwrap_or_else(|| panic!("Missing remaining-points"))
+    .iter()
+    .flat_map(|((first_team_name, second_team_name), &remaining_points)| {
+      let remaining_match = (
+        get_team_index(first_team_name),
+        get_team_index(second_team_name),
+      );
+      (0..remaining_points / WIN_FACTOR).map(move |_| remaining_match)
+    })
+    .collect();
+  if remaining_matches.len() > REMAINING_MATCHES_COUNT_MAX {
+    return None;
+  }
+
+  let mut best_places = vec![usize::MAX; teams.len()];
+  let mut worst_places = vec![0; teams.len()];
+  let mut best_placed_points_min = isize::MAX;
+  let mut points: Vec<isize> =
+    teams.iter().map(|team| team.get_points()).collect();
+  visit_outcomes(&remaining_matches, &mut points, &mut |points| {
+    for (i, &team_points) in points.iter().enumerate() {
+      let above_count = points.iter().filter(|&&p| p > team_points).count();
+      let not_below_count =
+        points.iter().filter(|&&p| p >= team_points).count() - 1;
+
+      best_places[i] = best_places[i].min(above_count);
+      worst_places[i] = worst_places[i].max(not_below_count);
+    }
+
+    let mut sorted_points = points.to_vec();
+    sorted_points.sort_unstable_by(|a, b| b.cmp(a));
+    best_placed_points_min =
+      best_placed_points_min.min(sorted_points[advancing_teams_count]);
+  });
+
+  Some(GroupPrediction {
+    teams,
+    best_places,
+    worst_places,
+    best_placed_points_min,
+  })
+}
+
+fn visit_outcomes(
+  remaining_matches: &[(usize, usize)],
+  points: &mut Vec<isize>,
+  visit: &mut impl FnMut(&[isize]),
+) {
+  let win_points = isize::try_from(WIN_FACTOR).unwrap();
+  let draw_points = isize::try_from(DRAW_FACTOR).unwrap();
+
+  match remaining_matches.split_first() {
+    None => visit(points),
+    Some((&(first_team_index, second_team_index), rest)) => {
+      for (first_team_points, second_team_points) in
+        [(win_points, 0), (draw_points, draw_points), (0, win_points)]
+      {
+        points[first_team_index] += first_team_points;
+        points[second_team_index] += second_team_points;
+        visit_outcomes(rest, points, visit);
+        points[first_team_index] -= first_team_points;
+        points[second_team_index] -= second_team_points;
+      }
+    },
+  }
+}
+
+#[allow(clippy::too_many_lines)]
+pub(super) fn test() {
+  #[cfg(test)]
+  use pretty_assertions::assert_eq;
+
+  let get_group =
+    |group_name: &str, matches_results: &[(&str, &str, usize, usize)]| {
+      let matches_results: Vec<MatchResult> = matches_results
+        .iter()
+        .map(
+          |&(first_team_name, second_team_name, first_score, second_score)| {
+            (
+              (Arc::new(String::from(first_team_name)), first_score),
+              (Arc::new(String::from(second_team_name)), second_score),
+            )
+          },
+        )
+        .collect();
+      let teams_names: BTreeSet<TeamId> = matches_results
+        .iter()
+        .flat_map(|((first_team_name, _), (second_team_name, _))| {
+          vec![Arc::clone(first_team_name), Arc::clone(second_team_name)]
+        })
+        .collect();
+
+      postprocess_tournament_results(
+        &Arc::new(String::from(group_name)),
+        &teams_names,
+        &matches_results,
+      )
+    };
+  let get_statuses = |statuses: Vec<(&str, AdvancementStatus)>| {
+    statuses
+      .into_iter()
+      .map(|(team_name, status)| (Arc::new(String::from(team_name)), status))
+      .collect::<BTreeMap<_, _>>()
+  };
+
+  // NOTE: Points are (a1: 12, a2: 7, a3: 1, a4: 2) with a1-a4 (twice), a2-a4
+  // and a3-a4 left. So a3 can only finish third.
+  let group_a = || {
+    get_group(
+      "Group A",
+      &[
+        ("a1", "a2", 1, 0),
+        ("a2", "a1", 0, 1),
+        ("a1", "a3", 1, 0),
+        ("a3", "a1", 0, 1),
+        ("a2", "a3", 1, 0),
+        ("a3", "a2", 0, 1),
+        ("a2", "a4", 0, 0),
+        ("a3", "a4", 0, 0),
+      ],
+    )
+  };
+  // NOTE: Points are (b1: 18, b2: 10, b3: 4, b4: 0) with b4-b3 left. So b4
+  // can't even finish third.
+  let group_b = || {
+    get_group(
+      "Group B",
+      &[
+        ("b1", "b2", 1, 0),
+        ("b2", "b1", 0, 1),
+        ("b1", "b3", 1, 0),
+        ("b3", "b1", 0, 1),
+        ("b1", "b4", 1, 0),
+        ("b4", "b1", 0, 1),
+        ("b2", "b3", 1, 0),
+        ("b3", "b2", 1, 1),
+        ("b2", "b4", 1, 0),
+        ("b4", "b2", 0, 1),
+        ("b3", "b4", 1, 0),
+      ],
+    )
+  };
+
+  let group_stage_name = Arc::new(String::from("dummy-group-stage"));
+  let group_b_statuses = || {
+    get_statuses(vec![
+      ("b1", AdvancementStatus::Clinched),
+      ("b2", AdvancementStatus::Clinched),
+      ("b3", AdvancementStatus::Undecided),
+      ("b4", AdvancementStatus::Eliminated),
+    ])
+  };
+
+  assert_eq!(
+    predict_group_stage(&GroupStage::new(
+      &group_stage_name,
+      vec![group_a(), group_b()],
+      2,
+      1,
+    ))
+    .unwrap(),
+    vec![
+      (
+        Arc::new(String::from("Group A")),
+        get_statuses(vec![
+          ("a1", AdvancementStatus::Clinched),
+          ("a2", AdvancementStatus::Undecided),
+          ("a3", AdvancementStatus::Undecided),
+          ("a4", AdvancementStatus::Undecided),
+        ]),
+      ),
+      (Arc::new(String::from("Group B")), group_b_statuses()),
+    ]
+    .into_iter()
+    .collect(),
+  );
+
+  // NOTE: Without best-placed teams a3 and b3 can't advance at all.
+  let predictions = predict_group_stage(&GroupStage::new(
+    &group_stage_name,
+    vec![group_a(), group_b()],
+    2,
+    0,
+  ))
+  .unwrap();
+  assert_eq!(
+    predictions[&Arc::new(String::from("Group A"))]
+      [&Arc::new(String::from("a3"))],
+    AdvancementStatus::Eliminated,
+  );
+  assert_eq!(
+    predictions[&Arc::new(String::from("Group B"))]
+      [&Arc::new(String::from("b3"))],
+    AdvancementStatus::Eliminated,
+  );
+
+  // NOTE: With a third group whose third-placed team is guaranteed more points
+  // than a3 can get, a3 can't advance as the best third-placed team anymore.
+  let group_c = get_group(
+    "Group C",
+    &[
+      ("c1", "c2", 1, 0),
+      ("c2", "c1", 0, 1),
+      ("c1", "c3", 1, 0),
+      ("c3", "c1", 0, 1),
+      ("c1", "c4", 1, 0),
+      ("c4", "c1", 0, 1),
+      ("c2", "c3", 1, 1),
+      ("c3", "c2", 1, 1),
+      ("c2", "c4", 1, 0),
+      ("c4", "c2", 0, 1),
+      ("c3", "c4", 1, 0),
+      ("c4", "c3", 0, 1),
+    ],
+  );
+  let predictions = predict_group_stage(&GroupStage::new(
+    &group_stage_name,
+    vec![group_a(), group_b(), group_c],
+    2,
+    1,
+  ))
+  .unwrap();
+  assert_eq!(
+    predictions[&Arc::new(String::from("Group A"))]
+      [&Arc::new(String::from("a3"))],
+    AdvancementStatus::Eliminated,
+  );
+
+  // NOTE: A 5-team double round-robin has 20 matches which are too many
+  // outcomes to visit.
+  let teams_names = ["e1", "e2", "e3", "e4", "e5"];
+  let group_e = postprocess_tournament_results(
+    &Arc::new(String::from("Group E")),
+    &teams_names
+      .iter()
+      .map(|&team_name| Arc::new(String::from(team_name)))
+      .collect(),
+    &[],
+  );
+  assert_eq!(
+    predict_group_stage(&GroupStage::new(
+      &group_stage_name,
+      vec![group_a(), group_e],
+      2,
+      1,
+    )),
+    None,
+  );
+}
+
+#[cfg(test)]
+mod tests {
+  use super::test;
+
+  #[test]
+  fn test_tournament_group_stage() {
+    test();
+  }
+}
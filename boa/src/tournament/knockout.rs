@@ -0,0 +1,565 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use itertools::Itertools;
+
+use crate::common::TeamId;
+
+#[must_use]
+#[derive(Debug, Eq, PartialEq)]
+pub struct KnockoutLeg {
+  // NOTE: Scores include extra-time and penalties are only for the tie's last
+  // leg (when the tie is still level).
+  pub home_team_score: usize,
+  pub away_team_score: usize,
+  pub penalties: Option<(usize, usize)>,
+}
+
+#[must_use]
+#[derive(Debug, Eq, PartialEq)]
+pub struct KnockoutTie {
+  // NOTE: The first team hosts the first leg and legs alternate hosts.
+  pub first_team: TeamId,
+  pub second_team: TeamId,
+  pub legs: Vec<KnockoutLeg>,
+}
+
+#[must_use]
+#[derive(Debug, Eq, PartialEq)]
+pub struct KnockoutRound {
+  pub name: Arc<String>,
+  pub legs_count: usize,
+  pub away_goals_rule: bool,
+  // NOTE: Empty until the round is drawn.
+  pub ties: Vec<KnockoutTie>,
+}
+
+#[must_use]
+#[derive(Debug, Eq, PartialEq)]
+pub struct KnockoutBracket {
+  // NOTE: Fields are private as predicting relies on them being validated.
+  name: Arc<String>,
+  // NOTE: From the first round to the final.
+  rounds: Vec<KnockoutRound>,
+  // NOTE: With a fixed bracket, a round's n-th tie is between the winners of
+  // the previous round's (2n)-th and (2n+1)-th ties. Otherwise, each round is
+  // drawn (randomly) after the previous one is over.
+  fixed: bool,
+  constructor_guard: PhantomData<()>,
+}
+
+#[must_use]
+#[derive(Debug, Eq, PartialEq)]
+pub enum KnockoutStatus {
+  Eliminated,
+  // NOTE: Teams that can still be met in the current or any later round.
+  Alive(BTreeSet<TeamId>),
+}
+
+impl KnockoutBracket {
+  /// # Panics
+  #[must_use]
+  pub fn new(
+    name: &Arc<String>,
+    rounds: Vec<KnockoutRound>,
+    fixed: bool,
+  ) -> Self {
+    const NAME_LENGTH_MIN: usize = 1;
+    const NAME_LENGTH_MAX: usize = 100;
+    const ROUNDS_COUNT_MIN: usize = 1;
+    const ROUNDS_COUNT_MAX: usize = 10;
+    const LEGS_COUNT_MIN: usize = 1;
+    const LEGS_COUNT_MAX: usize = 2;
+
+    assert!(
+      name.len() >= NAME_LENGTH_MIN && name.len() <= NAME_LENGTH_MAX,
+      "Invalid name ({:?}).",
+      name
+    );
+    assert!(
+      rounds.len() >= ROUNDS_COUNT_MIN && rounds.len() <= ROUNDS_COUNT_MAX,
+      "Invalid no. of rounds ({:?}).",
+      rounds.len()
+    );
+    assert!(
+      rounds[0].ties.len() == 1 << (rounds.len() - 1),
+      "Invalid no. of first-round ties ({:?}, {:?}).",
+      rounds[0].ties.len(),
+      rounds.len(),
+    );
+
+    for (i, round) in rounds.iter().enumerate() {
+      let ties_count = 1 << (rounds.len() - 1 - i);
+
+      assert!(
+        round.legs_count >= LEGS_COUNT_MIN
+          && round.legs_count <= LEGS_COUNT_MAX,
+        "Invalid no. of legs ({:?}).",
+        round,
+      );
+      assert!(
+        round.ties.is_empty() || round.ties.len() == ties_count,
+        "Invalid no. of ties ({:?}, {:?}).",
+        round,
+        ties_count,
+      );
+      assert!(
+        round
+          .ties
+          .iter()
+          .flat_map(|tie| vec![&tie.first_team, &tie.second_team])
+          .all_unique(),
+        "Teams in multiple ties ({:?}).",
+        round,
+      );
+      assert!(
+        round
+          .ties
+          .iter()
+          .all(|tie| tie.legs.len() <= round.legs_count),
+        "Too many legs ({:?}).",
+        round,
+      );
+      assert!(
+        round.ties.iter().all(|tie| {
+          let (level_legs, last_legs) = tie
+            .legs
+            .split_at(tie.legs.len().min(round.legs_count.saturating_sub(1)));
+          level_legs.iter().all(|leg| leg.penalties.is_none())
+            && last_legs.iter().all(|leg| {
+              !matches!(
+                leg.penalties,
+                Some((home_penalties, away_penalties))
+                  if home_penalties == away_penalties
+              )
+            })
+        }),
+        "Invalid penalties ({:?}).",
+        round,
+      );
+    }
+
+    let bracket = Self {
+      name: Arc::clone(name),
+      rounds,
+      fixed,
+      constructor_guard: PhantomData,
+    };
+
+    // NOTE: This also validates that ties are decided and that drawn rounds
+    // only have teams that can still reach them.
+    let _ = predict_rounds(&bracket);
+
+    bracket
+  }
+
+  #[must_use]
+  pub const fn get_name(&self) -> &Arc<String> {
+    &self.name
+  }
+
+  #[must_use]
+  pub fn get_rounds(&self) -> &[KnockoutRound] {
+    &self.rounds
+  }
+
+  #[must_use]
+  pub const fn is_fixed(&self) -> bool {
+    self.fixed
+  }
+
+  /// Takes the rounds back, e.g., to add newly played legs and build the
+  /// bracket again.
+  #[must_use]
+  pub fn into_rounds(self) -> Vec<KnockoutRound> {
+    self.rounds
+  }
+}
+
+#[must_use]
+struct PredictedRound {
+  // NOTE: Teams that can still play in the round.
+  teams: BTreeSet<TeamId>,
+  // NOTE: Missing for rounds that aren't drawn yet with random draws.
+  ties: Option<Vec<PredictedTie>>,
+}
+
+#[must_use]
+struct PredictedTie {
+  // NOTE: The teams that can still end up on each side of the tie.
+  first_side: BTreeSet<TeamId>,
+  second_side: BTreeSet<TeamId>,
+  winner: Option<TeamId>,
+}
+
+/// Predicts which teams are still alive in a knockout-bracket and which teams
+/// they can still meet.
+/// # Panics
+#[must_use]
+pub(super) fn predict_knockout_bracket(
+  bracket: &KnockoutBracket,
+) -> BTreeMap<TeamId, KnockoutStatus> {
+  let (predicted_rounds, alive_teams) = predict_rounds(bracket);
+
+  predicted_rounds[0]
+    .teams
+    .iter()
+    .map(|team| {
+      if !alive_teams.contains(team) {
+        return (Arc::clone(team), KnockoutStatus::Eliminated);
+      }
+
+      let opponents: BTreeSet<TeamId> = predicted_rounds
+        .iter()
+        .enumerate()
+        .filter(|(_, predicted_round)| predicted_round.teams.contains(team))
+        .flat_map(|(i, predicted_round)| {
+          predicted_round.ties.as_ref().map_or_else(
+            // NOTE: Any other team can be drawn against unless they meet in
+            // an earlier tie (as then only one of them can make it).
+            || {
+              predicted_round
+                .teams
+                .iter()
+                .filter(|&opponent| opponent != team)
+                .filter(|&opponent| {
+                  !predicted_rounds[..i]
+                    .iter()
+                    .flat_map(|earlier_round| earlier_round.ties.iter())
+                    .flatten()
+                    .any(|tie| {
+                      let tie_teams =
+                        || tie.first_side.iter().chain(&tie.second_side);
+                      tie_teams().contains(team)
+                        && tie_teams().contains(opponent)
+                    })
+                })
+                .map(Arc::clone)
+                .collect()
+            },
+            |ties| {
+              ties
+                .iter()
+                .filter(|tie| tie.winner.is_none())
+                .find_map(|tie| {
+                  if tie.first_side.contains(team) {
+                    Some(tie.second_side.clone())
+                  } else if tie.second_side.contains(team) {
+                    Some(tie.first_side.clone())
+                  } else {
+                    None
+                  }
+                })
+                .unwrap_or_default()
+            },
+          )
+        })
+        .collect();
+
+      (Arc::clone(team), KnockoutStatus::Alive(opponents))
+    })
+    .collect()
+}
+
+#[must_use]
+fn predict_rounds(
+  bracket: &KnockoutBracket,
+) -> (Vec<PredictedRound>, BTreeSet<TeamId>) {
+  let mut predicted_rounds: Vec<PredictedRound> = Vec::new();
+  let mut teams: BTreeSet<TeamId> = bracket.rounds[0]
+    .ties
+    .iter()
+    .flat_map(|tie| {
+      vec![Arc::clone(&tie.first_team), Arc::clone(&tie.second_team)]
+    })
+    .collect();
+
+  for round in &bracket.rounds {
+    let ties = if !round.ties.is_empty() {
+      assert!(
+        round.ties.iter().all(|tie| {
+          teams.contains(&tie.first_team) && teams.contains(&tie.second_team)
+        }),
+        "Teams can't reach round ({:?}, {:?}).",
+        round,
+        teams,
+      );
+
+      Some(
+        round
+          .ties
+          .iter()
+          .map(|tie| PredictedTie {
+            first_side: vec![Arc::clone(&tie.first_team)].into_iter().collect(),
+            second_side: vec![Arc::clone(&tie.second_team)]
+              .into_iter()
+              .collect(),
+            winner: get_tie_winner(round, tie),
+          })
+          .collect::<Vec<_>>(),
+      )
+    } else if bracket.fixed {
+      let previous_ties = predicted_rounds
+        .last()
+        .and_then(|predicted_round| predicted_round.ties.as_ref());
+
+      previous_ties.map(|previous_ties| {
+        previous_ties
+          .iter()
+          .map(get_tie_winners)
+          .tuples()
+          .map(|(first_side, second_side)| PredictedTie {
+            first_side,
+            second_side,
+            winner: None,
+          })
+          .collect::<Vec<_>>()
+      })
+    } else {
+      None
+    };
+
+    let next_teams: BTreeSet<TeamId> = ties.as_ref().map_or_else(
+      || teams.clone(),
+      |ties| ties.iter().flat_map(get_tie_winners).collect(),
+    );
+    predicted_rounds.push(PredictedRound { teams, ties });
+    teams = next_teams;
+  }
+
+  (predicted_rounds, teams)
+}
+
+#[must_use]
+fn get_tie_winners(tie: &PredictedTie) -> BTreeSet<TeamId> {
+  tie.winner.as_ref().map_or_else(
+    || {
+      tie
+        .first_side
+        .union(&tie.second_side)
+        .map(Arc::clone)
+        .collect()
+    },
+    |winner| vec![Arc::clone(winner)].into_iter().collect(),
+  )
+}
+
+// NOTE: Aggregate score first, then away goals (if the rule applies), and
+// finally penalties in the last leg.
+#[must_use]
+fn get_tie_winner(round: &KnockoutRound, tie: &KnockoutTie) -> Option<TeamId> {
+  if tie.legs.len() < round.legs_count {
+    return None;
+  }
+
+  let (mut first_team_goals, mut second_team_goals) = (0, 0);
+  let (mut first_team_away_goals, mut second_team_away_goals) = (0, 0);
+  for (i, leg) in tie.legs.iter().enumerate() {
+    if i % 2 == 0 {
+      first_team_goals += leg.home_team_score;
+      second_team_goals += leg.away_team_score;
+      second_team_away_goals += leg.away_team_score;
+    } else {
+      first_team_goals += leg.away_team_score;
+      second_team_goals += leg.home_team_score;
+      first_team_away_goals += leg.away_team_score;
+    }
+  }
+
+  let (first_team_penalties, second_team_penalties) = tie
+    .legs
+    .last()
+    .and_then(|leg| leg.penalties)
+    .map_or((0, 0), |(home_penalties, away_penalties)| {
+      if tie.legs.len() % 2 == 1 {
+        (home_penalties, away_penalties)
+      } else {
+        (away_penalties, home_penalties)
+      }
+    });
+
+  let winner_ordering = [
+    (first_team_goals, second_team_goals),
+    if round.away_goals_rule && round.legs_count > 1 {
+      (first_team_away_goals, second_team_away_goals)
+    } else {
+      (0, 0)
+    },
+    (first_team_penalties, second_team_penalties),
+  ]
+  .into_iter()
+  .map(|(first_team_value, second_team_value)| {
+    first_team_value.cmp(&second_team_value)
+  })
+  .find(|ordering| ordering.is_ne())
+  .unwrap_or_else(|| panic!("Undecided tie ({:?}).", tie));
+
+  Some(Arc::clone(if winner_ordering.is_gt() {
+    &tie.first_team
+  } else {
+    &tie.second_team
+  }))
+}
+
+#[allow(clippy::too_many_lines)]
+pub(super) fn test() {
+  #[cfg(test)]
+  use pretty_assertions::assert_eq;
+
+  let team = |team_name: &str| Arc::new(String::from(team_name));
+  let teams = |teams_names: &[&str]| -> BTreeSet<TeamId> {
+    teams_names
+      .iter()
+      .map(|team_name| team(team_name))
+      .collect()
+  };
+  let leg = |home_team_score, away_team_score, penalties| KnockoutLeg {
+    home_team_score,
+    away_team_score,
+    penalties,
+  };
+  let get_bracket = |fixed: bool| {
+    KnockoutBracket::new(
+      &team("dummy-cup"),
+      vec![
+        KnockoutRound {
+          name: team("Quarter-finals"),
+          legs_count: 2,
+          away_goals_rule: true,
+          ties: vec![
+            // NOTE: a wins on aggregate.
+            KnockoutTie {
+              first_team: team("a"),
+              second_team: team("b"),
+              legs: vec![leg(2, 0, None), leg(1, 1, None)],
+            },
+            // NOTE: d wins on away goals.
+            KnockoutTie {
+              first_team: team("c"),
+              second_team: team("d"),
+              legs: vec![leg(1, 1, None), leg(0, 0, None)],
+            },
+            // NOTE: Second leg isn't played yet.
+            KnockoutTie {
+              first_team: team("e"),
+              second_team: team("f"),
+              legs: vec![leg(3, 0, None)],
+            },
+            // NOTE: h wins on penalties in the second leg (at its home).
+            KnockoutTie {
+              first_team: team("g"),
+              second_team: team("h"),
+              legs: vec![leg(1, 1, None), leg(1, 1, Some((5, 4)))],
+            },
+          ],
+        },
+        KnockoutRound {
+          name: team("Semi-finals"),
+          legs_count: 2,
+          away_goals_rule: true,
+          ties: vec![],
+        },
+        KnockoutRound {
+          name: team("Final"),
+          legs_count: 1,
+          away_goals_rule: false,
+          ties: vec![],
+        },
+      ],
+      fixed,
+    )
+  };
+  let eliminated = || {
+    vec![
+      (team("b"), KnockoutStatus::Eliminated),
+      (team("c"), KnockoutStatus::Eliminated),
+      (team("g"), KnockoutStatus::Eliminated),
+    ]
+  };
+
+  assert_eq!(
+    predict_knockout_bracket(&get_bracket(true)),
+    eliminated()
+      .into_iter()
+      .chain(vec![
+        (
+          team("a"),
+          KnockoutStatus::Alive(teams(&["d", "e", "f", "h"]))
+        ),
+        (
+          team("d"),
+          KnockoutStatus::Alive(teams(&["a", "e", "f", "h"]))
+        ),
+        (
+          team("e"),
+          KnockoutStatus::Alive(teams(&["a", "d", "f", "h"]))
+        ),
+        (
+          team("f"),
+          KnockoutStatus::Alive(teams(&["a", "d", "e", "h"]))
+        ),
+        (
+          team("h"),
+          KnockoutStatus::Alive(teams(&["a", "d", "e", "f"]))
+        ),
+      ])
+      .collect(),
+  );
+
+  // NOTE: With random draws a can meet e, f and h in the semi-finals too but
+  // e and f can only meet each other in the quarter-finals.
+  let predictions = predict_knockout_bracket(&get_bracket(false));
+  assert_eq!(
+    predictions[&team("a")],
+    KnockoutStatus::Alive(teams(&["d", "e", "f", "h"])),
+  );
+  assert_eq!(
+    predictions[&team("e")],
+    KnockoutStatus::Alive(teams(&["a", "d", "f", "h"])),
+  );
+
+  // NOTE: Once drawn, the semi-finals decide who can meet whom and the final
+  // decides the bracket.
+  let bracket = get_bracket(false);
+  let (name, fixed) = (Arc::clone(bracket.get_name()), bracket.is_fixed());
+  let mut rounds = bracket.into_rounds();
+  rounds[0].ties[2].legs.push(leg(0, 0, None));
+  rounds[1].ties = vec![
+    KnockoutTie {
+      first_team: team("a"),
+      second_team: team("e"),
+      legs: vec![leg(0, 1, None), leg(0, 0, None)],
+    },
+    KnockoutTie {
+      first_team: team("h"),
+      second_team: team("d"),
+      legs: vec![leg(0, 0, None)],
+    },
+  ];
+  let bracket = KnockoutBracket::new(&name, rounds, fixed);
+  assert_eq!(
+    predict_knockout_bracket(&bracket),
+    eliminated()
+      .into_iter()
+      .chain(vec![
+        (team("a"), KnockoutStatus::Eliminated),
+        (team("d"), KnockoutStatus::Alive(teams(&["e", "h"]))),
+        (team("e"), KnockoutStatus::Alive(teams(&["d", "h"]))),
+        (team("f"), KnockoutStatus::Eliminated),
+        (team("h"), KnockoutStatus::Alive(teams(&["d", "e"]))),
+      ])
+      .collect(),
+  );
+}
+
+#[cfg(test)]
+mod tests {
+  use super::test;
+
+  #[test]
+  fn test_tournament_knockout() {
+    test();
+  }
+}
@@ -3,6 +3,8 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use itertools::Itertools;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
 use crate::common::EliminationStatus;
 use crate::common::Team;
@@ -14,164 +16,193 @@ use crate::mincut_maxflow::common::FlowNode;
 
 /// # Panics
 #[must_use]
-#[allow(clippy::too_many_lines)]
 pub(super) fn predict_tournament_eliminated_teams(
   tournament: &Tournament,
 ) -> BTreeSet<Arc<Team>> {
+  // NOTE: Teams' predictions are independent and the output is still
+  // deterministic as it's collected into an ordered set.
+  #[cfg(feature = "parallel")]
+  let teams = tournament.teams.par_iter();
+  #[cfg(not(feature = "parallel"))]
+  let teams = tournament.teams.iter();
+
+  let teams_predictions: BTreeSet<Arc<Team>> = teams
+    .map(|team| -> Arc<Team> {
+      Arc::new(Team::with_elimination_status(
+        team,
+        &predict_team_elimination_status(tournament, team),
+      ))
+    })
+    .collect();
+
+  teams_predictions
+}
+
+/// Predicts a single team's elimination-status which only requires solving a
+/// single mincut-maxflow problem (at most).
+/// # Panics
+#[must_use]
+#[allow(clippy::too_many_lines)]
+pub(super) fn predict_team_elimination_status(
+  tournament: &Tournament,
+  team: &Team,
+) -> EliminationStatus {
   let source_node = FlowNode::source();
   let sink_node = FlowNode::sink();
 
-  let teams_predictions: BTreeSet<Arc<Team>> = tournament
+  match team.elimination_status {
+    None => {},
+    Some(_) => panic!("Team elimination-status already predicted"),
+  };
+
+  let possible_eliminating_teams: BTreeSet<&Arc<Team>> = tournament
     .teams
     .iter()
-    .map(|team| -> Arc<Team> {
-      match team.elimination_status {
-        None => {},
-        Some(_) => panic!("Team elimination-status already predicted"),
-      };
-
-      let possible_eliminating_teams: BTreeSet<&Arc<Team>> = tournament
-        .teams
-        .iter()
-        .filter(|&candidate_team| candidate_team.name != team.name)
-        .filter(|&candidate_team| {
-          let max_points = team.earned_points + team.remaining_points;
-          candidate_team.earned_points > max_points
-        })
-        .collect();
-
-      // NOTE: Can't remember why this special-case exists. It's probably for
-      // one of the following reasons:
-      // 1. The mincut-maxflow algorithm/implementation can't handle it.
-      // 2. Even more special-handling has to be done otherwise.
-      if !possible_eliminating_teams.is_empty() {
-        return Arc::new(Team::with_elimination_status(
-          team,
-          &EliminationStatus::Trivially(
-            possible_eliminating_teams
-              .into_iter()
-              .map(Arc::clone)
-              .collect(),
-          ),
-        ));
-      }
-
-      let other_teams: HashMap<Arc<FlowNode>, &Arc<Team>> = tournament
-        .teams
-        .iter()
-        .filter(|&possible_other_team| possible_other_team.name != team.name)
-        .map(|other_team| {
-          (Arc::new(FlowNode::new(&other_team.name)), other_team)
-        })
-        .collect();
-      let other_teams_nodes: Vec<&Arc<FlowNode>> = other_teams.keys().collect();
-      let teams_earned_points: HashMap<&Arc<FlowNode>, usize> = other_teams
-        .iter()
-        .map(|(node, t)| (node, t.earned_points))
-        .collect();
-
-      let other_teams_nodes_combinations: Vec<(
-        &Arc<FlowNode>,
-        &Arc<FlowNode>,
-      )> = other_teams_nodes
-        .iter()
-        .combinations(2)
-        .map(|nodes| (*nodes[0], *nodes[1]))
-        .collect();
-
-      let remaining_points = tournament
-        .remaining_points
-        .as_ref()
-        .map_or_else(|| panic!("Missing remaining-points"), |value| value);
-
-      let remaining_points_edges: Vec<FlowEdge> =
-        other_teams_nodes_combinations
-          .iter()
-          .map(|(node1, node2)| {
-            let (id1, id2) = (&node1.id, &node2.id);
-
-            FlowEdge::new(
-              &source_node,
-              &Arc::new(node1.join(node2)),
-              Flow::Regular(
-                *remaining_points
-                  .get(&(Arc::clone(id1), Arc::clone(id2)))
-                  .unwrap_or_else(|| {
-                    remaining_points
-                      .get(&(Arc::clone(id2), Arc::clone(id1)))
-                      .unwrap_or(&0)
-                  }),
-              ),
-            )
-          })
-          .collect();
-
-      let intermediate_edges =
-        other_teams_nodes_combinations
-          .iter()
-          .flat_map(|(node1, node2)| {
-            let from = Arc::new(node1.join(node2));
-            let capacity = Flow::Infinite;
+    .filter(|&candidate_team| candidate_team.name != team.name)
+    .filter(|&candidate_team| {
+      let max_points = get_max_points(team);
+      candidate_team.get_points() > max_points
+    })
+    .collect();
 
-            vec![
-              FlowEdge::new(&from, node1, capacity),
-              FlowEdge::new(&from, node2, capacity),
-            ]
-          });
-
-      let points_to_earn_edges: Vec<FlowEdge> = other_teams_nodes
-        .iter()
-        .map(|&other_team_node| {
-          let other_team_earned_points =
-            *teams_earned_points.get(other_team_node).unwrap();
-          let own_team_max_points = team.earned_points + team.remaining_points;
-          // NOTE: This case can't happen because otherwise the function would
-          // have returned earlier.
-          assert!(
-            other_team_earned_points <= own_team_max_points,
-            "Impossible case."
-          );
-          let capacity =
-            Flow::Regular(own_team_max_points - other_team_earned_points);
-
-          let from = other_team_node;
-          let to = &sink_node;
-          FlowEdge::new(from, to, capacity)
-        })
-        .collect();
-
-      let mut edges: Vec<FlowEdge> = Vec::new();
-      edges.extend(remaining_points_edges);
-      edges.extend(intermediate_edges);
-      edges.extend(points_to_earn_edges);
-
-      let mincut_maxflow =
-        calculate_mincut_maxflow(&edges, &source_node, &sink_node);
-
-      if mincut_maxflow.source_full {
-        return Arc::new(Team::with_elimination_status(
-          team,
-          &EliminationStatus::Not,
-        ));
-      }
-
-      let eliminating_teams = tournament
-        .teams
-        .iter()
-        .filter(|team| {
-          mincut_maxflow.mincut.contains(&FlowNode::new(&team.name))
-        })
+  // NOTE: Can't remember why this special-case exists. It's probably for
+  // one of the following reasons:
+  // 1. The mincut-maxflow algorithm/implementation can't handle it.
+  // 2. Even more special-handling has to be done otherwise.
+  if !possible_eliminating_teams.is_empty() {
+    return EliminationStatus::Trivially(
+      possible_eliminating_teams
+        .into_iter()
         .map(Arc::clone)
-        .collect();
+        .collect(),
+    );
+  }
 
-      Arc::new(Team::with_elimination_status(
-        team,
-        &EliminationStatus::NonTrivially(eliminating_teams),
-      ))
+  let other_teams: HashMap<Arc<FlowNode>, &Arc<Team>> = tournament
+    .teams
+    .iter()
+    .filter(|&possible_other_team| possible_other_team.name != team.name)
+    .map(|other_team| (Arc::new(FlowNode::new(&other_team.name)), other_team))
+    .collect();
+  let other_teams_nodes: Vec<&Arc<FlowNode>> = other_teams.keys().collect();
+  let teams_earned_points: HashMap<&Arc<FlowNode>, isize> = other_teams
+    .iter()
+    .map(|(node, t)| (node, t.get_points()))
+    .collect();
+
+  let other_teams_nodes_combinations: Vec<(&Arc<FlowNode>, &Arc<FlowNode>)> =
+    other_teams_nodes
+      .iter()
+      .combinations(2)
+      .map(|nodes| (*nodes[0], *nodes[1]))
+      .collect();
+
+  let remaining_points = tournament
+    .remaining_points
+    .as_ref()
+    .map_or_else(|| panic!("Missing remaining-points"), |value| value);
+
+  let remaining_points_edges: Vec<FlowEdge> = other_teams_nodes_combinations
+    .iter()
+    .map(|(node1, node2)| {
+      let (id1, id2) = (&node1.id, &node2.id);
+
+      FlowEdge::new(
+        &source_node,
+        &Arc::new(node1.join(node2)),
+        Flow::Regular(
+          *remaining_points
+            .get(&(Arc::clone(id1), Arc::clone(id2)))
+            .unwrap_or_else(|| {
+              remaining_points
+                .get(&(Arc::clone(id2), Arc::clone(id1)))
+                .unwrap_or(&0)
+            }),
+        ),
+      )
     })
     .collect();
 
-  teams_predictions
+  let intermediate_edges =
+    other_teams_nodes_combinations
+      .iter()
+      .flat_map(|(node1, node2)| {
+        let from = Arc::new(node1.join(node2));
+        let capacity = Flow::Infinite;
+
+        vec![
+          FlowEdge::new(&from, node1, capacity),
+          FlowEdge::new(&from, node2, capacity),
+        ]
+      });
+
+  let points_to_earn_edges: Vec<FlowEdge> = other_teams_nodes
+    .iter()
+    .map(|&other_team_node| {
+      let other_team_earned_points =
+        *teams_earned_points.get(other_team_node).unwrap();
+      let own_team_max_points = get_max_points(team);
+      // NOTE: This case can't happen because otherwise the function would
+      // have returned earlier.
+      assert!(
+        other_team_earned_points <= own_team_max_points,
+        "Impossible case."
+      );
+      let capacity = Flow::Regular(
+        (own_team_max_points - other_team_earned_points).unsigned_abs(),
+      );
+
+      let from = other_team_node;
+      let to = &sink_node;
+      FlowEdge::new(from, to, capacity)
+    })
+    .collect();
+
+  let mut edges: Vec<FlowEdge> = Vec::new();
+  edges.extend(remaining_points_edges);
+  edges.extend(intermediate_edges);
+  edges.extend(points_to_earn_edges);
+
+  let mincut_maxflow =
+    calculate_mincut_maxflow(&edges, &source_node, &sink_node);
+
+  if mincut_maxflow.source_full {
+    return EliminationStatus::Not;
+  }
+
+  let eliminating_teams = tournament
+    .teams
+    .iter()
+    .filter(|eliminating_team| {
+      mincut_maxflow
+        .mincut
+        .contains(&FlowNode::new(&eliminating_team.name))
+    })
+    .map(Arc::clone)
+    .collect();
+
+  EliminationStatus::NonTrivially(eliminating_teams)
+}
+
+#[must_use]
+pub(super) const fn is_eliminated(
+  elimination_status: &EliminationStatus,
+) -> bool {
+  match elimination_status {
+    EliminationStatus::Not => false,
+    EliminationStatus::Trivially(_) | EliminationStatus::NonTrivially(_) => {
+      true
+    },
+  }
+}
+
+// NOTE: The most points a team can end up with (i.e., by winning all of its
+// remaining matches).
+#[must_use]
+pub(super) fn get_max_points(team: &Team) -> isize {
+  team.get_points().saturating_add(
+    isize::try_from(team.remaining_points).unwrap_or(isize::MAX),
+  )
 }
 
 #[must_use]
@@ -577,6 +608,77 @@ pub(super) fn test() {
       predict_tournament_eliminated_teams(&tournament),
       expected_prediction
     );
+
+    // NOTE: Predictions (with their eliminating teams) survive a round-trip.
+    let predicted_tournament = Tournament::new(
+      &tournament.name,
+      expected_prediction,
+      tournament.remaining_points.clone(),
+    );
+    assert_eq!(
+      serde_json::from_str::<Tournament>(
+        &serde_json::to_string(&predicted_tournament).unwrap()
+      )
+      .unwrap(),
+      predicted_tournament,
+    );
+  }
+
+  let team = Team::new(
+    &Arc::new(String::from("boston")),
+    3,
+    69,
+    13,
+    69,
+    0,
+    0,
+    69,
+    13,
+    Some(EliminationStatus::Not),
+  );
+  let team_json = r#"{"name":"boston","rank":3,"matches_played":69,"matches_left":13,"matches_drawn":69,"matches_won":0,"matches_lost":0,"earned_points":69,"remaining_points":13,"points_adjustment":0,"elimination_status":{"kind":"not"}}"#;
+  assert_eq!(serde_json::to_string(&team).unwrap(), team_json);
+  assert_eq!(serde_json::from_str::<Team>(team_json).unwrap(), team);
+
+  // NOTE: Deserialization is validated like construction.
+  let team_error = |json: &str| serde_json::from_str::<Team>(json).unwrap_err();
+  let tournament_error =
+    |json: &str| serde_json::from_str::<Tournament>(json).unwrap_err();
+  for (error, expected_error) in [
+    (
+      team_error(
+        &team_json.replace(r#""matches_played":69"#, r#""matches_played":70"#),
+      ),
+      "Invalid matches-stats",
+    ),
+    (
+      team_error(&team_json.replace(
+        r#"{"kind":"not"}"#,
+        r#"{"kind":"trivially","eliminating_teams":[]}"#,
+      )),
+      "Invalid eliminating-teams count",
+    ),
+    (
+      tournament_error(&format!(
+        r#"{{"name":"A","teams":[{}],"remaining_points":null}}"#,
+        team_json,
+      )),
+      "Invalid no. of teams",
+    ),
+    (
+      tournament_error(&format!(
+        r#"{{"name":"A","teams":[{},{}],"remaining_points":null}}"#,
+        team_json,
+        team_json.replace(r#""rank":3"#, r#""rank":4"#),
+      )),
+      "Duplicate teams",
+    ),
+    (
+      team_error(&team_json.replace(r#""points_adjustment":0,"#, "")),
+      "missing field `points_adjustment`",
+    ),
+  ] {
+    assert!(error.to_string().starts_with(expected_error), "{}", error);
   }
 }
 
@@ -0,0 +1,411 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashSet;
+use std::marker::PhantomData;
+use std::ops::Range;
+use std::sync::Arc;
+
+use crate::common::CompetitionFormat;
+use crate::common::CompetitionSplit;
+use crate::common::Team;
+use crate::common::TeamId;
+use crate::tournament::adjustments::is_valid_adjustment;
+use crate::tournament::adjustments::Adjustment;
+use crate::tournament::fetching::common::postprocess_tournament_fixtures;
+use crate::tournament::fetching::common::postprocess_tournament_results;
+use crate::tournament::fetching::common::MatchResult;
+use crate::tournament::fetching::test_helper_stored_tournaments_results;
+use crate::tournament::prediction::is_eliminated;
+use crate::tournament::prediction::predict_team_elimination_status;
+use crate::tournament::prediction::predict_tournament_eliminated_teams;
+
+#[must_use]
+#[derive(Debug, Eq, PartialEq)]
+pub struct TournamentReplay {
+  pub name: Arc<String>,
+  pub matchdays: Vec<ReplayedMatchday>,
+  // NOTE: Values are indices into the replayed matches' results and point to
+  // the exact match after which a team got eliminated (or clinched).
+  pub eliminations: BTreeMap<TeamId, usize>,
+  pub clinch: Option<(TeamId, usize)>,
+  constructor_guard: PhantomData<()>,
+}
+
+#[must_use]
+#[derive(Debug, Eq, PartialEq)]
+pub struct ReplayedMatchday {
+  pub matches_results: Vec<MatchResult>,
+  pub teams: BTreeSet<Arc<Team>>,
+  pub clinching_team: Option<TeamId>,
+  constructor_guard: PhantomData<()>,
+}
+
+/// Replays a tournament's (chronologically ordered) matches' results one
+/// matchday at a time and predicts eliminations after each one. The
+/// competition-format and adjustments are applied to every intermediate
+/// tournament as when fetching. `None` is returned when any of the results or
+/// adjustments is invalid (e.g., a team playing itself).
+/// # Panics
+#[must_use]
+pub(super) fn replay_tournament(
+  tournament_name: &Arc<String>,
+  matches_results: &[MatchResult],
+  format: &CompetitionFormat,
+  adjustments: &[Adjustment],
+) -> Option<TournamentReplay> {
+  if !are_valid_matches_results(tournament_name, matches_results)
+    || !adjustments.iter().all(is_valid_adjustment)
+  {
+    return None;
+  }
+
+  // NOTE: All teams are included from the very first match as otherwise teams
+  // that haven't played yet would be missing and predictions would be done
+  // against an incomplete tournament.
+  let teams_names: BTreeSet<TeamId> = matches_results
+    .iter()
+    .flat_map(|((first_team_name, _), (second_team_name, _))| {
+      vec![Arc::clone(first_team_name), Arc::clone(second_team_name)]
+    })
+    .collect();
+  // NOTE: Checked here as building the tournament would otherwise panic.
+  if let Some(split) = &format.split {
+    if split.groups_sizes.iter().sum::<usize>() != teams_names.len() {
+      return None;
+    }
+  }
+  let get_tournament = |matches_count: usize| {
+    postprocess_tournament_fixtures(
+      tournament_name,
+      &teams_names,
+      &matches_results[..matches_count],
+      None,
+      format,
+      adjustments,
+    )
+  };
+
+  let mut matchdays: Vec<ReplayedMatchday> = Vec::new();
+  let mut eliminations: BTreeMap<TeamId, usize> = BTreeMap::new();
+  for matchday in get_matchdays(matches_results) {
+    let teams =
+      predict_tournament_eliminated_teams(&get_tournament(matchday.end));
+
+    // NOTE: Predicting whole matchdays is much cheaper than predicting every
+    // single match and we only go match by match (and team by team) within
+    // the matchday a team got eliminated in to find the exact match.
+    for team in &teams {
+      if eliminations.contains_key(&team.name)
+        || !is_eliminated(team.elimination_status.as_ref().unwrap())
+      {
+        continue;
+      }
+
+      let elimination_index = (matchday.start..matchday.end - 1)
+        .find(|&i| {
+          let tournament = get_tournament(i + 1);
+          let intermediate_team = tournament
+            .teams
+            .iter()
+            .find(|t| t.name == team.name)
+            .unwrap();
+          is_eliminated(&predict_team_elimination_status(
+            &tournament,
+            intermediate_team,
+          ))
+        })
+        .unwrap_or(matchday.end - 1);
+      eliminations.insert(Arc::clone(&team.name), elimination_index);
+    }
+
+    let clinching_team = get_clinching_team(&teams);
+    matchdays.push(ReplayedMatchday {
+      matches_results: matches_results[matchday].to_vec(),
+      teams,
+      clinching_team,
+      constructor_guard: PhantomData,
+    });
+  }
+
+  // NOTE: A team clinches exactly when the last of the other teams gets
+  // eliminated.
+  let clinch = matchdays
+    .iter()
+    .find_map(|matchday| matchday.clinching_team.as_ref())
+    .map(|clinching_team| {
+      let clinch_index = eliminations
+        .iter()
+        .filter(|(team_name, _)| *team_name != clinching_team)
+        .map(|(_, &elimination_index)| elimination_index)
+        .max()
+        .unwrap();
+
+      (Arc::clone(clinching_team), clinch_index)
+    });
+
+  Some(TournamentReplay {
+    name: Arc::clone(tournament_name),
+    matchdays,
+    eliminations,
+    clinch,
+    constructor_guard: PhantomData,
+  })
+}
+
+// NOTE: Matches' results are given by the caller (unlike fetched ones) so
+// they're checked against what building the tournament would otherwise panic
+// on.
+#[must_use]
+fn are_valid_matches_results(
+  tournament_name: &Arc<String>,
+  matches_results: &[MatchResult],
+) -> bool {
+  const NAME_LENGTH_MIN: usize = 1;
+  const NAME_LENGTH_MAX: usize = 100;
+  // NOTE: Teams are ranked and ranks can't exceed this (see `Team::new()`).
+  const TEAMS_COUNT_MAX: usize = 100;
+
+  let is_valid_name = |name: &Arc<String>| {
+    (NAME_LENGTH_MIN..=NAME_LENGTH_MAX).contains(&name.len())
+  };
+  let teams_names: HashSet<&TeamId> = matches_results
+    .iter()
+    .flat_map(|((first_team_name, _), (second_team_name, _))| {
+      [first_team_name, second_team_name]
+    })
+    .collect();
+
+  is_valid_name(tournament_name)
+    && teams_names.len() <= TEAMS_COUNT_MAX
+    && teams_names.iter().all(|team_name| is_valid_name(team_name))
+    && matches_results.iter().all(
+      |((first_team_name, _), (second_team_name, _))| {
+        first_team_name != second_team_name
+      },
+    )
+}
+
+// NOTE: Matchdays are inferred from the chronological order as the longest
+// runs of consecutive matches where no team plays twice. This is close enough
+// even with postponed matches and doesn't require matches' dates.
+#[must_use]
+fn get_matchdays(matches_results: &[MatchResult]) -> Vec<Range<usize>> {
+  let mut matchdays: Vec<Range<usize>> = Vec::new();
+  let mut matchday_teams_names: HashSet<&TeamId> = HashSet::new();
+  let mut matchday_start = 0;
+
+  for (i, ((first_team_name, _), (second_team_name, _))) in
+    matches_results.iter().enumerate()
+  {
+    if matchday_teams_names.contains(first_team_name)
+      || matchday_teams_names.contains(second_team_name)
+    {
+      matchdays.push(matchday_start..i);
+      matchday_teams_names.clear();
+      matchday_start = i;
+    }
+
+    matchday_teams_names.insert(first_team_name);
+    matchday_teams_names.insert(second_team_name);
+  }
+  if matchday_start < matches_results.len() {
+    matchdays.push(matchday_start..matches_results.len());
+  }
+
+  matchdays
+}
+
+// NOTE: A team clinches the tournament once every other team is eliminated as
+// no other team can even tie with it anymore.
+#[must_use]
+fn get_clinching_team(teams: &BTreeSet<Arc<Team>>) -> Option<TeamId> {
+  let non_eliminated_teams: Vec<&Arc<Team>> = teams
+    .iter()
+    .filter(|team| !is_eliminated(team.elimination_status.as_ref().unwrap()))
+    .collect();
+
+  match non_eliminated_teams.as_slice() {
+    [clinching_team] => Some(Arc::clone(&clinching_team.name)),
+    _ => None,
+  }
+}
+
+#[allow(clippy::too_many_lines)]
+pub(super) fn test() {
+  #[cfg(test)]
+  use pretty_assertions::assert_eq;
+
+  let tournament_name = Arc::new(String::from("dummy-tournament"));
+  let (first, second, third) = (
+    Arc::new(String::from("first")),
+    Arc::new(String::from("second")),
+    Arc::new(String::from("third")),
+  );
+  let matches_results: Vec<MatchResult> = vec![
+    ((Arc::clone(&first), 1), (Arc::clone(&second), 0)),
+    ((Arc::clone(&first), 2), (Arc::clone(&third), 0)),
+    ((Arc::clone(&second), 0), (Arc::clone(&first), 3)),
+    ((Arc::clone(&third), 0), (Arc::clone(&first), 1)),
+    ((Arc::clone(&second), 1), (Arc::clone(&third), 1)),
+  ];
+  let format = CompetitionFormat::default();
+
+  let replay =
+    replay_tournament(&tournament_name, &matches_results, &format, &[])
+      .unwrap();
+  assert_eq!(
+    replay
+      .matchdays
+      .iter()
+      .map(|matchday| matchday.matches_results.len())
+      .collect::<Vec<_>>(),
+    vec![1, 1, 1, 1, 1],
+  );
+  assert_eq!(
+    replay.eliminations,
+    vec![(Arc::clone(&second), 2), (Arc::clone(&third), 3)]
+      .into_iter()
+      .collect(),
+  );
+  assert_eq!(replay.clinch, Some((Arc::clone(&first), 3)));
+  assert_eq!(replay.matchdays[3].clinching_team, Some(Arc::clone(&first)));
+  assert_eq!(replay.matchdays[2].clinching_team, None);
+
+  // NOTE: The same results under a single round-robin leave no match for the
+  // others to catch up with the first.
+  let single_round_robin = CompetitionFormat::new(1, None);
+  let single_round_robin_replay = replay_tournament(
+    &tournament_name,
+    &[
+      ((Arc::clone(&first), 1), (Arc::clone(&second), 0)),
+      ((Arc::clone(&first), 2), (Arc::clone(&third), 0)),
+      ((Arc::clone(&second), 1), (Arc::clone(&third), 1)),
+    ],
+    &single_round_robin,
+    &[],
+  )
+  .unwrap();
+  assert_eq!(
+    single_round_robin_replay.eliminations,
+    vec![(Arc::clone(&second), 1), (Arc::clone(&third), 1)]
+      .into_iter()
+      .collect(),
+  );
+  assert_eq!(
+    single_round_robin_replay.clinch,
+    Some((Arc::clone(&first), 1))
+  );
+
+  // NOTE: Adjustments apply from the very first match.
+  let deduction = Adjustment::Points {
+    team_name: Arc::clone(&second),
+    points: -100,
+  };
+  assert_eq!(
+    replay_tournament(
+      &tournament_name,
+      &matches_results,
+      &format,
+      &[deduction],
+    )
+    .unwrap()
+    .eliminations,
+    vec![(Arc::clone(&second), 0), (Arc::clone(&third), 3)]
+      .into_iter()
+      .collect(),
+  );
+
+  // NOTE: Split groups have to add up to the teams.
+  assert_eq!(
+    replay_tournament(
+      &tournament_name,
+      &matches_results,
+      &CompetitionFormat::new(2, Some(CompetitionSplit::new(vec![2, 2], 1))),
+      &[],
+    ),
+    None,
+  );
+  assert_eq!(
+    replay_tournament(
+      &tournament_name,
+      &matches_results,
+      &format,
+      &[Adjustment::VoidedMatch {
+        home_team_name: Arc::clone(&first),
+        away_team_name: Arc::clone(&first),
+      }],
+    ),
+    None,
+  );
+
+  // NOTE: Invalid results are rejected instead of panicking.
+  for invalid_matches_results in [
+    vec![((Arc::clone(&first), 1), (Arc::clone(&first), 0))],
+    vec![((Arc::new(String::new()), 1), (Arc::clone(&second), 0))],
+  ] {
+    assert_eq!(
+      replay_tournament(
+        &tournament_name,
+        &invalid_matches_results,
+        &format,
+        &[]
+      ),
+      None,
+    );
+  }
+  assert_eq!(
+    replay_tournament(&Arc::new(String::new()), &matches_results, &format, &[]),
+    None,
+  );
+  assert_eq!(
+    replay_tournament(&tournament_name, &[], &format, &[])
+      .map(|replay| replay.matchdays),
+    Some(vec![]),
+  );
+
+  let (tournament_name, matches_results) =
+    test_helper_stored_tournaments_results()
+      .into_iter()
+      .next()
+      .unwrap();
+  let replay =
+    replay_tournament(&tournament_name, &matches_results, &format, &[])
+      .unwrap();
+  assert_eq!(
+    replay
+      .matchdays
+      .iter()
+      .map(|matchday| matchday.matches_results.len())
+      .collect::<Vec<_>>(),
+    vec![8, 8, 8, 6, 8, 8, 8, 7, 8, 7, 8, 7, 8, 2, 2, 7, 8, 8, 8, 8, 7, 7],
+  );
+  assert_eq!(
+    replay.eliminations,
+    vec![(Arc::new(String::from("Al Hazem")), 153)]
+      .into_iter()
+      .collect(),
+  );
+  assert_eq!(replay.clinch, None);
+  let last_matchday_teams = &replay.matchdays.last().unwrap().teams;
+  assert_eq!(
+    last_matchday_teams,
+    &predict_tournament_eliminated_teams(&postprocess_tournament_results(
+      &tournament_name,
+      &last_matchday_teams
+        .iter()
+        .map(|team| Arc::clone(&team.name))
+        .collect(),
+      &matches_results,
+    )),
+  );
+}
+
+#[cfg(test)]
+mod tests {
+  use super::test;
+
+  #[test]
+  fn test_tournament_replay() {
+    test();
+  }
+}
@@ -0,0 +1,237 @@
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::common::EliminationStatus;
+use crate::common::Team;
+use crate::common::TeamId;
+use crate::common::Tournament;
+use crate::tournament::fetching::common::postprocess_tournament_results;
+use crate::tournament::fetching::common::rank_teams;
+use crate::tournament::fetching::common::MatchResult;
+use crate::tournament::fetching::common::DRAW_FACTOR;
+use crate::tournament::fetching::common::HACKY_INTERMEDIATE_RANK;
+use crate::tournament::fetching::common::WIN_FACTOR;
+use crate::tournament::fetching::test_helper_stored_tournaments_results;
+use crate::tournament::predict_tournament;
+
+#[must_use]
+struct TeamStats {
+  matches_played: usize,
+  matches_left: usize,
+  matches_drawn: usize,
+  matches_won: usize,
+  matches_lost: usize,
+  earned_points: usize,
+  remaining_points: usize,
+  points_adjustment: isize,
+}
+
+/// Applies hypothetical matches' results (in order) to a tournament without
+/// any fetching. `None` is returned when any of the results can't happen
+/// (e.g., unknown teams or no remaining matches between them) or when the
+/// tournament's remaining-points are unknown.
+/// # Panics
+#[must_use]
+pub(super) fn apply_matches_results(
+  tournament: &Tournament,
+  matches_results: &[MatchResult],
+) -> Option<Tournament> {
+  let mut remaining_points: HashMap<(TeamId, TeamId), usize> =
+    tournament.remaining_points.clone()?;
+  let mut teams_stats: HashMap<&TeamId, TeamStats> = tournament
+    .teams
+    .iter()
+    .map(|team| {
+      (
+        &team.name,
+        TeamStats {
+          matches_played: team.matches_played,
+          matches_left: team.matches_left,
+          matches_drawn: team.matches_drawn,
+          matches_won: team.matches_won,
+          matches_lost: team.matches_lost,
+          earned_points: team.earned_points,
+          remaining_points: team.remaining_points,
+          points_adjustment: team.points_adjustment,
+        },
+      )
+    })
+    .collect();
+
+  for (
+    (first_team_name, first_team_score),
+    (second_team_name, second_team_score),
+  ) in matches_results
+  {
+    let pair = [
+      (Arc::clone(first_team_name), Arc::clone(second_team_name)),
+      (Arc::clone(second_team_name), Arc::clone(first_team_name)),
+    ]
+    .into_iter()
+    .find(|candidate_pair| remaining_points.contains_key(candidate_pair))?;
+    let pair_remaining_points = remaining_points.get_mut(&pair).unwrap();
+    if *pair_remaining_points < WIN_FACTOR {
+      return None;
+    }
+    *pair_remaining_points -= WIN_FACTOR;
+
+    for (team_name, ordering) in [
+      (first_team_name, first_team_score.cmp(second_team_score)),
+      (second_team_name, second_team_score.cmp(first_team_score)),
+    ] {
+      let stats = teams_stats.get_mut(team_name)?;
+      stats.matches_played += 1;
+      stats.matches_left -= 1;
+      stats.remaining_points -= WIN_FACTOR;
+
+      match ordering {
+        Ordering::Greater => {
+          stats.matches_won += 1;
+          stats.earned_points += WIN_FACTOR;
+        },
+        Ordering::Equal => {
+          stats.matches_drawn += 1;
+          stats.earned_points += DRAW_FACTOR;
+        },
+        Ordering::Less => {
+          stats.matches_lost += 1;
+        },
+      }
+    }
+  }
+
+  let teams = rank_teams(teams_stats.into_iter().map(|(team_name, stats)| {
+    Team::with_points_adjustment(
+      &Team::new(
+        team_name,
+        HACKY_INTERMEDIATE_RANK,
+        stats.matches_played,
+        stats.matches_left,
+        stats.matches_drawn,
+        stats.matches_won,
+        stats.matches_lost,
+        stats.earned_points,
+        stats.remaining_points,
+        None,
+      ),
+      stats.points_adjustment,
+    )
+  }));
+
+  Some(Tournament::new(
+    &tournament.name,
+    teams,
+    Some(remaining_points),
+  ))
+}
+
+pub(super) fn test() {
+  #[cfg(test)]
+  use pretty_assertions::assert_eq;
+
+  let (tournament_name, matches_results) =
+    test_helper_stored_tournaments_results()
+      .into_iter()
+      .next()
+      .unwrap();
+  let teams_names: BTreeSet<TeamId> = matches_results
+    .iter()
+    .flat_map(|((first_team_name, _), (second_team_name, _))| {
+      vec![Arc::clone(first_team_name), Arc::clone(second_team_name)]
+    })
+    .collect();
+  let tournament = postprocess_tournament_results(
+    &tournament_name,
+    &teams_names,
+    &matches_results,
+  );
+
+  let hypothetical_matches_results: Vec<MatchResult> = vec![
+    (
+      (Arc::new(String::from("Al Hilal")), 2),
+      (Arc::new(String::from("Al Nassr")), 1),
+    ),
+    (
+      (Arc::new(String::from("Al Hazem")), 0),
+      (Arc::new(String::from("Damac")), 0),
+    ),
+  ];
+  let all_matches_results: Vec<MatchResult> = matches_results
+    .iter()
+    .chain(&hypothetical_matches_results)
+    .cloned()
+    .collect();
+  assert_eq!(
+    apply_matches_results(&tournament, &hypothetical_matches_results),
+    Some(postprocess_tournament_results(
+      &tournament_name,
+      &teams_names,
+      &all_matches_results,
+    )),
+  );
+
+  let predicted_tournament = predict_tournament(
+    &apply_matches_results(&tournament, &hypothetical_matches_results).unwrap(),
+  );
+  let al_hilal = predicted_tournament
+    .teams
+    .iter()
+    .find(|team| *team.name == "Al Hilal")
+    .unwrap();
+  assert_eq!(
+    (
+      al_hilal.rank,
+      al_hilal.earned_points,
+      al_hilal.remaining_points
+    ),
+    (4, 34, 36),
+  );
+  assert_eq!(al_hilal.elimination_status, Some(EliminationStatus::Not));
+
+  // NOTE: Same team, unknown team and no remaining matches (only one match is
+  // left between Al Hilal and Al Nassr).
+  for impossible_matches_results in [
+    vec![(
+      (Arc::new(String::from("Al Hilal")), 1),
+      (Arc::new(String::from("Al Hilal")), 0),
+    )],
+    vec![(
+      (Arc::new(String::from("Al Hilal")), 1),
+      (Arc::new(String::from("Manchester City")), 0),
+    )],
+    vec![
+      (
+        (Arc::new(String::from("Al Hilal")), 1),
+        (Arc::new(String::from("Al Nassr")), 0),
+      ),
+      (
+        (Arc::new(String::from("Al Nassr")), 1),
+        (Arc::new(String::from("Al Hilal")), 0),
+      ),
+    ],
+  ] {
+    assert_eq!(
+      apply_matches_results(&tournament, &impossible_matches_results),
+      None,
+    );
+  }
+  assert_eq!(
+    apply_matches_results(
+      &Tournament::new(&tournament.name, tournament.teams.clone(), None),
+      &hypothetical_matches_results,
+    ),
+    None,
+  );
+}
+
+#[cfg(test)]
+mod tests {
+  use super::test;
+
+  #[test]
+  fn test_tournament_scenario() {
+    test();
+  }
+}
@@ -54,6 +54,9 @@ class _TeamNative extends Struct {
   @Uint64()
   // ignore: non_constant_identifier_names
   external int remaining_points;
+  @Int64()
+  // ignore: non_constant_identifier_names
+  external int points_adjustment;
 
   @Uint64()
   // ignore: non_constant_identifier_names
@@ -76,6 +79,7 @@ class Team {
     this.matchesLost,
     this.earnedPoints,
     this.remainingPoints,
+    this.pointsAdjustment,
     this.eliminationStatus,
     this.eliminatingTeams,
   );
@@ -90,6 +94,7 @@ class Team {
   int matchesLost;
   int earnedPoints;
   int remainingPoints;
+  int pointsAdjustment;
 
   int eliminationStatus;
   List<Team> eliminatingTeams;
@@ -112,6 +117,291 @@ final _BoaGetTournaments _boaGetTournaments = _boa
     )
     .asFunction();
 
+class _FetchFailureNative extends Struct {
+  // ignore: non_constant_identifier_names
+  external Pointer<Utf8> provider_name;
+  // ignore: non_constant_identifier_names
+  external Pointer<Utf8> tournament_name;
+  external Pointer<Utf8> message;
+}
+
+class FetchFailure {
+  FetchFailure(this.providerName, this.tournamentName, this.message);
+
+  String providerName;
+  String tournamentName;
+  String message;
+}
+
+class _DiscrepancyNative extends Struct {
+  // ignore: non_constant_identifier_names
+  external Pointer<Utf8> tournament_name;
+  external Pointer<Utf8> message;
+}
+
+class Discrepancy {
+  Discrepancy(this.tournamentName, this.message);
+
+  String tournamentName;
+  String message;
+}
+
+class _StaleTournamentNative extends Struct {
+  // ignore: non_constant_identifier_names
+  external Pointer<Utf8> provider_name;
+  // ignore: non_constant_identifier_names
+  external Pointer<Utf8> tournament_name;
+  @Int64()
+  // ignore: non_constant_identifier_names
+  external int fetched_at;
+  external Pointer<Utf8> message;
+}
+
+class StaleTournament {
+  StaleTournament(
+    this.providerName,
+    this.tournamentName,
+    this.fetchedAt,
+    this.message,
+  );
+
+  String providerName;
+  String tournamentName;
+  DateTime fetchedAt;
+  String message;
+}
+
+class _MatchResultNative extends Struct {
+  // ignore: non_constant_identifier_names
+  external Pointer<Utf8> first_team_name;
+  @Uint64()
+  // ignore: non_constant_identifier_names
+  external int first_team_score;
+  // ignore: non_constant_identifier_names
+  external Pointer<Utf8> second_team_name;
+  @Uint64()
+  // ignore: non_constant_identifier_names
+  external int second_team_score;
+}
+
+class MatchResult {
+  MatchResult(
+    this.firstTeamName,
+    this.firstTeamScore,
+    this.secondTeamName,
+    this.secondTeamScore,
+  );
+
+  String firstTeamName;
+  int firstTeamScore;
+  String secondTeamName;
+  int secondTeamScore;
+}
+
+// ignore: avoid_private_typedef_functions
+typedef _BoaPredictTournamentScenarioNative = Int32 Function(
+  Pointer<Utf8>,
+  Uint64,
+  Pointer<_MatchResultNative>,
+  Pointer<Uint64>,
+  Pointer<Pointer<_TournamentNative>>,
+);
+// ignore: avoid_private_typedef_functions
+typedef _BoaPredictTournamentScenario = int Function(
+  Pointer<Utf8>,
+  int,
+  Pointer<_MatchResultNative>,
+  Pointer<Uint64>,
+  Pointer<Pointer<_TournamentNative>>,
+);
+
+final _BoaPredictTournamentScenario _boaPredictTournamentScenario = _boa
+    .lookup<NativeFunction<_BoaPredictTournamentScenarioNative>>(
+      'boa_predict_tournament_scenario',
+    )
+    .asFunction();
+
+// ignore: avoid_private_typedef_functions
+typedef _BoaGetFetchFailuresNative = Int32 Function(
+  Pointer<Uint64>,
+  Pointer<Pointer<_FetchFailureNative>>,
+);
+// ignore: avoid_private_typedef_functions
+typedef _BoaGetFetchFailures = int Function(
+  Pointer<Uint64>,
+  Pointer<Pointer<_FetchFailureNative>>,
+);
+
+final _BoaGetFetchFailures _boaGetFetchFailures = _boa
+    .lookup<NativeFunction<_BoaGetFetchFailuresNative>>(
+      'boa_get_fetch_failures',
+    )
+    .asFunction();
+
+// ignore: avoid_private_typedef_functions
+typedef _BoaFreeFetchFailuresNative = Void Function(
+  Pointer<Pointer<_FetchFailureNative>>,
+);
+// ignore: avoid_private_typedef_functions
+typedef _BoaFreeFetchFailures = void Function(
+  Pointer<Pointer<_FetchFailureNative>>,
+);
+
+final _BoaFreeFetchFailures _boaFreeFetchFailures = _boa
+    .lookup<NativeFunction<_BoaFreeFetchFailuresNative>>(
+      'boa_free_fetch_failures',
+    )
+    .asFunction();
+
+// ignore: avoid_private_typedef_functions
+typedef _BoaGetDiscrepanciesNative = Int32 Function(
+  Pointer<Uint64>,
+  Pointer<Pointer<_DiscrepancyNative>>,
+);
+// ignore: avoid_private_typedef_functions
+typedef _BoaGetDiscrepancies = int Function(
+  Pointer<Uint64>,
+  Pointer<Pointer<_DiscrepancyNative>>,
+);
+
+final _BoaGetDiscrepancies _boaGetDiscrepancies = _boa
+    .lookup<NativeFunction<_BoaGetDiscrepanciesNative>>(
+      'boa_get_discrepancies',
+    )
+    .asFunction();
+
+// ignore: avoid_private_typedef_functions
+typedef _BoaFreeDiscrepanciesNative = Void Function(
+  Pointer<Pointer<_DiscrepancyNative>>,
+);
+// ignore: avoid_private_typedef_functions
+typedef _BoaFreeDiscrepancies = void Function(
+  Pointer<Pointer<_DiscrepancyNative>>,
+);
+
+final _BoaFreeDiscrepancies _boaFreeDiscrepancies = _boa
+    .lookup<NativeFunction<_BoaFreeDiscrepanciesNative>>(
+      'boa_free_discrepancies',
+    )
+    .asFunction();
+
+// ignore: avoid_private_typedef_functions
+typedef _BoaGetStaleTournamentsNative = Int32 Function(
+  Pointer<Uint64>,
+  Pointer<Pointer<_StaleTournamentNative>>,
+);
+// ignore: avoid_private_typedef_functions
+typedef _BoaGetStaleTournaments = int Function(
+  Pointer<Uint64>,
+  Pointer<Pointer<_StaleTournamentNative>>,
+);
+
+final _BoaGetStaleTournaments _boaGetStaleTournaments = _boa
+    .lookup<NativeFunction<_BoaGetStaleTournamentsNative>>(
+      'boa_get_stale_tournaments',
+    )
+    .asFunction();
+
+// ignore: avoid_private_typedef_functions
+typedef _BoaFreeStaleTournamentsNative = Void Function(
+  Pointer<Pointer<_StaleTournamentNative>>,
+);
+// ignore: avoid_private_typedef_functions
+typedef _BoaFreeStaleTournaments = void Function(
+  Pointer<Pointer<_StaleTournamentNative>>,
+);
+
+final _BoaFreeStaleTournaments _boaFreeStaleTournaments = _boa
+    .lookup<NativeFunction<_BoaFreeStaleTournamentsNative>>(
+      'boa_free_stale_tournaments',
+    )
+    .asFunction();
+
+// ignore: avoid_private_typedef_functions
+typedef _BoaSetThreadsCountNative = Int32 Function(Uint64);
+// ignore: avoid_private_typedef_functions
+typedef _BoaSetThreadsCount = int Function(int);
+
+final _BoaSetThreadsCount _boaSetThreadsCount = _boa
+    .lookup<NativeFunction<_BoaSetThreadsCountNative>>(
+      'boa_set_threads_count',
+    )
+    .asFunction();
+
+// ignore: avoid_private_typedef_functions
+typedef _BoaSetAdjustmentsFileNative = Int32 Function(Pointer<Utf8>);
+// ignore: avoid_private_typedef_functions
+typedef _BoaSetAdjustmentsFile = int Function(Pointer<Utf8>);
+
+final _BoaSetAdjustmentsFile _boaSetAdjustmentsFile = _boa
+    .lookup<NativeFunction<_BoaSetAdjustmentsFileNative>>(
+      'boa_set_adjustments_file',
+    )
+    .asFunction();
+
+// ignore: avoid_private_typedef_functions
+typedef _BoaSetProvidersConfigFileNative = Int32 Function(Pointer<Utf8>);
+// ignore: avoid_private_typedef_functions
+typedef _BoaSetProvidersConfigFile = int Function(Pointer<Utf8>);
+
+final _BoaSetProvidersConfigFile _boaSetProvidersConfigFile = _boa
+    .lookup<NativeFunction<_BoaSetProvidersConfigFileNative>>(
+      'boa_set_providers_config_file',
+    )
+    .asFunction();
+
+// ignore: avoid_private_typedef_functions
+typedef _BoaSetCacheDirNative = Int32 Function(Pointer<Utf8>);
+// ignore: avoid_private_typedef_functions
+typedef _BoaSetCacheDir = int Function(Pointer<Utf8>);
+
+final _BoaSetCacheDir _boaSetCacheDir = _boa
+    .lookup<NativeFunction<_BoaSetCacheDirNative>>(
+      'boa_set_cache_dir',
+    )
+    .asFunction();
+
+// ignore: avoid_private_typedef_functions
+typedef _BoaSetProviderEnabledNative = Int32 Function(Pointer<Utf8>, Int32);
+// ignore: avoid_private_typedef_functions
+typedef _BoaSetProviderEnabled = int Function(Pointer<Utf8>, int);
+
+final _BoaSetProviderEnabled _boaSetProviderEnabled = _boa
+    .lookup<NativeFunction<_BoaSetProviderEnabledNative>>(
+      'boa_set_provider_enabled',
+    )
+    .asFunction();
+
+// ignore: avoid_private_typedef_functions
+typedef _BoaGetTeamDisplayNameNative = Int32 Function(
+  Pointer<Utf8>,
+  Pointer<Utf8>,
+  Pointer<Pointer<Utf8>>,
+);
+// ignore: avoid_private_typedef_functions
+typedef _BoaGetTeamDisplayName = int Function(
+  Pointer<Utf8>,
+  Pointer<Utf8>,
+  Pointer<Pointer<Utf8>>,
+);
+
+final _BoaGetTeamDisplayName _boaGetTeamDisplayName = _boa
+    .lookup<NativeFunction<_BoaGetTeamDisplayNameNative>>(
+      'boa_get_team_display_name',
+    )
+    .asFunction();
+
+// ignore: avoid_private_typedef_functions
+typedef _BoaFreeTeamDisplayNameNative = Void Function(Pointer<Pointer<Utf8>>);
+// ignore: avoid_private_typedef_functions
+typedef _BoaFreeTeamDisplayName = void Function(Pointer<Pointer<Utf8>>);
+
+final _BoaFreeTeamDisplayName _boaFreeTeamDisplayName = _boa
+    .lookup<NativeFunction<_BoaFreeTeamDisplayNameNative>>(
+      'boa_free_team_display_name',
+    )
+    .asFunction();
+
 // ignore: avoid_private_typedef_functions
 typedef _BoaFreeTournamentsNative = Void Function(
   Pointer<Pointer<_TournamentNative>>,
@@ -127,6 +417,75 @@ final _BoaFreeTournaments _boaFreeTournaments = _boa
     )
     .asFunction();
 
+// NOTE: Only works once and before any prediction and only when boa is built
+// with the `parallel` feature.
+bool setThreadsCount(final int threadsCount) =>
+    _boaSetThreadsCount(threadsCount) == 0;
+
+// NOTE: Adjustments (e.g., point deductions) are applied to tournaments fetched
+// afterwards.
+bool setAdjustmentsFile(final String path) {
+  final pathNative = path.toNativeUtf8();
+  final statusCode = _boaSetAdjustmentsFile(pathNative);
+  malloc.free(pathNative);
+
+  return statusCode == 0;
+}
+
+// NOTE: The config (which competitions and seasons are fetched) is used by
+// `getTournaments` afterwards.
+bool setProvidersConfigFile(final String path) {
+  final pathNative = path.toNativeUtf8();
+  final statusCode = _boaSetProvidersConfigFile(pathNative);
+  malloc.free(pathNative);
+
+  return statusCode == 0;
+}
+
+// NOTE: Should be the app's cache directory (e.g., from `path_provider`) as
+// nothing is cached otherwise.
+bool setCacheDir(final String path) {
+  final pathNative = path.toNativeUtf8();
+  final statusCode = _boaSetCacheDir(pathNative);
+  malloc.free(pathNative);
+
+  return statusCode == 0;
+}
+
+// NOTE: Providers (e.g., `Koora`) are enabled or disabled for `getTournaments`
+// regardless of the providers' config.
+bool setProviderEnabled(final String providerName, final bool isEnabled) {
+  final providerNameNative = providerName.toNativeUtf8();
+  final statusCode = _boaSetProviderEnabled(
+    providerNameNative,
+    isEnabled ? 1 : 0,
+  );
+  malloc.free(providerNameNative);
+
+  return statusCode == 0;
+}
+
+// NOTE: Falls back to the team's name (e.g., for unregistered teams or
+// locales).
+String getTeamDisplayName(final String teamName, final String locale) {
+  final teamNameNative = teamName.toNativeUtf8();
+  final localeNative = locale.toNativeUtf8();
+  final displayNameNative = calloc.allocate<Pointer<Utf8>>(
+    sizeOf<Pointer<Utf8>>(),
+  );
+
+  _boaGetTeamDisplayName(teamNameNative, localeNative, displayNameNative);
+  final displayName = displayNameNative.value.toDartString();
+
+  _boaFreeTeamDisplayName(displayNameNative);
+  calloc.free(displayNameNative);
+  malloc
+    ..free(teamNameNative)
+    ..free(localeNative);
+
+  return displayName;
+}
+
 List<Tournament> getTournaments() {
   final tournamentsCountNative = calloc.allocate<Uint64>(sizeOf<Uint64>());
   final tournamentsNative = calloc.allocate<Pointer<_TournamentNative>>(
@@ -137,6 +496,186 @@ List<Tournament> getTournaments() {
     tournamentsCountNative,
     tournamentsNative,
   );
+
+  final tournaments = _doTournaments(
+    statusCode,
+    tournamentsCountNative,
+    tournamentsNative,
+  );
+
+  calloc
+    ..free(tournamentsCountNative)
+    ..free(tournamentsNative);
+
+  return tournaments;
+}
+
+// NOTE: Tournaments that couldn't be fetched by the last `getTournaments` (the
+// others are still returned by it).
+List<FetchFailure> getFetchFailures() {
+  final failuresCountNative = calloc.allocate<Uint64>(sizeOf<Uint64>());
+  final failuresNative = calloc.allocate<Pointer<_FetchFailureNative>>(
+    sizeOf<Pointer<_FetchFailureNative>>(),
+  );
+
+  final statusCode = _boaGetFetchFailures(failuresCountNative, failuresNative);
+  final count = statusCode == 0 ? failuresCountNative.value : 0;
+
+  final failures = <FetchFailure>[];
+  for (var i = 0; i < count; i++) {
+    final failureNative = failuresNative.value[i];
+    failures.add(
+      FetchFailure(
+        failureNative.provider_name.toDartString(),
+        failureNative.tournament_name.toDartString(),
+        failureNative.message.toDartString(),
+      ),
+    );
+  }
+
+  if (statusCode == 0) {
+    _boaFreeFetchFailures(failuresNative);
+  }
+  calloc
+    ..free(failuresCountNative)
+    ..free(failuresNative);
+
+  return failures;
+}
+
+// NOTE: Providers' disagreements about tournaments fetched by more than one
+// provider in the last `getTournaments` (only the primary provider is used).
+List<Discrepancy> getDiscrepancies() {
+  final discrepanciesCountNative = calloc.allocate<Uint64>(sizeOf<Uint64>());
+  final discrepanciesNative = calloc.allocate<Pointer<_DiscrepancyNative>>(
+    sizeOf<Pointer<_DiscrepancyNative>>(),
+  );
+
+  final statusCode = _boaGetDiscrepancies(
+    discrepanciesCountNative,
+    discrepanciesNative,
+  );
+  final count = statusCode == 0 ? discrepanciesCountNative.value : 0;
+
+  final discrepancies = <Discrepancy>[];
+  for (var i = 0; i < count; i++) {
+    final discrepancyNative = discrepanciesNative.value[i];
+    discrepancies.add(
+      Discrepancy(
+        discrepancyNative.tournament_name.toDartString(),
+        discrepancyNative.message.toDartString(),
+      ),
+    );
+  }
+
+  if (statusCode == 0) {
+    _boaFreeDiscrepancies(discrepanciesNative);
+  }
+  calloc
+    ..free(discrepanciesCountNative)
+    ..free(discrepanciesNative);
+
+  return discrepancies;
+}
+
+// NOTE: Tournaments that couldn't be refreshed by the last `getTournaments` so
+// their cached data (as fetched at `fetchedAt`) was used instead.
+List<StaleTournament> getStaleTournaments() {
+  final staleTournamentsCountNative = calloc.allocate<Uint64>(
+    sizeOf<Uint64>(),
+  );
+  final staleTournamentsNative =
+      calloc.allocate<Pointer<_StaleTournamentNative>>(
+    sizeOf<Pointer<_StaleTournamentNative>>(),
+  );
+
+  final statusCode = _boaGetStaleTournaments(
+    staleTournamentsCountNative,
+    staleTournamentsNative,
+  );
+  final count = statusCode == 0 ? staleTournamentsCountNative.value : 0;
+
+  final staleTournaments = <StaleTournament>[];
+  for (var i = 0; i < count; i++) {
+    final staleTournamentNative = staleTournamentsNative.value[i];
+    staleTournaments.add(
+      StaleTournament(
+        staleTournamentNative.provider_name.toDartString(),
+        staleTournamentNative.tournament_name.toDartString(),
+        DateTime.fromMillisecondsSinceEpoch(
+          staleTournamentNative.fetched_at * 1000,
+          isUtc: true,
+        ),
+        staleTournamentNative.message.toDartString(),
+      ),
+    );
+  }
+
+  if (statusCode == 0) {
+    _boaFreeStaleTournaments(staleTournamentsNative);
+  }
+  calloc
+    ..free(staleTournamentsCountNative)
+    ..free(staleTournamentsNative);
+
+  return staleTournaments;
+}
+
+// NOTE: The tournament has to be already fetched by `getTournaments` and
+// `null` is returned if it wasn't or if any of the results can't happen.
+Tournament? predictTournamentScenario(
+  final String tournamentName,
+  final List<MatchResult> matchesResults,
+) {
+  final tournamentNameNative = tournamentName.toNativeUtf8();
+  final matchesResultsNative = calloc.allocate<_MatchResultNative>(
+    sizeOf<_MatchResultNative>() * matchesResults.length,
+  );
+  for (var i = 0; i < matchesResults.length; i++) {
+    matchesResultsNative[i]
+      ..first_team_name = matchesResults[i].firstTeamName.toNativeUtf8()
+      ..first_team_score = matchesResults[i].firstTeamScore
+      ..second_team_name = matchesResults[i].secondTeamName.toNativeUtf8()
+      ..second_team_score = matchesResults[i].secondTeamScore;
+  }
+  final tournamentsCountNative = calloc.allocate<Uint64>(sizeOf<Uint64>());
+  final tournamentsNative = calloc.allocate<Pointer<_TournamentNative>>(
+    sizeOf<Pointer<_TournamentNative>>(),
+  );
+
+  final statusCode = _boaPredictTournamentScenario(
+    tournamentNameNative,
+    matchesResults.length,
+    matchesResultsNative,
+    tournamentsCountNative,
+    tournamentsNative,
+  );
+
+  final tournaments = _doTournaments(
+    statusCode,
+    tournamentsCountNative,
+    tournamentsNative,
+  );
+
+  for (var i = 0; i < matchesResults.length; i++) {
+    malloc
+      ..free(matchesResultsNative[i].first_team_name)
+      ..free(matchesResultsNative[i].second_team_name);
+  }
+  malloc.free(tournamentNameNative);
+  calloc
+    ..free(matchesResultsNative)
+    ..free(tournamentsCountNative)
+    ..free(tournamentsNative);
+
+  return tournaments.isEmpty ? null : tournaments.first;
+}
+
+List<Tournament> _doTournaments(
+  final int statusCode,
+  final Pointer<Uint64> tournamentsCountNative,
+  final Pointer<Pointer<_TournamentNative>> tournamentsNative,
+) {
   // FIXME: Better end-to-end error-handling (i.e: from boa to UI).
   final count = statusCode == 0 ? tournamentsCountNative.value : 0;
 
@@ -152,11 +691,9 @@ List<Tournament> getTournaments() {
     tournaments.add(Tournament(tournamentNative.name.toDartString(), teams));
   }
 
-  _boaFreeTournaments(tournamentsNative);
-
-  calloc
-    ..free(tournamentsCountNative)
-    ..free(tournamentsNative);
+  if (statusCode == 0) {
+    _boaFreeTournaments(tournamentsNative);
+  }
 
   return tournaments;
 }
@@ -177,6 +714,7 @@ Team _doTeam(final _TeamNative teamNative) {
     teamNative.matches_lost,
     teamNative.earned_points,
     teamNative.remaining_points,
+    teamNative.points_adjustment,
     teamNative.elimination_status,
     eliminatingTeams,
   );
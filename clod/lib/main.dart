@@ -136,7 +136,12 @@ class _TeamsWidget extends StatelessWidget {
                               DataCell(Text(team.matchesDrawn.toString())),
                               DataCell(Text(team.matchesWon.toString())),
                               DataCell(Text(team.matchesLost.toString())),
-                              DataCell(Text(team.earnedPoints.toString())),
+                              DataCell(
+                                Text(
+                                  (team.earnedPoints + team.pointsAdjustment)
+                                      .toString(),
+                                ),
+                              ),
                               DataCell(Text(team.remainingPoints.toString())),
                               DataCell(Text(team.eliminationStatus.toString())),
                               DataCell(
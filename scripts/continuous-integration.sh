@@ -199,6 +199,10 @@ echo "Building & testing boa (release for host)..." >&2
 boa-cargo-build --release
 boa-cargo-test --release
 
+echo "Building & testing boa (release for host with parallel feature)..." >&2
+boa-cargo-build --release --features parallel
+boa-cargo-test --release --features parallel
+
 unset RUST_BACKTRACE
 
 echo "Linting boa..." >&2
@@ -213,7 +217,7 @@ echo "Linting boa..." >&2
 # dependencies and we have little control over them.
 # 2. `double-must-use`: Because it requires global knowledge and ensuring that
 # only a single must-use is used. Which is just an invitation for errors.
-boa-cargo clippy --quiet -- \
+boa-cargo clippy --quiet --all-features -- \
   -D warnings \
   \
   -W clippy::all \